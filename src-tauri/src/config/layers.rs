@@ -0,0 +1,329 @@
+// 🧩 Config layers — defaults < system < user < environment < CLI
+//
+// The effective AppConfig is built by overlaying, in order:
+//   1. built-in defaults
+//   2. the admin-managed system file (e.g. /etc/gignaati/config.json)
+//   3. the per-user file (`config_path()`)
+//   4. `GIGNAATI_<FIELD>` environment variables
+//   5. `--<field>=<value>` command-line flags
+// Fields listed in the system file's `"locked"` array keep the system value
+// regardless of later layers, and cannot be changed through `update`.
+// Everything but the user file is read once per process; a changed system
+// file takes effect on the next start.
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use super::AppConfig;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Source {
+    Default,
+    System,
+    User,
+    Env,
+    Cli,
+}
+
+/// Where one effective value came from.
+#[derive(Serialize, Debug, Clone)]
+pub struct FieldSource {
+    pub field: String,
+    pub value: Value,
+    pub source: Source,
+    /// File path, environment variable or flag that supplied the value.
+    pub origin: Option<String>,
+    pub locked: bool,
+}
+
+/// Result of overlaying every layer onto the user document.
+pub struct Resolved {
+    pub doc: Map<String, Value>,
+    pub sources: BTreeMap<String, FieldSource>,
+}
+
+static OVERLAYS: Lazy<Overlays> = Lazy::new(Overlays::read);
+
+/// Admin-managed, machine-wide config file.
+pub fn system_config_path() -> PathBuf {
+    if cfg!(target_os = "windows") {
+        env::var("PROGRAMDATA")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from(r"C:\ProgramData"))
+            .join("Gignaati")
+            .join("config.json")
+    } else if cfg!(target_os = "macos") {
+        PathBuf::from("/Library/Application Support/Gignaati/config.json")
+    } else {
+        PathBuf::from("/etc/gignaati/config.json")
+    }
+}
+
+/// Fields the administrator has locked in the system file.
+pub fn locked_fields() -> BTreeSet<String> {
+    OVERLAYS.locked.clone()
+}
+
+/// The layers around the user file: the system file and the environment /
+/// CLI overrides (already coerced to JSON values).
+pub(super) struct Overlays {
+    pub(super) system: Map<String, Value>,
+    pub(super) locked: BTreeSet<String>,
+    pub(super) system_origin: String,
+    /// (field, value, origin, source), in precedence order.
+    pub(super) overrides: Vec<(String, Value, String, Source)>,
+}
+
+impl Overlays {
+    fn read() -> Self {
+        let fields = field_names();
+        let (system, locked) = read_system();
+        let overrides = env_overrides(&fields)
+            .into_iter()
+            .chain(cli_overrides(&fields))
+            .filter_map(|(field, raw, origin, source)| match coerce(&field, &raw) {
+                Some(value) => Some((field, value, origin, source)),
+                None => {
+                    eprintln!("⚠ Ignoring {}: '{}' is not a valid value for {}", origin, raw, field);
+                    None
+                }
+            })
+            .collect();
+        Overlays {
+            system,
+            locked,
+            system_origin: system_config_path().to_string_lossy().to_string(),
+            overrides,
+        }
+    }
+}
+
+fn defaults_document() -> Map<String, Value> {
+    match serde_json::to_value(AppConfig::defaults()) {
+        Ok(Value::Object(map)) => map,
+        _ => Map::new(),
+    }
+}
+
+fn field_names() -> Vec<String> {
+    defaults_document().keys().cloned().collect()
+}
+
+/// Overlay defaults, system, `user`, environment and CLI layers.
+pub fn resolve(user: &Map<String, Value>) -> Resolved {
+    resolve_with(user, &OVERLAYS)
+}
+
+pub(super) fn resolve_with(user: &Map<String, Value>, overlays: &Overlays) -> Resolved {
+    let defaults = defaults_document();
+    let fields: Vec<String> = defaults.keys().cloned().collect();
+    let locked = &overlays.locked;
+
+    let mut doc = Map::new();
+    let mut sources = BTreeMap::new();
+    let mut set = |field: &str, value: Value, source: Source, origin: Option<String>| {
+        doc.insert(field.to_string(), value.clone());
+        sources.insert(
+            field.to_string(),
+            FieldSource {
+                field: field.to_string(),
+                value,
+                source,
+                origin,
+                locked: false,
+            },
+        );
+    };
+
+    for (field, value) in &defaults {
+        set(field, value.clone(), Source::Default, None);
+    }
+
+    for (field, value) in overlays.system.iter().filter(|(f, _)| fields.contains(f)) {
+        set(field, value.clone(), Source::System, Some(overlays.system_origin.clone()));
+    }
+
+    let user_origin = super::config_path().to_string_lossy().to_string();
+    for (field, value) in user.iter().filter(|(f, _)| fields.contains(f)) {
+        if !locked.contains(field) {
+            set(field, value.clone(), Source::User, Some(user_origin.clone()));
+        }
+    }
+
+    for (field, value, origin, source) in &overlays.overrides {
+        if !locked.contains(field) {
+            set(field, value.clone(), *source, Some(origin.clone()));
+        }
+    }
+
+    for field in locked {
+        if let Some(s) = sources.get_mut(field) {
+            s.locked = true;
+        }
+    }
+
+    Resolved { doc, sources }
+}
+
+/// System file as a flat object plus an optional `"locked": [..]` array.
+fn read_system() -> (Map<String, Value>, BTreeSet<String>) {
+    let path = system_config_path();
+    let Ok(data) = fs::read_to_string(&path) else {
+        return (Map::new(), BTreeSet::new());
+    };
+    let mut map = match serde_json::from_str::<Value>(&data) {
+        Ok(Value::Object(map)) => map,
+        _ => {
+            eprintln!("⚠ Ignoring unreadable system config '{}'", path.display());
+            return (Map::new(), BTreeSet::new());
+        }
+    };
+
+    let locked = match map.remove("locked") {
+        Some(Value::Array(items)) => items
+            .into_iter()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect(),
+        _ => BTreeSet::new(),
+    };
+    (map, locked)
+}
+
+/// `GIGNAATI_N8N_PORT=5679` → (`n8n_port`, `5679`).
+fn env_overrides(fields: &[String]) -> Vec<(String, String, String, Source)> {
+    fields
+        .iter()
+        .filter_map(|field| {
+            let var = format!("GIGNAATI_{}", field.to_uppercase());
+            env::var(&var)
+                .ok()
+                .map(|raw| (field.clone(), raw, var, Source::Env))
+        })
+        .collect()
+}
+
+/// `--n8n-port=5679` or `--n8n-port 5679` → (`n8n_port`, `5679`).
+fn cli_overrides(fields: &[String]) -> Vec<(String, String, String, Source)> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut overrides = Vec::new();
+
+    for field in fields {
+        let flag = format!("--{}", field.replace('_', "-"));
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            let raw = if let Some(value) = arg.strip_prefix(&format!("{}=", flag)) {
+                Some(value.to_string())
+            } else if *arg == flag {
+                iter.next().cloned()
+            } else {
+                None
+            };
+            if let Some(raw) = raw {
+                overrides.push((field.clone(), raw, flag.clone(), Source::Cli));
+            }
+        }
+    }
+    overrides
+}
+
+/// Turn a raw string into a JSON value the field accepts: tried as JSON first
+/// (numbers, booleans, objects), then as a plain string.
+fn coerce(field: &str, raw: &str) -> Option<Value> {
+    let accepts = |value: &Value| {
+        let mut probe = defaults_document();
+        probe.insert(field.to_string(), value.clone());
+        serde_json::from_value::<AppConfig>(Value::Object(probe)).is_ok()
+    };
+
+    serde_json::from_str::<Value>(raw)
+        .ok()
+        .filter(accepts)
+        .or_else(|| Some(Value::String(raw.to_string())).filter(accepts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn overlays(system: Value, locked: &[&str], overrides: Vec<(&str, Value, Source)>) -> Overlays {
+        Overlays {
+            system: system.as_object().cloned().unwrap_or_default(),
+            locked: locked.iter().map(|f| f.to_string()).collect(),
+            system_origin: "/etc/gignaati/config.json".into(),
+            overrides: overrides
+                .into_iter()
+                .map(|(field, value, source)| (field.to_string(), value, format!("--{}", field), source))
+                .collect(),
+        }
+    }
+
+    fn user(doc: Value) -> Map<String, Value> {
+        doc.as_object().cloned().unwrap()
+    }
+
+    #[test]
+    fn coerce_parses_json_first_then_falls_back_to_strings() {
+        assert_eq!(coerce("n8n_port", "5679"), Some(json!(5679)));
+        assert_eq!(coerce("n8n_use_existing", "true"), Some(json!(true)));
+        // Looks like a number, but the field holds a string
+        assert_eq!(coerce("n8n_pinned_version", "1.2"), Some(json!("1.2")));
+        assert_eq!(
+            coerce("preferred_binaries", r#"{"node":"/opt/node/bin/node"}"#),
+            Some(json!({ "node": "/opt/node/bin/node" }))
+        );
+    }
+
+    #[test]
+    fn coerce_rejects_values_the_field_cannot_hold() {
+        assert_eq!(coerce("n8n_port", "not-a-port"), None);
+        assert_eq!(coerce("n8n_port", "70000"), None);
+        assert_eq!(coerce("n8n_use_existing", "maybe"), None);
+    }
+
+    #[test]
+    fn later_layers_win() {
+        let layers = overlays(
+            json!({ "n8n_port": 6000, "ollama_port": 12000 }),
+            &[],
+            vec![("ollama_port", json!(13000), Source::Env)],
+        );
+        let resolved = resolve_with(&user(json!({ "n8n_port": 7000 })), &layers);
+
+        assert_eq!(resolved.doc["n8n_port"], json!(7000));
+        assert_eq!(resolved.sources["n8n_port"].source, Source::User);
+        assert_eq!(resolved.doc["ollama_port"], json!(13000));
+        assert_eq!(resolved.sources["ollama_port"].source, Source::Env);
+        assert_eq!(resolved.sources["ollama_port"].origin.as_deref(), Some("--ollama_port"));
+        assert_eq!(resolved.sources["n8n_installed"].source, Source::Default);
+    }
+
+    #[test]
+    fn locked_fields_keep_the_system_value() {
+        let layers = overlays(
+            json!({ "npm_registry": "https://npm.corp.example" }),
+            &["npm_registry"],
+            vec![("npm_registry", json!("https://evil.example"), Source::Cli)],
+        );
+        let resolved = resolve_with(&user(json!({ "npm_registry": "https://mine.example" })), &layers);
+
+        let field = &resolved.sources["npm_registry"];
+        assert_eq!(field.value, json!("https://npm.corp.example"));
+        assert_eq!(field.source, Source::System);
+        assert!(field.locked);
+    }
+
+    #[test]
+    fn unknown_fields_are_ignored() {
+        let layers = overlays(json!({ "no_such_field": 1 }), &[], Vec::new());
+        let resolved = resolve_with(&user(json!({ "also_unknown": 2 })), &layers);
+        assert!(!resolved.doc.contains_key("no_such_field"));
+        assert!(!resolved.doc.contains_key("also_unknown"));
+    }
+}
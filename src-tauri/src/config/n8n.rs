@@ -0,0 +1,499 @@
+// 🧩 n8n runtime settings — the `n8n` section of AppConfig
+//
+// Typed settings map onto n8n's environment variables. Free-form `extra_env`
+// and `secrets` cover everything else; their keys are checked against the
+// variable families n8n actually reads, and secrets are never shown unredacted.
+
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use super::ConfigIssue;
+
+pub(crate) const REDACTED: &str = "••••••••";
+
+/// Prefixes / names of environment variables n8n is known to read.
+const KNOWN_PREFIXES: &[&str] = &[
+    "N8N_",
+    "DB_",
+    "EXECUTIONS_",
+    "QUEUE_",
+    "WEBHOOK_",
+    "GENERIC_",
+    "NODES_",
+    "NODE_FUNCTION_",
+    "CREDENTIALS_",
+    "EXTERNAL_",
+    "WORKFLOWS_",
+    "CODE_",
+    "OLLAMA_",
+];
+const KNOWN_NAMES: &[&str] = &["TZ", "NODE_OPTIONS", "NODE_ENV", "VUE_APP_URL_BASE_API"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct BasicAuth {
+    pub user: String,
+    pub password: String,
+}
+
+/// Where n8n keeps workflows, credentials and executions.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum N8nDatabase {
+    /// n8n's built-in SQLite file under the user folder.
+    #[default]
+    Sqlite,
+    /// External or locally run PostgreSQL (DB_TYPE=postgresdb).
+    Postgres(PostgresSettings),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct PostgresSettings {
+    pub host: String,
+    pub port: u16,
+    pub database: String,
+    pub user: String,
+    /// Treated as a secret.
+    pub password: String,
+    pub schema: String,
+    /// DB_POSTGRESDB_SSL_ENABLED.
+    pub ssl: bool,
+}
+
+impl Default for PostgresSettings {
+    fn default() -> Self {
+        PostgresSettings {
+            host: "localhost".into(),
+            port: 5432,
+            database: "n8n".into(),
+            user: "n8n".into(),
+            password: String::new(),
+            schema: "public".into(),
+            ssl: false,
+        }
+    }
+}
+
+impl PostgresSettings {
+    /// DB_TYPE and DB_POSTGRESDB_* for these settings.
+    pub fn env(&self) -> Vec<EnvVar> {
+        let mut vars = vec![
+            EnvVar::new("DB_TYPE", "postgresdb"),
+            EnvVar::new("DB_POSTGRESDB_HOST", self.host.as_str()),
+            EnvVar::new("DB_POSTGRESDB_PORT", self.port.to_string()),
+            EnvVar::new("DB_POSTGRESDB_DATABASE", self.database.as_str()),
+            EnvVar::new("DB_POSTGRESDB_USER", self.user.as_str()),
+            EnvVar::secret("DB_POSTGRESDB_PASSWORD", self.password.as_str()),
+            EnvVar::new("DB_POSTGRESDB_SCHEMA", self.schema.as_str()),
+        ];
+        if self.ssl {
+            vars.push(EnvVar::new("DB_POSTGRESDB_SSL_ENABLED", "true"));
+        }
+        vars
+    }
+
+    /// Problems with these settings, reported under `field`.
+    pub fn validate(&self, field: &str) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+        for (name, value) in [
+            ("host", &self.host),
+            ("database", &self.database),
+            ("user", &self.user),
+            ("schema", &self.schema),
+        ] {
+            if value.trim().is_empty() {
+                issues.push(ConfigIssue {
+                    field: format!("{}.{}", field, name),
+                    message: format!("PostgreSQL {} is required", name),
+                });
+            }
+        }
+        if self.port == 0 {
+            issues.push(ConfigIssue {
+                field: format!("{}.port", field),
+                message: "Port 0 is not valid".into(),
+            });
+        }
+        issues
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct N8nSettings {
+    /// IANA zone, e.g. `Asia/Kolkata` → GENERIC_TIMEZONE and TZ.
+    pub timezone: Option<String>,
+    /// N8N_ENCRYPTION_KEY — protects stored credentials. Treated as a secret.
+    pub encryption_key: Option<String>,
+    /// N8N_BASIC_AUTH_* (password treated as a secret). n8n 1.0 replaced
+    /// basic auth with user management; newer releases ignore it.
+    pub basic_auth: Option<BasicAuth>,
+    /// WEBHOOK_URL — public base URL when behind a tunnel or proxy.
+    pub webhook_url: Option<String>,
+    /// N8N_USER_FOLDER — where n8n keeps `.n8n` (database, settings).
+    pub user_folder: Option<String>,
+    /// SQLite (default) or PostgreSQL.
+    pub database: N8nDatabase,
+    /// DB_SQLITE_POOL_SIZE (default 2; SQLite only).
+    pub sqlite_pool_size: Option<u8>,
+    /// N8N_RUNNERS_ENABLED (default true).
+    pub runners_enabled: Option<bool>,
+    /// N8N_BLOCK_ENV_ACCESS_IN_NODE (default false).
+    pub block_env_access_in_node: Option<bool>,
+    /// N8N_GIT_NODE_DISABLE_BARE_REPOS (default true).
+    pub git_node_disable_bare_repos: Option<bool>,
+    /// Additional variables passed through as-is.
+    pub extra_env: BTreeMap<String, String>,
+    /// Like `extra_env`, but redacted in previews and logs.
+    pub secrets: BTreeMap<String, String>,
+}
+
+/// One variable handed to the n8n child process.
+#[derive(Serialize, Debug, Clone)]
+pub struct EnvVar {
+    pub name: String,
+    pub value: String,
+    pub secret: bool,
+}
+
+impl EnvVar {
+    pub fn new(name: &str, value: impl Into<String>) -> Self {
+        EnvVar {
+            name: name.into(),
+            value: value.into(),
+            secret: false,
+        }
+    }
+
+    pub fn secret(name: &str, value: impl Into<String>) -> Self {
+        EnvVar {
+            secret: true,
+            ..EnvVar::new(name, value)
+        }
+    }
+
+    /// Copy safe to show in the UI.
+    pub fn redacted(&self) -> EnvVar {
+        EnvVar {
+            value: if self.secret {
+                REDACTED.into()
+            } else {
+                self.value.clone()
+            },
+            ..self.clone()
+        }
+    }
+}
+
+impl N8nSettings {
+    /// Variables set by the typed fields above; `extra_env` / `secrets` may
+    /// not redefine these.
+    fn managed_names() -> &'static [&'static str] {
+        &[
+            "GENERIC_TIMEZONE",
+            "TZ",
+            "N8N_ENCRYPTION_KEY",
+            "N8N_BASIC_AUTH_ACTIVE",
+            "N8N_BASIC_AUTH_USER",
+            "N8N_BASIC_AUTH_PASSWORD",
+            "WEBHOOK_URL",
+            "N8N_USER_FOLDER",
+            "DB_SQLITE_POOL_SIZE",
+            "DB_TYPE",
+            "DB_POSTGRESDB_HOST",
+            "DB_POSTGRESDB_PORT",
+            "DB_POSTGRESDB_DATABASE",
+            "DB_POSTGRESDB_USER",
+            "DB_POSTGRESDB_PASSWORD",
+            "DB_POSTGRESDB_SCHEMA",
+            "DB_POSTGRESDB_SSL_ENABLED",
+            "N8N_RUNNERS_ENABLED",
+            "N8N_BLOCK_ENV_ACCESS_IN_NODE",
+            "N8N_GIT_NODE_DISABLE_BARE_REPOS",
+            "N8N_PORT",
+            "N8N_RUNNERS_BROKER_PORT",
+            "OLLAMA_API_URL",
+            "PATH",
+        ]
+    }
+
+    /// Copy with every secret replaced, safe for support bundles: the
+    /// encryption key, basic-auth and PostgreSQL passwords, `secrets` values
+    /// and `extra_env` entries whose names look like credentials.
+    pub fn redacted(&self) -> N8nSettings {
+        let mut copy = self.clone();
+        if copy.encryption_key.is_some() {
+            copy.encryption_key = Some(REDACTED.into());
+        }
+        if let Some(auth) = &mut copy.basic_auth {
+            auth.password = REDACTED.into();
+        }
+        if let N8nDatabase::Postgres(pg) = &mut copy.database {
+            if !pg.password.is_empty() {
+                pg.password = REDACTED.into();
+            }
+        }
+        copy.webhook_url = copy.webhook_url.as_deref().map(super::redact_url);
+        for value in copy.secrets.values_mut() {
+            *value = REDACTED.into();
+        }
+        for (name, value) in copy.extra_env.iter_mut() {
+            if looks_secret(name) {
+                *value = REDACTED.into();
+            }
+        }
+        copy
+    }
+
+    /// n8n's data folder: `<user_folder>/.n8n`, by default `~/.n8n`.
+    pub fn data_dir(&self) -> PathBuf {
+        non_empty(&self.user_folder)
+            .map(PathBuf::from)
+            .or_else(dirs::home_dir)
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".n8n")
+    }
+
+    /// Environment for `n8n` (the version to be launched, if known), defaults
+    /// included. Typed settings are passed as given even when `validate`
+    /// flags them; `extra_env` / `secrets` entries it rejects are left out,
+    /// and so is basic auth for n8n 1.0 and later.
+    pub fn env(&self, n8n: Option<&Version>) -> Vec<EnvVar> {
+        let mut vars = match &self.database {
+            N8nDatabase::Sqlite => vec![EnvVar::new(
+                "DB_SQLITE_POOL_SIZE",
+                self.sqlite_pool_size.unwrap_or(2).to_string(),
+            )],
+            N8nDatabase::Postgres(pg) => pg.env(),
+        };
+        vars.extend([
+            EnvVar::new(
+                "N8N_RUNNERS_ENABLED",
+                self.runners_enabled.unwrap_or(true).to_string(),
+            ),
+            EnvVar::new(
+                "N8N_BLOCK_ENV_ACCESS_IN_NODE",
+                self.block_env_access_in_node.unwrap_or(false).to_string(),
+            ),
+            EnvVar::new(
+                "N8N_GIT_NODE_DISABLE_BARE_REPOS",
+                self.git_node_disable_bare_repos.unwrap_or(true).to_string(),
+            ),
+        ]);
+
+        if let Some(tz) = non_empty(&self.timezone) {
+            vars.push(EnvVar::new("GENERIC_TIMEZONE", tz));
+            vars.push(EnvVar::new("TZ", tz));
+        }
+        if let Some(key) = non_empty(&self.encryption_key) {
+            vars.push(EnvVar::secret("N8N_ENCRYPTION_KEY", key));
+        }
+        if let Some(auth) = self.basic_auth.as_ref().filter(|_| basic_auth_supported(n8n)) {
+            vars.push(EnvVar::new("N8N_BASIC_AUTH_ACTIVE", "true"));
+            vars.push(EnvVar::new("N8N_BASIC_AUTH_USER", auth.user.as_str()));
+            vars.push(EnvVar::secret("N8N_BASIC_AUTH_PASSWORD", auth.password.as_str()));
+        }
+        if let Some(url) = non_empty(&self.webhook_url) {
+            vars.push(EnvVar::new("WEBHOOK_URL", url));
+        }
+        if let Some(folder) = non_empty(&self.user_folder) {
+            vars.push(EnvVar::new("N8N_USER_FOLDER", folder));
+        }
+
+        for (name, value) in &self.extra_env {
+            if check_key(name).is_none() {
+                vars.push(EnvVar::new(name, value.as_str()));
+            }
+        }
+        for (name, value) in &self.secrets {
+            if check_key(name).is_none() {
+                vars.push(EnvVar::secret(name, value.as_str()));
+            }
+        }
+        vars
+    }
+
+    /// Problems reported through `AppConfig::validate`; `n8n` is the version
+    /// to be launched, if known.
+    pub fn validate(&self, n8n: Option<&Version>) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+        let mut issue = |field: String, message: String| issues.push(ConfigIssue { field, message });
+
+        if let Some(tz) = non_empty(&self.timezone) {
+            if !tz.contains('/') && tz != "UTC" {
+                issue(
+                    "n8n.timezone".into(),
+                    format!("'{}' is not an IANA zone like 'Europe/Berlin'", tz),
+                );
+            }
+        }
+        if let Some(key) = non_empty(&self.encryption_key) {
+            if key.len() < 16 {
+                issue(
+                    "n8n.encryption_key".into(),
+                    "Encryption key should be at least 16 characters".into(),
+                );
+            }
+        }
+        if let Some(auth) = &self.basic_auth {
+            if !basic_auth_supported(n8n) {
+                let release = n8n.map(|v| format!("n8n {}", v)).unwrap_or_else(|| "n8n 1.0 and later".into());
+                issue(
+                    "n8n.basic_auth".into(),
+                    format!("{} no longer supports basic auth; set up an owner account in n8n instead", release),
+                );
+            } else if auth.user.trim().is_empty() || auth.password.is_empty() {
+                issue(
+                    "n8n.basic_auth".into(),
+                    "Basic auth needs both a user and a password".into(),
+                );
+            }
+        }
+        if let Some(url) = non_empty(&self.webhook_url) {
+            if !(url.starts_with("https://") || url.starts_with("http://")) {
+                issue(
+                    "n8n.webhook_url".into(),
+                    format!("'{}' is not an http(s) URL", url),
+                );
+            }
+        }
+        if let N8nDatabase::Postgres(pg) = &self.database {
+            for problem in pg.validate("n8n.database") {
+                issue(problem.field, problem.message);
+            }
+        }
+        if self.sqlite_pool_size == Some(0) {
+            issue(
+                "n8n.sqlite_pool_size".into(),
+                "Pool size must be at least 1".into(),
+            );
+        }
+
+        for (section, map) in [("extra_env", &self.extra_env), ("secrets", &self.secrets)] {
+            for name in map.keys() {
+                if let Some(problem) = check_key(name) {
+                    issue(format!("n8n.{}.{}", section, name), problem);
+                }
+            }
+        }
+        for name in self.extra_env.keys().filter(|k| self.secrets.contains_key(*k)) {
+            issue(
+                format!("n8n.extra_env.{}", name),
+                "Also defined in secrets; the secret value wins".into(),
+            );
+        }
+
+        issues
+    }
+}
+
+/// `None` if `name` may be set through `extra_env` / `secrets`.
+fn check_key(name: &str) -> Option<String> {
+    let well_formed = !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+    if !well_formed {
+        return Some(format!("'{}' is not a valid variable name (A-Z, 0-9, _)", name));
+    }
+    if N8nSettings::managed_names().contains(&name) {
+        return Some(format!("'{}' is managed by the Workbench; use the typed setting", name));
+    }
+    let known = KNOWN_NAMES.contains(&name) || KNOWN_PREFIXES.iter().any(|p| name.starts_with(p));
+    if !known {
+        return Some(format!("'{}' is not a variable n8n reads", name));
+    }
+    None
+}
+
+/// N8N_BASIC_AUTH_* was removed in n8n 1.0; an unknown version counts as current.
+fn basic_auth_supported(n8n: Option<&Version>) -> bool {
+    n8n.is_some_and(|v| v.major < 1)
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// Variable names that usually carry credentials.
+fn looks_secret(name: &str) -> bool {
+    let upper = name.to_uppercase();
+    ["KEY", "SECRET", "TOKEN", "PASSWORD", "PASS", "CREDENTIAL"]
+        .iter()
+        .any(|marker| upper.contains(marker))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(vars: &[EnvVar]) -> Vec<&str> {
+        vars.iter().map(|v| v.name.as_str()).collect()
+    }
+
+    fn with_basic_auth() -> N8nSettings {
+        N8nSettings {
+            basic_auth: Some(BasicAuth {
+                user: "admin".into(),
+                password: "hunter2".into(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn basic_auth_only_reaches_pre_1_0_releases() {
+        let settings = with_basic_auth();
+        let legacy = Version::new(0, 236, 3);
+        let current = Version::new(1, 64, 0);
+
+        assert!(names(&settings.env(Some(&legacy))).contains(&"N8N_BASIC_AUTH_USER"));
+        assert!(!names(&settings.env(Some(&current))).contains(&"N8N_BASIC_AUTH_USER"));
+        assert!(!names(&settings.env(None)).contains(&"N8N_BASIC_AUTH_ACTIVE"));
+
+        assert!(settings.validate(Some(&legacy)).is_empty());
+        let issues = settings.validate(Some(&current));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].field, "n8n.basic_auth");
+        assert!(issues[0].message.contains("n8n 1.64.0"), "{}", issues[0].message);
+    }
+
+    #[test]
+    fn rejected_free_form_entries_are_left_out() {
+        let settings = N8nSettings {
+            extra_env: BTreeMap::from([
+                ("N8N_LOG_LEVEL".to_string(), "debug".to_string()),
+                ("N8N_PORT".to_string(), "1".to_string()),
+                ("HOME".to_string(), "/tmp".to_string()),
+                ("bad-name".to_string(), "x".to_string()),
+            ]),
+            secrets: BTreeMap::from([("N8N_SMTP_PASS".to_string(), "s3cret".to_string())]),
+            ..Default::default()
+        };
+        let vars = settings.env(None);
+        let vars = names(&vars);
+        assert!(vars.contains(&"N8N_LOG_LEVEL"));
+        assert!(vars.contains(&"N8N_SMTP_PASS"));
+        assert!(!vars.contains(&"N8N_PORT") && !vars.contains(&"HOME") && !vars.contains(&"bad-name"));
+        assert_eq!(settings.validate(None).len(), 3);
+    }
+
+    #[test]
+    fn typed_settings_are_passed_as_given() {
+        let settings = N8nSettings {
+            timezone: Some("Kolkata".into()),
+            ..Default::default()
+        };
+        assert!(names(&settings.env(None)).contains(&"GENERIC_TIMEZONE"));
+        assert_eq!(settings.validate(None)[0].field, "n8n.timezone");
+    }
+
+    #[test]
+    fn secrets_are_redacted_for_display() {
+        let vars = with_basic_auth().env(Some(&Version::new(0, 236, 0)));
+        let password = vars.iter().find(|v| v.name == "N8N_BASIC_AUTH_PASSWORD").unwrap();
+        assert_eq!(password.redacted().value, REDACTED);
+        assert_eq!(with_basic_auth().redacted().basic_auth.unwrap().password, REDACTED);
+    }
+}
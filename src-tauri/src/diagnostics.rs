@@ -0,0 +1,451 @@
+// 🧩 Gignaati Workbench — Diagnostics bundle
+//
+// Collects everything support asks for into one zip: the effective config
+// (secrets redacted), the preflight report, discovered binaries and their
+// compatibility, service status and recent output, the tail of each persisted
+// log file, port ownership, the model list and OS / hardware details, plus a
+// manifest. A section that cannot be collected is recorded in the manifest
+// instead of failing the bundle. Credentials embedded in URLs (mirrors,
+// proxies) are masked in every file. In anonymized mode the home directory
+// and user name are scrubbed from paths.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use sysinfo::System;
+use tauri::{AppHandle, Emitter, Manager};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::config::{redact_urls, AppConfig};
+use crate::discovery::{self, Candidate, Tool};
+use crate::logging;
+use crate::model_recommender::{hardware_profile, HardwareProfile};
+use crate::n8n_backup::utc_stamp;
+use crate::ollama_client::OllamaClient;
+use crate::ollama_models::{fetch_models, ModelFilter, ModelSort};
+use crate::ports::manager::check_port_available;
+use crate::ports::ownership::{port_holder, PortHolder};
+use crate::preflight;
+use crate::supervisor::{self, ServiceKind, ServiceStatus};
+use crate::version::{self, CompatibilityReport};
+
+const MANIFEST_FORMAT: u32 = 1;
+/// Bytes taken from the end of each persisted log file.
+const LOG_TAIL_BYTES: u64 = 512 * 1024;
+
+#[derive(Serialize, Clone)]
+struct ComponentLog {
+    component: String,
+    message: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct BundleEntry {
+    /// Path inside the zip.
+    pub path: String,
+    pub size: u64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct DiagnosticsManifest {
+    pub format: u32,
+    /// Unix seconds (UTC).
+    pub created_at: u64,
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    /// Home directory and user name replaced in every file.
+    pub anonymized: bool,
+    pub files: Vec<BundleEntry>,
+    /// Sections that could not be collected, with the reason.
+    pub skipped: BTreeMap<String, String>,
+}
+
+/// Returned to the UI.
+#[derive(Serialize, Debug, Clone)]
+pub struct DiagnosticsBundle {
+    pub path: String,
+    pub size: u64,
+    pub manifest: DiagnosticsManifest,
+}
+
+#[derive(Serialize)]
+struct Binaries {
+    candidates: BTreeMap<&'static str, Vec<Candidate>>,
+    compatibility: CompatibilityReport,
+}
+
+#[derive(Serialize)]
+struct PortEntry {
+    service: String,
+    port: u16,
+    free: bool,
+    holder: Option<PortHolder>,
+}
+
+#[derive(Serialize)]
+struct SystemInfo {
+    os_name: Option<String>,
+    os_version: Option<String>,
+    long_os_version: Option<String>,
+    kernel_version: Option<String>,
+    uptime_secs: u64,
+    hardware: HardwareProfile,
+}
+
+/// Build the bundle at `dest`.
+pub fn collect(app: &AppHandle, dest: &Path, anonymize: bool) -> Result<DiagnosticsBundle, String> {
+    let scrubber = Scrubber::new(anonymize);
+    let cfg = AppConfig::load_or_default();
+
+    // (path inside the zip, contents or the reason it is missing)
+    let mut sections: Vec<(String, Result<String, String>)> = vec![
+        ("config.json".into(), to_json(&cfg.redacted())),
+        ("config-issues.json".into(), to_json(&cfg.validate())),
+        ("preflight.json".into(), preflight::latest_report(app).and_then(|r| to_json(&r))),
+        ("binaries.json".into(), to_json(&binaries())),
+        ("ports.json".into(), to_json(&ports(&cfg))),
+        ("models.json".into(), models().and_then(|m| to_json(&m))),
+        ("system.json".into(), to_json(&system_info())),
+    ];
+
+    let services: Vec<ServiceStatus> = [ServiceKind::N8n, ServiceKind::Ollama]
+        .into_iter()
+        .filter_map(supervisor::status)
+        .collect();
+    sections.push(("services.json".into(), to_json(&services)));
+    for kind in [ServiceKind::N8n, ServiceKind::Ollama] {
+        let lines = supervisor::recent_logs(kind);
+        let name = format!("logs/{}.log", kind.id());
+        if lines.is_empty() {
+            sections.push((name, Err("No output captured this session".into())));
+        } else {
+            sections.push((name, Ok(lines.join("\n") + "\n")));
+        }
+    }
+
+    for (component, path) in logging::current_files() {
+        sections.push((format!("logs/files/{}.log", component), read_tail(&path)));
+    }
+
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let file = File::create(dest).map_err(|e| format!("Failed to create {}: {}", dest.display(), e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let mut manifest = DiagnosticsManifest {
+        format: MANIFEST_FORMAT,
+        created_at: now(),
+        app_version: env!("CARGO_PKG_VERSION").into(),
+        os: std::env::consts::OS.into(),
+        arch: std::env::consts::ARCH.into(),
+        anonymized: anonymize,
+        files: Vec::new(),
+        skipped: BTreeMap::new(),
+    };
+
+    for (path, contents) in sections {
+        match contents {
+            Ok(text) => {
+                let text = scrubber.apply(&redact_urls(&text));
+                write_entry(&mut zip, options, &path, text.as_bytes())?;
+                manifest.files.push(BundleEntry {
+                    path,
+                    size: text.len() as u64,
+                });
+            }
+            Err(reason) => {
+                manifest.skipped.insert(path, scrubber.apply(&redact_urls(&reason)));
+            }
+        }
+    }
+
+    let manifest_json = to_json(&manifest)?;
+    write_entry(&mut zip, options, "manifest.json", manifest_json.as_bytes())?;
+    zip.finish().map_err(|e| format!("Failed to finish {}: {}", dest.display(), e))?;
+
+    Ok(DiagnosticsBundle {
+        path: dest.to_string_lossy().to_string(),
+        size: fs::metadata(dest).map(|m| m.len()).unwrap_or(0),
+        manifest,
+    })
+}
+
+fn write_entry(
+    zip: &mut ZipWriter<File>,
+    options: SimpleFileOptions,
+    path: &str,
+    data: &[u8],
+) -> Result<(), String> {
+    zip.start_file(path, options)
+        .map_err(|e| format!("Failed to add {}: {}", path, e))?;
+    zip.write_all(data)
+        .map_err(|e| format!("Failed to write {}: {}", path, e))
+}
+
+// === Sections ===
+
+fn binaries() -> Binaries {
+    Binaries {
+        candidates: Tool::ALL
+            .into_iter()
+            .map(|tool| (tool.id(), discovery::candidates(tool, false)))
+            .collect(),
+        compatibility: version::report(version::check_installed()),
+    }
+}
+
+fn ports(cfg: &AppConfig) -> Vec<PortEntry> {
+    let mut ports = vec![
+        ("n8n".to_string(), cfg.n8n_port.unwrap_or(5678)),
+        ("ollama".to_string(), cfg.ollama_port.unwrap_or(11434)),
+    ];
+    ports.extend(cfg.service_ports.iter().map(|(name, port)| (name.clone(), *port)));
+
+    ports
+        .into_iter()
+        .map(|(service, port)| {
+            let free = check_port_available(port);
+            PortEntry {
+                service,
+                port,
+                free,
+                holder: if free { None } else { port_holder(port) },
+            }
+        })
+        .collect()
+}
+
+fn models() -> Result<Vec<crate::ollama_models::OllamaModel>, String> {
+    fetch_models(&OllamaClient::from_config(), &ModelSort::default(), &ModelFilter::default())
+        .map_err(|e| format!("Ollama is not answering: {}", e))
+}
+
+fn system_info() -> SystemInfo {
+    SystemInfo {
+        os_name: System::name(),
+        os_version: System::os_version(),
+        long_os_version: System::long_os_version(),
+        kernel_version: System::kernel_version(),
+        uptime_secs: System::uptime(),
+        hardware: hardware_profile(),
+    }
+}
+
+// === Anonymization ===
+
+/// Replaces the home directory with `~` and any remaining `/<user>` or
+/// `\<user>` path segment with `<user>`.
+struct Scrubber {
+    home: Option<String>,
+    user: Option<String>,
+}
+
+impl Scrubber {
+    fn new(enabled: bool) -> Self {
+        if !enabled {
+            return Scrubber { home: None, user: None };
+        }
+        let home = dirs::home_dir();
+        let user = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .ok()
+            .or_else(|| {
+                home.as_ref()
+                    .and_then(|h| h.file_name())
+                    .map(|n| n.to_string_lossy().to_string())
+            })
+            .filter(|u| !u.is_empty());
+        Scrubber {
+            home: home.map(|h| h.to_string_lossy().to_string()).filter(|h| h.len() > 1),
+            user,
+        }
+    }
+
+    fn apply(&self, text: &str) -> String {
+        let mut out = text.to_string();
+        if let Some(home) = &self.home {
+            // JSON escapes Windows backslashes
+            out = out.replace(&home.replace('\\', "\\\\"), "~").replace(home, "~");
+        }
+        if let Some(user) = &self.user {
+            out = replace_segment(&out, user, "<user>");
+        }
+        out
+    }
+}
+
+/// Replace `name` where it is a whole path segment: preceded by a separator
+/// and followed by a separator, quote, whitespace or the end of the text.
+fn replace_segment(text: &str, name: &str, with: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for (start, _) in text.match_indices(name) {
+        let end = start + name.len();
+        let before = text[..start].chars().next_back();
+        let after = text[end..].chars().next();
+        let bounded_before = matches!(before, Some('/') | Some('\\'));
+        let bounded_after = match after {
+            None => true,
+            Some(c) => matches!(c, '/' | '\\' | '"' | '\'' | ':' | ')' | ',') || c.is_whitespace(),
+        };
+        if bounded_before && bounded_after {
+            out.push_str(&text[last..start]);
+            out.push_str(with);
+            last = end;
+        }
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+// === Helpers ===
+
+/// Last `LOG_TAIL_BYTES` of a log file, starting at a line boundary.
+fn read_tail(path: &Path) -> Result<String, String> {
+    let mut file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let start = len.saturating_sub(LOG_TAIL_BYTES);
+    file.seek(SeekFrom::Start(start)).map_err(|e| e.to_string())?;
+    let mut data = Vec::new();
+    file.read_to_end(&mut data).map_err(|e| e.to_string())?;
+    let text = String::from_utf8_lossy(&data).to_string();
+    Ok(match text.split_once('\n') {
+        Some((_, rest)) if start > 0 => rest.to_string(),
+        _ => text,
+    })
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, String> {
+    serde_json::to_string_pretty(value).map_err(|e| e.to_string())
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn log(app: &AppHandle, message: &str) {
+    app.emit(
+        "component-log",
+        ComponentLog {
+            component: "Diagnostics".into(),
+            message: message.into(),
+        },
+    )
+    .ok();
+}
+
+// === Commands ===
+
+/// 🩺 Build a diagnostics zip for a support ticket. Written to `path`, or
+/// `<app data>/reports/diagnostics-<timestamp>.zip` by default.
+#[tauri::command]
+pub async fn collect_diagnostics(
+    app: AppHandle,
+    path: Option<String>,
+    anonymize: Option<bool>,
+) -> Result<DiagnosticsBundle, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let dest = match path {
+            Some(p) => PathBuf::from(p),
+            None => app
+                .path()
+                .app_data_dir()
+                .map_err(|e| format!("Failed to get app data dir: {}", e))?
+                .join("reports")
+                .join(format!("diagnostics-{}.zip", utc_stamp(now()))),
+        };
+        log(&app, "🩺 Collecting diagnostics...");
+        let bundle = collect(&app, &dest, anonymize.unwrap_or(false))?;
+        log(
+            &app,
+            &format!(
+                "✅ Diagnostics saved to {} ({} files, {} skipped).",
+                bundle.path,
+                bundle.manifest.files.len(),
+                bundle.manifest.skipped.len()
+            ),
+        );
+        Ok(bundle)
+    })
+    .await
+    .map_err(|e| format!("Diagnostics failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scrubber(home: Option<&str>, user: Option<&str>) -> Scrubber {
+        Scrubber {
+            home: home.map(String::from),
+            user: user.map(String::from),
+        }
+    }
+
+    #[test]
+    fn json_escaped_windows_home_is_masked() {
+        let s = scrubber(Some(r"C:\Users\bob"), Some("bob"));
+        let json = r#"{"n8n_path":"C:\\Users\\bob\\AppData\\Roaming\\npm\\n8n.cmd"}"#;
+        assert_eq!(s.apply(json), r#"{"n8n_path":"~\\AppData\\Roaming\\npm\\n8n.cmd"}"#);
+        assert_eq!(s.apply(r"log at C:\Users\bob\logs"), r"log at ~\logs");
+    }
+
+    #[test]
+    fn username_is_masked_only_as_a_whole_segment() {
+        let s = scrubber(None, Some("bob"));
+        assert_eq!(s.apply("/home/bob/x"), "/home/<user>/x");
+        assert_eq!(s.apply("/bobcat"), "/bobcat");
+        assert_eq!(s.apply("/srv/bobby/bob"), "/srv/bobby/<user>");
+        assert_eq!(s.apply("owner: bob"), "owner: bob");
+        assert_eq!(s.apply(r#"["/home/bob", 'D:\bob']"#), r#"["/home/<user>", 'D:\<user>']"#);
+    }
+
+    #[test]
+    fn username_at_the_end_of_the_text_is_masked() {
+        let s = scrubber(None, Some("bob"));
+        assert_eq!(s.apply("/Users/bob"), "/Users/<user>");
+        assert_eq!(s.apply("cwd=/Users/bob\n"), "cwd=/Users/<user>\n");
+    }
+
+    #[test]
+    fn disabled_scrubber_leaves_text_alone() {
+        let s = Scrubber::new(false);
+        assert_eq!(s.apply("/home/bob/x"), "/home/bob/x");
+    }
+
+    #[test]
+    fn read_tail_starts_on_a_line_boundary() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("n8n.log");
+        // 12-byte lines, so the cut lands in the middle of one
+        let text: String = (0..50_000).map(|n| format!("line {:06}\n", n)).collect();
+        fs::write(&path, &text).unwrap();
+        assert_ne!((text.len() as u64 - LOG_TAIL_BYTES) % 12, 0);
+
+        let tail = read_tail(&path).unwrap();
+        assert!(tail.len() as u64 <= LOG_TAIL_BYTES);
+        assert!(text.ends_with(&tail));
+        let first = tail.lines().next().unwrap();
+        assert!(first.starts_with("line ") && first.len() == 11, "{:?}", first);
+        assert!(tail.ends_with("line 049999\n"));
+    }
+
+    #[test]
+    fn read_tail_keeps_short_files_whole() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("short.log");
+        fs::write(&path, "first\nsecond\n").unwrap();
+        assert_eq!(read_tail(&path).unwrap(), "first\nsecond\n");
+    }
+}
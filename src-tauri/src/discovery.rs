@@ -0,0 +1,559 @@
+// 🧩 Gignaati Workbench — Binary discovery
+//
+// One place that answers "where is node / npm / n8n / ollama?". Every
+// candidate is collected from, in order of preference:
+//   1. the Workbench's own runtime and paths recorded in AppConfig
+//   2. PATH
+//   3. version managers and package-manager prefixes (nvm, fnm, volta, asdf,
+//      Homebrew, the global npm prefix)
+//   4. known per-OS install locations
+// and reported with its version, source and CPU architecture. Results are
+// cached for a few minutes; installers invalidate the cache when they change
+// something. A user can pin a preferred candidate per tool
+// (`preferred_binaries` in AppConfig).
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::config::AppConfig;
+use crate::installer::node_runtime::managed_runtime;
+
+const CACHE_TTL: Duration = Duration::from_secs(300);
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Tool {
+    Node,
+    Npm,
+    N8n,
+    Ollama,
+}
+
+impl Tool {
+    pub const ALL: [Tool; 4] = [Tool::Node, Tool::Npm, Tool::N8n, Tool::Ollama];
+
+    /// Key in `preferred_binaries`.
+    pub fn id(self) -> &'static str {
+        match self {
+            Tool::Node => "node",
+            Tool::Npm => "npm",
+            Tool::N8n => "n8n",
+            Tool::Ollama => "ollama",
+        }
+    }
+
+    fn version_arg(self) -> &'static str {
+        match self {
+            Tool::Node | Tool::Npm => "-v",
+            Tool::N8n | Tool::Ollama => "--version",
+        }
+    }
+
+    /// File names to look for in a directory.
+    fn file_names(self) -> &'static [&'static str] {
+        match (self, cfg!(target_os = "windows")) {
+            (Tool::Node, true) => &["node.exe"],
+            (Tool::Npm, true) => &["npm.cmd"],
+            (Tool::N8n, true) => &["n8n.cmd"],
+            (Tool::Ollama, true) => &["ollama.exe"],
+            (Tool::Node, false) => &["node"],
+            (Tool::Npm, false) => &["npm"],
+            (Tool::N8n, false) => &["n8n"],
+            (Tool::Ollama, false) => &["ollama"],
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Source {
+    /// The Workbench's private runtime, or a path recorded in AppConfig.
+    Workbench,
+    Path,
+    Nvm,
+    Fnm,
+    Volta,
+    Asdf,
+    Homebrew,
+    NpmPrefix,
+    KnownLocation,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Candidate {
+    pub tool: Tool,
+    pub path: String,
+    /// Trimmed `--version` output; `None` if the binary did not run.
+    pub version: Option<String>,
+    pub source: Source,
+    /// `x86_64`, `aarch64`, ... from the executable header (`None` for scripts).
+    pub arch: Option<String>,
+    /// The candidate `locate` returns.
+    pub preferred: bool,
+}
+
+type Found = (Instant, Vec<Candidate>);
+
+static CACHE: Lazy<Mutex<HashMap<Tool, Found>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Every working candidate for `tool`, best first. Cached unless `refresh`.
+pub fn candidates(tool: Tool, refresh: bool) -> Vec<Candidate> {
+    if !refresh {
+        if let Some((at, found)) = CACHE.lock().unwrap().get(&tool) {
+            if at.elapsed() < CACHE_TTL {
+                return with_preference(tool, found.clone());
+            }
+        }
+    }
+
+    let found: Vec<Candidate> = search(tool)
+        .into_iter()
+        .filter_map(|(path, source)| probe(tool, path, source))
+        .collect();
+    let found = with_pinned(tool, found, pinned(tool).as_deref());
+
+    CACHE
+        .lock()
+        .unwrap()
+        .insert(tool, (Instant::now(), found.clone()));
+    with_preference(tool, found)
+}
+
+/// The preferred candidate: the user's pin if it still works, else the best.
+pub fn locate_candidate(tool: Tool) -> Option<Candidate> {
+    candidates(tool, false).into_iter().find(|c| c.preferred)
+}
+
+/// Path of the preferred candidate.
+pub fn locate(tool: Tool) -> Option<String> {
+    locate_candidate(tool).map(|c| c.path)
+}
+
+/// Forget cached results after installing or removing something.
+pub fn invalidate(tool: Tool) {
+    CACHE.lock().unwrap().remove(&tool);
+}
+
+fn pinned(tool: Tool) -> Option<String> {
+    AppConfig::load_or_default()
+        .preferred_binaries
+        .get(tool.id())
+        .cloned()
+}
+
+/// A pin outside the usual search locations still counts as a candidate
+/// while it works; it goes first, like the Workbench's own binaries.
+fn with_pinned(tool: Tool, mut found: Vec<Candidate>, pinned: Option<&str>) -> Vec<Candidate> {
+    let Some(pinned) = pinned else {
+        return found;
+    };
+    if !found.iter().any(|c| same_file(Path::new(&c.path), Path::new(pinned))) {
+        if let Some(candidate) = probe(tool, PathBuf::from(pinned), Source::Workbench) {
+            found.insert(0, candidate);
+        }
+    }
+    found
+}
+
+fn with_preference(tool: Tool, mut found: Vec<Candidate>) -> Vec<Candidate> {
+    let index = pinned(tool)
+        .and_then(|p| found.iter().position(|c| same_file(Path::new(&c.path), Path::new(&p))))
+        .unwrap_or(0);
+    if let Some(c) = found.get_mut(index) {
+        c.preferred = true;
+    }
+    found
+}
+
+// === Search ===
+
+/// Candidate paths in preference order, de-duplicated by real path.
+fn search(tool: Tool) -> Vec<(PathBuf, Source)> {
+    let mut dirs: Vec<(PathBuf, Source)> = Vec::new();
+    let mut files: Vec<(PathBuf, Source)> = Vec::new();
+
+    // 1. Workbench
+    let cfg = AppConfig::load_or_default();
+    let recorded = match tool {
+        Tool::Node => cfg.node_path.clone(),
+        Tool::Npm => cfg.npm_path.clone(),
+        Tool::N8n => None, // managed versions are launched via `n8n_path`, see install_n8n_real
+        Tool::Ollama => cfg.ollama_path.clone(),
+    };
+    if let Some(path) = recorded {
+        files.push((PathBuf::from(path), Source::Workbench));
+    }
+    if let Some(rt) = managed_runtime() {
+        dirs.push((rt.bin_dir(), Source::Workbench));
+    }
+
+    // 2. PATH
+    if let Some(path) = env::var_os("PATH") {
+        dirs.extend(env::split_paths(&path).map(|d| (d, Source::Path)));
+    }
+
+    // 3. Version managers / package managers. Node-based tools (npm and
+    // globally installed n8n) live next to the node binary they belong to.
+    if tool != Tool::Ollama {
+        dirs.extend(node_manager_dirs());
+    }
+    dirs.extend(homebrew_dirs().into_iter().map(|d| (d, Source::Homebrew)));
+    if tool == Tool::N8n {
+        if let Some(prefix) = npm_global_bin() {
+            dirs.push((prefix, Source::NpmPrefix));
+        }
+    }
+
+    // 4. Known locations
+    dirs.extend(known_dirs(tool).into_iter().map(|d| (d, Source::KnownLocation)));
+
+    for (dir, source) in dirs {
+        for name in tool.file_names() {
+            files.push((dir.join(name), source));
+        }
+    }
+
+    let mut seen: Vec<PathBuf> = Vec::new();
+    files
+        .into_iter()
+        .filter(|(path, _)| path.is_file())
+        .filter(|(path, _)| {
+            let real = path.canonicalize().unwrap_or_else(|_| path.clone());
+            let new = !seen.contains(&real);
+            seen.push(real);
+            new
+        })
+        .collect()
+}
+
+fn home() -> PathBuf {
+    dirs::home_dir().unwrap_or_else(|| PathBuf::from("."))
+}
+
+fn env_dir(var: &str) -> Option<PathBuf> {
+    env::var_os(var).map(PathBuf::from)
+}
+
+/// Subdirectories of `root`, newest-looking version first.
+fn version_dirs(root: &Path) -> Vec<PathBuf> {
+    let mut found: Vec<PathBuf> = fs::read_dir(root)
+        .map(|entries| entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()).collect())
+        .unwrap_or_default();
+    found.sort_by_key(|p| {
+        let name = p.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        std::cmp::Reverse(
+            name.trim_start_matches('v')
+                .split('.')
+                .map(|part| part.parse::<u64>().unwrap_or(0))
+                .collect::<Vec<_>>(),
+        )
+    });
+    found
+}
+
+/// Bin directories of nvm, fnm, volta and asdf Node installs.
+fn node_manager_dirs() -> Vec<(PathBuf, Source)> {
+    let mut dirs = Vec::new();
+    let windows = cfg!(target_os = "windows");
+
+    // nvm (nvm-sh) / nvm-windows
+    let nvm_root = env_dir("NVM_DIR").unwrap_or_else(|| home().join(".nvm"));
+    for v in version_dirs(&nvm_root.join("versions").join("node")) {
+        dirs.push((v.join("bin"), Source::Nvm));
+    }
+    if windows {
+        if let Some(root) = env_dir("NVM_HOME") {
+            dirs.extend(version_dirs(&root).into_iter().map(|v| (v, Source::Nvm)));
+        }
+        if let Some(link) = env_dir("NVM_SYMLINK") {
+            dirs.push((link, Source::Nvm));
+        }
+    }
+
+    // fnm
+    let fnm_roots = [
+        env_dir("FNM_DIR"),
+        Some(home().join(".local").join("share").join("fnm")),
+        Some(home().join("Library").join("Application Support").join("fnm")),
+        Some(home().join(".fnm")),
+        env_dir("APPDATA").map(|d| d.join("fnm")),
+    ];
+    for root in fnm_roots.into_iter().flatten() {
+        for v in version_dirs(&root.join("node-versions")) {
+            let install = v.join("installation");
+            dirs.push((if windows { install } else { install.join("bin") }, Source::Fnm));
+        }
+    }
+
+    // volta (shims dispatch to the project's pinned node)
+    let volta = env_dir("VOLTA_HOME").unwrap_or_else(|| {
+        if windows {
+            env_dir("LOCALAPPDATA").unwrap_or_else(home).join("Volta")
+        } else {
+            home().join(".volta")
+        }
+    });
+    dirs.push((volta.join("bin"), Source::Volta));
+
+    // asdf
+    let asdf = env_dir("ASDF_DATA_DIR").unwrap_or_else(|| home().join(".asdf"));
+    for v in version_dirs(&asdf.join("installs").join("nodejs")) {
+        dirs.push((v.join("bin"), Source::Asdf));
+    }
+
+    dirs
+}
+
+fn homebrew_dirs() -> Vec<PathBuf> {
+    if cfg!(target_os = "windows") {
+        return Vec::new();
+    }
+    let mut dirs: Vec<PathBuf> = env_dir("HOMEBREW_PREFIX").map(|p| p.join("bin")).into_iter().collect();
+    dirs.extend(
+        ["/opt/homebrew/bin", "/usr/local/bin", "/home/linuxbrew/.linuxbrew/bin"]
+            .iter()
+            .map(PathBuf::from),
+    );
+    dirs
+}
+
+/// Where `npm install -g` puts launchers.
+fn npm_global_bin() -> Option<PathBuf> {
+    let npm = locate(Tool::Npm)?;
+    let mut cmd = Command::new(&npm);
+    cmd.args(["prefix", "-g"]);
+    with_own_dir_on_path(&mut cmd, Path::new(&npm));
+    let output = cmd.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let prefix = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    Some(if cfg!(target_os = "windows") {
+        prefix
+    } else {
+        prefix.join("bin")
+    })
+}
+
+fn known_dirs(tool: Tool) -> Vec<PathBuf> {
+    if cfg!(target_os = "windows") {
+        let program_files = env_dir("ProgramFiles").unwrap_or_else(|| PathBuf::from(r"C:\Program Files"));
+        let program_files_x86 =
+            env_dir("ProgramFiles(x86)").unwrap_or_else(|| PathBuf::from(r"C:\Program Files (x86)"));
+        match tool {
+            Tool::Node | Tool::Npm => vec![program_files.join("nodejs"), program_files_x86.join("nodejs")],
+            Tool::N8n => {
+                let mut dirs: Vec<PathBuf> = env_dir("APPDATA").map(|d| d.join("npm")).into_iter().collect();
+                dirs.push(PathBuf::from(r"C:\ProgramData\npm"));
+                dirs.push(program_files.join("nodejs"));
+                dirs
+            }
+            Tool::Ollama => {
+                let mut dirs: Vec<PathBuf> = env_dir("LOCALAPPDATA")
+                    .map(|d| d.join("Programs").join("Ollama"))
+                    .into_iter()
+                    .collect();
+                dirs.push(program_files.join("Ollama"));
+                dirs.push(program_files_x86.join("Ollama"));
+                dirs
+            }
+        }
+    } else {
+        let mut dirs = vec![PathBuf::from("/usr/local/bin"), PathBuf::from("/usr/bin")];
+        if tool == Tool::Ollama {
+            dirs.push(home().join(".local").join("bin"));
+            dirs.push(PathBuf::from("/Applications/Ollama.app/Contents/Resources"));
+        }
+        dirs
+    }
+}
+
+// === Probing ===
+
+/// A working candidate at `path`, or `None` if it does not run.
+fn probe(tool: Tool, path: PathBuf, source: Source) -> Option<Candidate> {
+    let version = probe_version(tool, &path)?;
+    Some(Candidate {
+        tool,
+        arch: binary_arch(&path),
+        path: path.to_string_lossy().to_string(),
+        version: Some(version),
+        source,
+        preferred: false,
+    })
+}
+
+/// Run `<path> --version` (or `-v`); npm and n8n launchers are scripts that
+/// need their own node, so the binary's directory goes first on PATH.
+fn probe_version(tool: Tool, path: &Path) -> Option<String> {
+    let mut cmd = Command::new(path);
+    cmd.arg(tool.version_arg());
+    with_own_dir_on_path(&mut cmd, path);
+    let output = cmd.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!version.is_empty()).then_some(version)
+}
+
+fn with_own_dir_on_path(cmd: &mut Command, path: &Path) {
+    let Some(dir) = path.parent() else {
+        return;
+    };
+    let mut paths = vec![dir.to_path_buf()];
+    if let Some(existing) = env::var_os("PATH") {
+        paths.extend(env::split_paths(&existing));
+    }
+    if let Ok(joined) = env::join_paths(paths) {
+        cmd.env("PATH", joined);
+    }
+}
+
+/// CPU architecture from an ELF, Mach-O or PE header.
+fn binary_arch(path: &Path) -> Option<String> {
+    let mut header = [0u8; 512];
+    let mut file = File::open(path).ok()?;
+    let n = file.read(&mut header).ok()?;
+    let header = &header[..n];
+
+    let u16_le = |at: usize| header.get(at..at + 2).map(|b| u16::from_le_bytes([b[0], b[1]]));
+    let u32_le = |at: usize| {
+        header
+            .get(at..at + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    };
+
+    let arch = match header {
+        [0x7f, b'E', b'L', b'F', _, data, ..] => {
+            let machine = if *data == 2 {
+                header.get(18..20).map(|b| u16::from_be_bytes([b[0], b[1]]))?
+            } else {
+                u16_le(18)?
+            };
+            match machine {
+                0x3e => "x86_64",
+                0xb7 => "aarch64",
+                0x03 => "x86",
+                0x28 => "arm",
+                _ => return None,
+            }
+        }
+        [0xcf, 0xfa, 0xed, 0xfe, ..] => match u32_le(4)? {
+            0x0100_0007 => "x86_64",
+            0x0100_000c => "aarch64",
+            _ => return None,
+        },
+        [0xca, 0xfe, 0xba, 0xbe, ..] => "universal",
+        [b'M', b'Z', ..] => {
+            let pe = u32_le(0x3c)? as usize;
+            if header.get(pe..pe + 4)? != b"PE\0\0" {
+                return None;
+            }
+            match u16_le(pe + 4)? {
+                0x8664 => "x86_64",
+                0xaa64 => "aarch64",
+                0x014c => "x86",
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    Some(arch.to_string())
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+// === Commands ===
+
+/// 🔎 Every node / npm / n8n / ollama found on this machine (or just `tool`)
+#[tauri::command]
+pub fn discover_binaries(tool: Option<Tool>, refresh: Option<bool>) -> Vec<Candidate> {
+    let tools: Vec<Tool> = match tool {
+        Some(t) => vec![t],
+        None => Tool::ALL.to_vec(),
+    };
+    tools
+        .into_iter()
+        .flat_map(|t| candidates(t, refresh.unwrap_or(false)))
+        .collect()
+}
+
+/// 📌 Prefer a specific binary for a tool (`None` to go back to automatic).
+/// For n8n this is the "use existing installation" opt-in.
+#[tauri::command]
+pub fn set_preferred_binary(tool: Tool, path: Option<String>) -> Result<Option<Candidate>, String> {
+    if let Some(p) = &path {
+        if probe_version(tool, Path::new(p)).is_none() {
+            return Err(format!("'{}' did not respond to {} {}", p, tool.id(), tool.version_arg()));
+        }
+    }
+
+    if tool == Tool::N8n {
+        if let Some(p) = &path {
+            crate::installer::install_n8n_real::use_existing_n8n(p.clone())?;
+        }
+    }
+
+    AppConfig::modify(|cfg| match &path {
+        Some(p) => {
+            cfg.preferred_binaries.insert(tool.id().to_string(), p.clone());
+        }
+        None => {
+            cfg.preferred_binaries.remove(tool.id());
+        }
+    })?;
+
+    Ok(candidates(tool, true).into_iter().find(|c| c.preferred))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    fn fake_ollama(dir: &Path) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+        let path = dir.join("ollama");
+        fs::write(&path, "#!/bin/sh\necho 'ollama version is 0.5.7'\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn a_pin_outside_the_search_path_is_kept_as_a_candidate() {
+        let tmp = tempfile::tempdir().unwrap();
+        let pinned = fake_ollama(tmp.path()).to_string_lossy().to_string();
+
+        let found = with_pinned(Tool::Ollama, Vec::new(), Some(&pinned));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].path, pinned);
+        assert_eq!(found[0].source, Source::Workbench);
+        assert_eq!(found[0].version.as_deref(), Some("ollama version is 0.5.7"));
+
+        // Already found by the search: not added twice
+        assert_eq!(with_pinned(Tool::Ollama, found, Some(&pinned)).len(), 1);
+    }
+
+    #[test]
+    fn a_broken_pin_is_not_a_candidate() {
+        let tmp = tempfile::tempdir().unwrap();
+        let missing = tmp.path().join("ollama").to_string_lossy().to_string();
+        assert!(with_pinned(Tool::Ollama, Vec::new(), Some(&missing)).is_empty());
+    }
+}
@@ -1,6 +1,16 @@
 use tauri::{AppHandle, Emitter};
-use serde::Serialize;
-use std::{thread, time::Duration};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::AppConfig;
+use crate::discovery::{self, Tool};
+use crate::installer::components::{N8nComponent, NodeComponent, OllamaComponent};
+use crate::installer::engine::Component;
+use crate::installer::n8n_versions::versions_dir;
+use crate::installer::node_runtime::managed_runtime;
+use crate::installer::ollama_runtime::ollama_dir;
+use crate::ollama_models::ollama_models_dir;
 
 /// Progress payload for cleanup progress bar
 #[derive(Serialize, Clone)]
@@ -18,73 +28,312 @@ pub struct ComponentLog {
     pub message: String,
 }
 
+/// What the user asked us to remove. Defaults to a dry run that keeps user data.
+#[derive(Deserialize, Debug, Clone)]
+pub struct CleanupOptions {
+    #[serde(default = "default_true")]
+    pub dry_run: bool,
+    /// Also delete n8n's data folder (`.n8n` under the configured user folder,
+    /// `~/.n8n` by default) with all workflows and credentials.
+    #[serde(default)]
+    pub remove_n8n_data: bool,
+    /// Also delete downloaded Ollama models.
+    #[serde(default)]
+    pub remove_ollama_models: bool,
+}
+
+impl Default for CleanupOptions {
+    fn default() -> Self {
+        CleanupOptions {
+            dry_run: true,
+            remove_n8n_data: false,
+            remove_ollama_models: false,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CleanupTarget {
+    N8nProcess,
+    OllamaProcess,
+    N8nPackage,
+    NodeRuntime,
+    OllamaRuntime,
+    N8nData,
+    OllamaModels,
+}
+
+impl CleanupTarget {
+    fn label(self) -> &'static str {
+        match self {
+            CleanupTarget::N8nProcess => "Agentic Platform (n8n) process",
+            CleanupTarget::OllamaProcess => "Ollama Server process",
+            CleanupTarget::N8nPackage => "Agentic Platform (n8n)",
+            CleanupTarget::NodeRuntime => "Node.js runtime",
+            CleanupTarget::OllamaRuntime => "AI Brain (Ollama)",
+            CleanupTarget::N8nData => "n8n data (~/.n8n)",
+            CleanupTarget::OllamaModels => "Ollama models",
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct CleanupItem {
+    pub target: CleanupTarget,
+    pub component: String,
+    pub path: Option<String>,
+    pub bytes: u64,
+    /// Set after execution; `None` in a dry run.
+    pub removed: Option<bool>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct CleanupReport {
+    pub dry_run: bool,
+    pub items: Vec<CleanupItem>,
+    pub total_bytes: u64,
+}
+
+impl CleanupReport {
+    /// Bytes of the items that were actually removed.
+    pub fn freed_bytes(&self) -> u64 {
+        self.items
+            .iter()
+            .filter(|i| i.removed == Some(true))
+            .map(|i| i.bytes)
+            .sum()
+    }
+}
+
+/// 🧹 Uninstall Workbench-managed components.
+///
+/// With `dry_run` (the default) nothing is touched and the report lists what
+/// would be removed and how much space would be freed.
 #[tauri::command]
-pub async fn cleanup_installation(app: AppHandle) -> Result<(), String> {
-    let components = vec!["Node.js", "Agentic Platform", "AI Brain"];
+pub async fn cleanup_installation(
+    app: AppHandle,
+    options: Option<CleanupOptions>,
+) -> Result<CleanupReport, String> {
+    // Stopping services, sizing model folders and deleting them all block
+    tauri::async_runtime::spawn_blocking(move || cleanup(&app, &options.unwrap_or_default()))
+        .await
+        .map_err(|e| format!("Cleanup task failed: {}", e))
+}
+
+fn cleanup(app: &AppHandle, options: &CleanupOptions) -> CleanupReport {
+    let mut report = plan_cleanup(app, options);
+
+    if options.dry_run {
+        return report;
+    }
 
     // Start cleanup header message
-    app.emit(
-        "component-log",
-        ComponentLog {
-            component: "Cleanup".into(),
-            message: "🧹 Starting Cleanup Process...".into(),
-        },
-    )
-    .ok();
+    log(app, "Cleanup", "🧹 Starting Cleanup Process...");
+
+    for item in report.items.iter_mut() {
+        progress(app, &item.component, 0, "running", &format!("🧼 Removing {}...", item.component));
 
-    // Simulate cleanup process for each component
-    for name in components {
-        simulate_cleanup(&app, name);
+        let result = execute(app, item);
+        item.removed = Some(result.is_ok());
+        match result {
+            Ok(()) => {
+                progress(app, &item.component, 100, "done", &format!("🗑 {} removed.", item.component));
+            }
+            Err(e) => {
+                log(app, &item.component, &format!("❌ {}", e));
+                progress(app, &item.component, 100, "failed", &e);
+                item.error = Some(e);
+            }
+        }
+    }
+
+    for tool in Tool::ALL {
+        discovery::invalidate(tool);
     }
 
     // Finish message
+    log(
+        app,
+        "Cleanup",
+        &format!(
+            "✅ Cleanup Complete. Freed approximately {}. System is ready for a fresh installation.",
+            human_size(report.freed_bytes())
+        ),
+    );
+
+    report.dry_run = false;
+    report
+}
+
+/// Build the list of things a cleanup with these options would remove.
+fn plan_cleanup(app: &AppHandle, options: &CleanupOptions) -> CleanupReport {
+    let mut items = vec![
+        item(CleanupTarget::N8nProcess, None),
+        item(CleanupTarget::OllamaProcess, None),
+    ];
+
+    let runtime = managed_runtime();
+
+    // Workbench-managed n8n versions; an opted-in global install is left alone
+    if let Some(dir) = versions_dir(app).ok().filter(|d| d.exists()) {
+        items.push(item(CleanupTarget::N8nPackage, Some(dir)));
+    }
+
+    if let Some(rt) = runtime {
+        items.push(item(CleanupTarget::NodeRuntime, Some(rt.root)));
+    }
+
+    // Workbench-provisioned Ollama and its user service; models live elsewhere
+    if let Some(dir) = ollama_dir(app).ok().filter(|d| d.exists()) {
+        items.push(item(CleanupTarget::OllamaRuntime, Some(dir)));
+    }
+
+    if options.remove_n8n_data {
+        let dir = AppConfig::load_or_default().n8n.data_dir();
+        if dir.exists() {
+            items.push(item(CleanupTarget::N8nData, Some(dir)));
+        }
+    }
+
+    if options.remove_ollama_models {
+        if let Some(dir) = ollama_models_dir().filter(|d| d.exists()) {
+            items.push(item(CleanupTarget::OllamaModels, Some(dir)));
+        }
+    }
+
+    let total_bytes = items.iter().map(|i| i.bytes).sum();
+    CleanupReport {
+        dry_run: true,
+        items,
+        total_bytes,
+    }
+}
+
+fn item(target: CleanupTarget, path: Option<PathBuf>) -> CleanupItem {
+    CleanupItem {
+        target,
+        component: target.label().into(),
+        bytes: path.as_deref().map(dir_size).unwrap_or(0),
+        path: path.map(|p| p.to_string_lossy().to_string()),
+        removed: None,
+        error: None,
+    }
+}
+
+fn execute(app: &AppHandle, item: &CleanupItem) -> Result<(), String> {
+    match item.target {
+        CleanupTarget::N8nProcess => crate::n8n_manager::stop_n8n(app.clone()),
+        CleanupTarget::OllamaProcess => crate::ollama_server::stop_ollama_server(app.clone()),
+        CleanupTarget::N8nPackage => N8nComponent.uninstall(app),
+        CleanupTarget::NodeRuntime => NodeComponent.uninstall(app),
+        CleanupTarget::OllamaRuntime => OllamaComponent.uninstall(app),
+        CleanupTarget::N8nData => remove_path(item),
+        CleanupTarget::OllamaModels => {
+            remove_path(item)?;
+            // The default model went with the folder
+            AppConfig::modify(|cfg| cfg.ollama_default_model = None)?;
+            Ok(())
+        }
+    }
+}
+
+fn remove_path(item: &CleanupItem) -> Result<(), String> {
+    let path = item.path.as_deref().ok_or("No path recorded")?;
+    fs::remove_dir_all(path).map_err(|e| format!("Failed to delete '{}': {}", path, e))
+}
+
+/// Total size of all files below `path` (symlinks are not followed).
+fn dir_size(path: &Path) -> u64 {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|e| dir_size(&e.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+fn log(app: &AppHandle, component: &str, message: &str) {
     app.emit(
         "component-log",
         ComponentLog {
-            component: "Cleanup".into(),
-            message: "✅ Cleanup Complete. System is ready for a fresh installation.".into(),
+            component: component.into(),
+            message: message.into(),
         },
     )
     .ok();
-
-    Ok(())
 }
 
-/// Simulates removing a component (with fake progress)
-fn simulate_cleanup(app: &AppHandle, name: &str) {
+fn progress(app: &AppHandle, component: &str, percent: u8, status: &str, message: &str) {
     app.emit(
-        "component-log",
-        ComponentLog {
-            component: name.to_string(),
-            message: format!("🧼 Removing {}...", name),
+        "component-progress",
+        CleanupProgress {
+            component: component.into(),
+            percent,
+            status: status.into(),
+            message: message.into(),
         },
     )
     .ok();
+}
 
-    for i in 0..=100 {
-        thread::sleep(Duration::from_millis(35));
-        app.emit(
-            "component-progress",
-            CleanupProgress {
-                component: name.to_string(),
-                percent: i,
-                status: if i < 100 {
-                    "running".into()
-                } else {
-                    "done".into()
-                },
-                message: format!("Cleaning {}... {}%", name, i),
-            },
-        )
-        .ok();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sized(target: CleanupTarget, bytes: u64, removed: Option<bool>) -> CleanupItem {
+        CleanupItem {
+            target,
+            component: target.label().into(),
+            path: None,
+            bytes,
+            removed,
+            error: None,
+        }
     }
 
-    app.emit(
-        "component-log",
-        ComponentLog {
-            component: name.to_string(),
-            message: format!("🗑 {} removed successfully.", name),
-        },
-    )
-    .ok();
+    #[test]
+    fn freed_bytes_counts_only_removed_items() {
+        let report = CleanupReport {
+            dry_run: false,
+            items: vec![
+                sized(CleanupTarget::N8nPackage, 300, Some(true)),
+                sized(CleanupTarget::NodeRuntime, 500, Some(false)),
+                sized(CleanupTarget::OllamaModels, 70, None),
+            ],
+            total_bytes: 870,
+        };
+        assert_eq!(report.freed_bytes(), 300);
+    }
+
+    #[test]
+    fn human_size_picks_the_largest_unit() {
+        assert_eq!(human_size(512), "512.0 B");
+        assert_eq!(human_size(1536), "1.5 KB");
+        assert_eq!(human_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
 }
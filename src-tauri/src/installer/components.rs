@@ -0,0 +1,392 @@
+// 🧩 Gignaati Workbench Installer
+// 🔧 Component Definitions — the standard installation graph
+//
+//   Node.js ──▶ Agentic Platform (n8n)
+//   AI Brain (Ollama) ──▶ default model pull
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use tauri::AppHandle;
+
+use crate::config::AppConfig;
+use crate::discovery::{self, Tool};
+use crate::installer::engine::{Component, Detection, EngineMode, EngineReport, InstallEngine, StepReport};
+use crate::installer::install_n8n_real::{install_n8n_real, installed_n8n};
+use crate::installer::n8n_versions::versions_dir;
+use crate::installer::node_runtime::{managed_runtime, provision_node, NODE_LTS_VERSION};
+use crate::installer::ollama_real::install_ollama_real;
+use crate::installer::ollama_runtime::{ollama_dir, unregister_service};
+use crate::ollama_client::{OllamaClient, PullProgress};
+use crate::supervisor::{self, ServiceKind};
+use crate::version::{self, Verdict};
+
+// === Node.js ===
+
+pub struct NodeComponent;
+
+impl Component for NodeComponent {
+    fn id(&self) -> &str {
+        "node"
+    }
+
+    fn display_name(&self) -> &str {
+        "Node.js"
+    }
+
+    fn weight(&self) -> u8 {
+        25
+    }
+
+    fn detect(&self, _app: &AppHandle) -> Detection {
+        match managed_runtime() {
+            Some(rt) if rt.version == NODE_LTS_VERSION => Detection::Satisfied {
+                version: Some(format!("v{}", rt.version)),
+            },
+            Some(rt) => Detection::Outdated {
+                found: format!("v{}", rt.version),
+            },
+            None => Detection::Missing,
+        }
+    }
+
+    fn install(&self, app: &AppHandle, _progress: &mut dyn FnMut(u8, &str)) -> Result<(), String> {
+        provision_node(app).map(|_| ())
+    }
+
+    fn verify(&self, _app: &AppHandle) -> Result<String, String> {
+        let rt = managed_runtime().ok_or("Node.js runtime not recorded in config")?;
+        let output = Command::new(&rt.node)
+            .arg("-v")
+            .output()
+            .map_err(|e| format!("Failed to run node: {}", e))?;
+        if !output.status.success() {
+            return Err(format!("'{}' exited with {}", rt.node.display(), output.status));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    fn uninstall(&self, _app: &AppHandle) -> Result<(), String> {
+        let mut cfg = AppConfig::load()?;
+        if let Some(dir) = cfg.node_runtime_dir.take() {
+            let path = PathBuf::from(&dir);
+            if path.exists() {
+                fs::remove_dir_all(&path)
+                    .map_err(|e| format!("Failed to remove '{}': {}", dir, e))?;
+            }
+        }
+        cfg.node_path = None;
+        cfg.npm_path = None;
+        cfg.node_version = None;
+        cfg.npm_version = None;
+        cfg.save()?;
+        discovery::invalidate(Tool::Node);
+        discovery::invalidate(Tool::Npm);
+        Ok(())
+    }
+}
+
+// === Agentic Platform (n8n) ===
+
+pub struct N8nComponent;
+
+impl Component for N8nComponent {
+    fn id(&self) -> &str {
+        "n8n"
+    }
+
+    fn display_name(&self) -> &str {
+        "Agentic Platform"
+    }
+
+    fn weight(&self) -> u8 {
+        35
+    }
+
+    fn depends_on(&self) -> Vec<String> {
+        vec!["node".into()]
+    }
+
+    fn detect(&self, _app: &AppHandle) -> Detection {
+        match installed_n8n(managed_runtime().as_ref()) {
+            Some((_, version)) => Detection::Satisfied {
+                version: Some(version),
+            },
+            None => Detection::Missing,
+        }
+    }
+
+    fn install(&self, app: &AppHandle, _progress: &mut dyn FnMut(u8, &str)) -> Result<(), String> {
+        install_n8n_real(app.clone())
+    }
+
+    fn verify(&self, _app: &AppHandle) -> Result<String, String> {
+        installed_n8n(managed_runtime().as_ref())
+            .map(|(_, version)| version)
+            .ok_or_else(|| "n8n did not respond to --version".to_string())
+    }
+
+    fn uninstall(&self, app: &AppHandle) -> Result<(), String> {
+        // Only Workbench-managed versions are removed; an existing install the
+        // user opted into belongs to them and is merely forgotten
+        let dir = versions_dir(app)?;
+        if dir.exists() {
+            fs::remove_dir_all(&dir)
+                .map_err(|e| format!("Failed to delete '{}': {}", dir.display(), e))?;
+        }
+        AppConfig::modify(|cfg| {
+            cfg.n8n_installed = false;
+            cfg.n8n_path = None;
+            cfg.n8n_version = None;
+            cfg.n8n_previous_version = None;
+            cfg.n8n_use_existing = false;
+        })?;
+        Ok(())
+    }
+
+    fn record(&self) {
+        if let Some((path, _)) = installed_n8n(managed_runtime().as_ref()) {
+            let saved = AppConfig::modify(|cfg| {
+                cfg.n8n_installed = true;
+                cfg.n8n_path = Some(path);
+            });
+            if let Err(e) = saved {
+                eprintln!("⚠ Failed to record n8n install: {}", e);
+            }
+        }
+    }
+}
+
+// === AI Brain (Ollama) ===
+
+pub struct OllamaComponent;
+
+impl Component for OllamaComponent {
+    fn id(&self) -> &str {
+        "ollama"
+    }
+
+    fn display_name(&self) -> &str {
+        "AI Brain (Ollama)"
+    }
+
+    fn weight(&self) -> u8 {
+        30
+    }
+
+    fn detect(&self, _app: &AppHandle) -> Detection {
+        let Some(raw) = discovery::locate_candidate(Tool::Ollama).and_then(|c| c.version) else {
+            return Detection::Missing;
+        };
+        match version::parse_output(Tool::Ollama, &raw).map(|v| version::check_ollama(&v)) {
+            Some(finding) if finding.verdict == Verdict::Blocking => Detection::Outdated { found: raw },
+            _ => Detection::Satisfied { version: Some(raw) },
+        }
+    }
+
+    fn install(&self, app: &AppHandle, _progress: &mut dyn FnMut(u8, &str)) -> Result<(), String> {
+        install_ollama_real(app.clone())
+    }
+
+    fn verify(&self, _app: &AppHandle) -> Result<String, String> {
+        discovery::invalidate(Tool::Ollama);
+        discovery::locate_candidate(Tool::Ollama)
+            .and_then(|c| c.version)
+            .ok_or_else(|| "Ollama binary not found".to_string())
+    }
+
+    fn uninstall(&self, app: &AppHandle) -> Result<(), String> {
+        let dir = ollama_dir(app)?;
+        let managed = AppConfig::load()?
+            .ollama_path
+            .is_some_and(|p| PathBuf::from(p).starts_with(&dir));
+        if !managed {
+            return Err("Ollama was installed outside the Workbench; remove it with the system uninstaller.".into());
+        }
+
+        supervisor::stop(app, ServiceKind::Ollama);
+        unregister_service();
+        if dir.exists() {
+            fs::remove_dir_all(&dir)
+                .map_err(|e| format!("Failed to remove '{}': {}", dir.display(), e))?;
+        }
+        AppConfig::modify(|cfg| {
+            cfg.ollama_installed = false;
+            cfg.ollama_path = None;
+            cfg.ollama_version = None;
+        })?;
+        discovery::invalidate(Tool::Ollama);
+        Ok(())
+    }
+
+    fn record(&self) {
+        if let Some(found) = discovery::locate_candidate(Tool::Ollama) {
+            let (path, version) = (found.path, found.version);
+            let saved = AppConfig::modify(|cfg| {
+                cfg.ollama_installed = true;
+                cfg.ollama_version = version;
+                cfg.ollama_path = Some(path);
+            });
+            if let Err(e) = saved {
+                eprintln!("⚠ Failed to record Ollama install: {}", e);
+            }
+        }
+    }
+}
+
+// === Model pull ===
+
+pub struct ModelComponent {
+    id: String,
+    model: String,
+}
+
+impl ModelComponent {
+    pub fn new(model: &str) -> Self {
+        ModelComponent {
+            id: format!("model:{}", model),
+            model: model.to_string(),
+        }
+    }
+
+    fn is_present(&self) -> bool {
+        let wanted = if self.model.contains(':') {
+            self.model.clone()
+        } else {
+            format!("{}:latest", self.model)
+        };
+        OllamaClient::from_config()
+            .tags()
+            .map(|tags| tags.models.iter().any(|m| m.name == wanted))
+            .unwrap_or(false)
+    }
+}
+
+impl Component for ModelComponent {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn display_name(&self) -> &str {
+        &self.model
+    }
+
+    fn weight(&self) -> u8 {
+        10
+    }
+
+    fn depends_on(&self) -> Vec<String> {
+        vec!["ollama".into()]
+    }
+
+    fn detect(&self, _app: &AppHandle) -> Detection {
+        if self.is_present() {
+            Detection::Satisfied { version: None }
+        } else {
+            Detection::Missing
+        }
+    }
+
+    fn install(&self, app: &AppHandle, progress: &mut dyn FnMut(u8, &str)) -> Result<(), String> {
+        if OllamaClient::from_config().version().is_err() {
+            crate::ollama_server::start_server(app)?;
+        }
+        // Built after the start: the server may have moved to another port
+        let mut totals = PullTotals::default();
+        let mut reported = None;
+        OllamaClient::from_config()
+            .pull(&self.model, |line| {
+                if let Some(pct) = totals.update(line).filter(|&p| reported.is_none_or(|r| p > r)) {
+                    reported = Some(pct);
+                    progress(pct, &format!("Pulling {}... {}%", self.model, pct));
+                }
+                true
+            })
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    fn verify(&self, _app: &AppHandle) -> Result<String, String> {
+        if self.is_present() {
+            Ok("model available".into())
+        } else {
+            Err(format!("'{}' not listed by ollama after pull", self.model))
+        }
+    }
+
+    fn uninstall(&self, _app: &AppHandle) -> Result<(), String> {
+        OllamaClient::from_config()
+            .delete(&self.model)
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Byte totals of a pull across its layers; each NDJSON line reports one
+/// layer's `completed` / `total`.
+#[derive(Default)]
+struct PullTotals {
+    layers: HashMap<String, (u64, u64)>,
+}
+
+impl PullTotals {
+    /// Overall percent after `line`, capped at 99 until the step is verified.
+    fn update(&mut self, line: &PullProgress) -> Option<u8> {
+        let (Some(digest), Some(total)) = (&line.digest, line.total) else {
+            return None;
+        };
+        self.layers.insert(digest.clone(), (line.completed.unwrap_or(0).min(total), total));
+        let (done, total) = self
+            .layers
+            .values()
+            .fold((0, 0), |(d, t), &(c, x)| (d + c, t + x));
+        (total > 0).then(|| (done * 100 / total).min(99) as u8)
+    }
+}
+
+/// The Workbench installation graph, including the configured default model.
+pub fn standard_engine() -> InstallEngine {
+    let engine = InstallEngine::new()
+        .with(NodeComponent)
+        .with(N8nComponent)
+        .with(OllamaComponent);
+
+    match AppConfig::load_or_default().ollama_default_model {
+        Some(model) if !model.trim().is_empty() => engine.with(ModelComponent::new(model.trim())),
+        _ => engine,
+    }
+}
+
+/// Run the standard graph. Shared entry point for every installer front-end.
+pub fn run_standard<F>(app: &AppHandle, mode: EngineMode, on_step: F) -> Result<EngineReport, String>
+where
+    F: FnMut(&StepReport),
+{
+    standard_engine().run(app, mode, on_step)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(digest: Option<&str>, completed: Option<u64>, total: Option<u64>) -> PullProgress {
+        PullProgress {
+            status: "downloading".into(),
+            digest: digest.map(String::from),
+            total,
+            completed,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn pull_totals_sum_every_layer() {
+        let mut totals = PullTotals::default();
+        assert_eq!(totals.update(&line(None, None, None)), None);
+        assert_eq!(totals.update(&line(Some("sha256:big"), None, Some(900))), Some(0));
+        assert_eq!(totals.update(&line(Some("sha256:big"), Some(450), Some(900))), Some(50));
+        assert_eq!(totals.update(&line(Some("sha256:small"), Some(0), Some(100))), Some(45));
+        assert_eq!(totals.update(&line(Some("sha256:big"), Some(900), Some(900))), Some(90));
+        assert_eq!(totals.update(&line(Some("sha256:small"), Some(100), Some(100))), Some(99));
+    }
+}
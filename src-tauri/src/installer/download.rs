@@ -0,0 +1,309 @@
+// 🧩 Gignaati Workbench Installer
+// 🔧 Download Helpers — HTTP fetch, SHA-256 verification and archive extraction
+//
+// Shared by the runtime provisioners. Every URL is built from a configurable
+// mirror base, so the same code runs against nodejs.org or a local HTTP mirror.
+
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{BufReader, Read, Write};
+use std::path::{Component, Path};
+use std::time::Duration;
+
+use crate::config::redact_urls;
+
+/// Fetch a small text resource (e.g. SHASUMS256.txt).
+pub fn fetch_text(url: &str) -> Result<String, String> {
+    agent()
+        .get(url)
+        .call()
+        .map_err(|e| redact_urls(&format!("Failed to fetch {}: {}", url, e)))?
+        .into_string()
+        .map_err(|e| redact_urls(&format!("Failed to read {}: {}", url, e)))
+}
+
+/// Stream `url` into `dest`, reporting `(downloaded, total)` bytes as it goes.
+pub fn download_file<F>(url: &str, dest: &Path, mut on_progress: F) -> Result<u64, String>
+where
+    F: FnMut(u64, Option<u64>),
+{
+    let response = agent()
+        .get(url)
+        .call()
+        .map_err(|e| redact_urls(&format!("Failed to download {}: {}", url, e)))?;
+
+    let total = response
+        .header("Content-Length")
+        .and_then(|v| v.parse::<u64>().ok());
+
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+
+    let partial = dest.with_extension("part");
+    let mut file = File::create(&partial)
+        .map_err(|e| format!("Failed to create {}: {}", partial.display(), e))?;
+
+    let mut reader = response.into_reader();
+    let mut buf = [0u8; 64 * 1024];
+    let mut downloaded = 0u64;
+
+    loop {
+        let n = reader
+            .read(&mut buf)
+            .map_err(|e| format!("Download interrupted: {}", e))?;
+        if n == 0 {
+            break;
+        }
+        file.write_all(&buf[..n])
+            .map_err(|e| format!("Failed to write {}: {}", partial.display(), e))?;
+        downloaded += n as u64;
+        on_progress(downloaded, total);
+    }
+
+    file.flush().map_err(|e| e.to_string())?;
+    drop(file);
+    fs::rename(&partial, dest)
+        .map_err(|e| format!("Failed to finalize {}: {}", dest.display(), e))?;
+
+    Ok(downloaded)
+}
+
+/// Compute the lowercase hex SHA-256 of a file.
+pub fn sha256_file(path: &Path) -> Result<String, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut reader = BufReader::new(file);
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 64 * 1024];
+
+    loop {
+        let n = reader.read(&mut buf).map_err(|e| e.to_string())?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Look up the expected hash for `file_name` in a `SHASUMS256.txt` /
+/// `sha256sum.txt` style listing (entries may carry a `./dist/` style prefix).
+pub fn find_checksum(shasums: &str, file_name: &str) -> Option<String> {
+    shasums.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        let hash = parts.next()?;
+        let name = parts.next()?.trim_start_matches('*');
+        let name = name.rsplit('/').next().unwrap_or(name);
+        (name == file_name).then(|| hash.to_lowercase())
+    })
+}
+
+/// Verify `path` against an expected SHA-256, deleting the file on mismatch.
+pub fn verify_sha256(path: &Path, expected: &str) -> Result<(), String> {
+    let actual = sha256_file(path)?;
+    if actual.eq_ignore_ascii_case(expected) {
+        return Ok(());
+    }
+    let _ = fs::remove_file(path);
+    Err(format!(
+        "Checksum mismatch for {}: expected {}, got {}",
+        path.display(),
+        expected,
+        actual
+    ))
+}
+
+/// Unpack a `.tar.gz`/`.tgz` or `.zip` archive into `dest`.
+pub fn extract_archive(archive: &Path, dest: &Path) -> Result<(), String> {
+    fs::create_dir_all(dest)
+        .map_err(|e| format!("Failed to create {}: {}", dest.display(), e))?;
+
+    let name = archive
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let file =
+        File::open(archive).map_err(|e| format!("Failed to open {}: {}", archive.display(), e))?;
+
+    if name.ends_with(".zip") {
+        let mut zip = zip::ZipArchive::new(file).map_err(|e| format!("Invalid zip: {}", e))?;
+        zip.extract(dest)
+            .map_err(|e| format!("Failed to extract {}: {}", archive.display(), e))
+    } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        let decoder = flate2::read::GzDecoder::new(BufReader::new(file));
+        let mut tar = tar::Archive::new(decoder);
+        tar.set_preserve_permissions(true);
+        let failed = |e: std::io::Error| format!("Failed to extract {}: {}", archive.display(), e);
+        for entry in tar.entries().map_err(failed)? {
+            let mut entry = entry.map_err(failed)?;
+            // tar skips `..` / absolute entries silently; a release archive
+            // never has them, so treat one as tampering
+            let path = entry.path().map_err(failed)?.into_owned();
+            if !is_enclosed(&path) {
+                return Err(format!("Unsafe path '{}' in {}", path.display(), archive.display()));
+            }
+            entry.unpack_in(dest).map_err(failed)?;
+        }
+        Ok(())
+    } else {
+        Err(format!("Unsupported archive format: {}", archive.display()))
+    }
+}
+
+/// Relative path without `..`, i.e. stays inside the extraction directory.
+fn is_enclosed(path: &Path) -> bool {
+    path.components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+/// HTTP agent used for every download; honours `HTTPS_PROXY` / `HTTP_PROXY`
+/// / `ALL_PROXY` from the environment.
+fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .try_proxy_from_env(true)
+        .timeout_connect(Duration::from_secs(15))
+        .timeout_read(Duration::from_secs(60))
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{serve, Reply};
+    use std::io::Cursor;
+
+    /// `.tgz` bytes; names are written raw so `..` entries can be built.
+    fn tgz(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        let mut builder = tar::Builder::new(encoder);
+        for (name, data) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.as_gnu_mut().unwrap().name[..name.len()].copy_from_slice(name.as_bytes());
+            header.set_size(data.len() as u64);
+            header.set_mode(0o755);
+            header.set_cksum();
+            builder.append(&header, *data).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    fn zip_bytes(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, data) in entries {
+            writer
+                .start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(data).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn sha256_hex(data: &[u8]) -> String {
+        hex::encode(Sha256::digest(data))
+    }
+
+    #[test]
+    fn find_checksum_matches_plain_prefixed_and_binary_entries() {
+        let listing = "AAA  node-v20.tar.gz\nbbb  ./dist/ollama-linux-amd64.tgz\nccc *win.zip\n";
+        assert_eq!(find_checksum(listing, "node-v20.tar.gz").as_deref(), Some("aaa"));
+        assert_eq!(find_checksum(listing, "ollama-linux-amd64.tgz").as_deref(), Some("bbb"));
+        assert_eq!(find_checksum(listing, "win.zip").as_deref(), Some("ccc"));
+        assert_eq!(find_checksum(listing, "node-v20.tar"), None);
+    }
+
+    #[test]
+    fn downloads_verifies_and_extracts_from_local_mirror() {
+        let archive = tgz(&[("pkg/bin/tool", b"#!/bin/sh\necho ok\n")]);
+        let shasums = format!("{}  ./pkg.tgz\n", sha256_hex(&archive));
+        let base = serve(vec![
+            ("/v1/pkg.tgz", Reply::ok(archive.clone())),
+            ("/v1/SHASUMS256.txt", Reply::ok(shasums)),
+        ]);
+        let tmp = tempfile::tempdir().unwrap();
+        let dest = tmp.path().join("pkg.tgz");
+
+        let listing = fetch_text(&format!("{}/v1/SHASUMS256.txt", base)).unwrap();
+        let expected = find_checksum(&listing, "pkg.tgz").unwrap();
+        let mut last = (0, None);
+        let size = download_file(&format!("{}/v1/pkg.tgz", base), &dest, |done, total| {
+            last = (done, total)
+        })
+        .unwrap();
+
+        assert_eq!(size, archive.len() as u64);
+        assert_eq!(last, (archive.len() as u64, Some(archive.len() as u64)));
+        verify_sha256(&dest, &expected).unwrap();
+        extract_archive(&dest, &tmp.path().join("out")).unwrap();
+        assert!(tmp.path().join("out/pkg/bin/tool").is_file());
+    }
+
+    #[test]
+    fn checksum_mismatch_fails_and_removes_the_file() {
+        let base = serve(vec![("/pkg.tgz", Reply::ok(tgz(&[("a", b"a")])))]);
+        let tmp = tempfile::tempdir().unwrap();
+        let dest = tmp.path().join("pkg.tgz");
+        download_file(&format!("{}/pkg.tgz", base), &dest, |_, _| {}).unwrap();
+
+        let err = verify_sha256(&dest, &sha256_hex(b"something else")).unwrap_err();
+        assert!(err.contains("Checksum mismatch"), "{}", err);
+        assert!(!dest.exists());
+    }
+
+    #[test]
+    fn truncated_download_is_an_error_and_leaves_no_file() {
+        let base = serve(vec![(
+            "/pkg.tgz",
+            Reply::Truncated {
+                body: vec![7; 1000],
+                declared: 4000,
+            },
+        )]);
+        let tmp = tempfile::tempdir().unwrap();
+        let dest = tmp.path().join("pkg.tgz");
+
+        assert!(download_file(&format!("{}/pkg.tgz", base), &dest, |_, _| {}).is_err());
+        assert!(!dest.exists());
+    }
+
+    #[test]
+    fn missing_file_on_mirror_is_an_error() {
+        let base = serve(vec![]);
+        let tmp = tempfile::tempdir().unwrap();
+        let err = download_file(&format!("{}/nope.tgz", base), &tmp.path().join("x"), |_, _| {})
+            .unwrap_err();
+        assert!(err.contains("404"), "{}", err);
+    }
+
+    #[test]
+    fn tar_entry_escaping_the_destination_is_rejected() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive = tmp.path().join("evil.tgz");
+        fs::write(&archive, tgz(&[("ok.txt", b"fine"), ("../evil.txt", b"pwned")])).unwrap();
+
+        let err = extract_archive(&archive, &tmp.path().join("out")).unwrap_err();
+        assert!(err.contains("Unsafe path"), "{}", err);
+        assert!(!tmp.path().join("evil.txt").exists());
+    }
+
+    #[test]
+    fn zip_entry_escaping_the_destination_is_rejected() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive = tmp.path().join("evil.zip");
+        fs::write(&archive, zip_bytes(&[("../evil.txt", b"pwned")])).unwrap();
+
+        assert!(extract_archive(&archive, &tmp.path().join("out")).is_err());
+        assert!(!tmp.path().join("evil.txt").exists());
+    }
+
+    #[test]
+    fn zip_archive_extracts() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive = tmp.path().join("pkg.zip");
+        fs::write(&archive, zip_bytes(&[("dir/ollama.exe", b"MZ")])).unwrap();
+
+        extract_archive(&archive, &tmp.path().join("out")).unwrap();
+        assert_eq!(fs::read(tmp.path().join("out/dir/ollama.exe")).unwrap(), b"MZ");
+    }
+}
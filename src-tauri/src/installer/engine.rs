@@ -0,0 +1,504 @@
+// 🧩 Gignaati Workbench Installer
+// 🔧 Installation Engine — declarative component graph
+//
+// Every installable piece (Node.js, n8n, Ollama, models) implements
+// `Component`. The engine orders them by their declared dependencies,
+// skips anything already satisfied, stops dependents when a prerequisite
+// fails and emits the same `component-progress` / `component-log` events
+// for every front-end (`smart_installer`, `run_installation`,
+// `start_progress_tracking`).
+
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use tauri::{AppHandle, Emitter};
+
+/// Result of probing whether a component is already present.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Detection {
+    Satisfied { version: Option<String> },
+    Outdated { found: String },
+    Missing,
+}
+
+/// One node in the installation graph. `A` is what the steps receive; always
+/// the `AppHandle` outside of tests.
+pub trait Component<A = AppHandle>: Send + Sync {
+    /// Stable identifier used for dependency edges (e.g. `"node"`).
+    fn id(&self) -> &str;
+    /// Name shown in the UI; also the `component` field of emitted events.
+    fn display_name(&self) -> &str;
+    /// Relative share of the overall progress bar.
+    fn weight(&self) -> u8;
+    /// Ids of components that must succeed before this one runs.
+    fn depends_on(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn detect(&self, app: &A) -> Detection;
+    /// `progress` takes this step's percent and a status line, for installers
+    /// that do not emit their own `component-progress` events.
+    fn install(&self, app: &A, progress: &mut dyn FnMut(u8, &str)) -> Result<(), String>;
+    /// Confirm the component works after installation; returns a version or summary.
+    fn verify(&self, app: &A) -> Result<String, String>;
+    fn uninstall(&self, app: &A) -> Result<(), String>;
+    /// Persist whatever AppConfig should know once the component is ready.
+    fn record(&self) {}
+}
+
+/// What the engine should do with each component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngineMode {
+    /// Detect, install if needed, then verify.
+    Install,
+    /// Detect only; never modifies the system.
+    Check,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StepOutcome {
+    AlreadySatisfied,
+    Installed,
+    Missing,
+    Failed,
+    Blocked,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct StepReport {
+    pub id: String,
+    pub component: String,
+    pub outcome: StepOutcome,
+    pub message: String,
+    /// Overall progress (0–100) after this step.
+    pub overall_percent: u8,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct EngineReport {
+    pub success: bool,
+    pub steps: Vec<StepReport>,
+}
+
+impl EngineReport {
+    pub fn summary(&self) -> String {
+        let failed: Vec<&str> = self
+            .steps
+            .iter()
+            .filter(|s| matches!(s.outcome, StepOutcome::Failed | StepOutcome::Blocked))
+            .map(|s| s.component.as_str())
+            .collect();
+        if failed.is_empty() {
+            "🎉 All components installed successfully! Ready to launch.".into()
+        } else {
+            format!("⚠ Setup incomplete — failed or blocked: {}", failed.join(", "))
+        }
+    }
+}
+
+#[derive(Serialize, Clone)]
+struct ComponentLog {
+    component: String,
+    message: String,
+}
+
+#[derive(Serialize, Clone)]
+struct ComponentProgress {
+    component: String,
+    percent: u8,
+    status: String,
+    message: String,
+    eta_seconds: Option<u32>,
+}
+
+/// Receives the engine's `component-log` / `component-progress` events.
+pub trait EngineEvents {
+    fn log(&self, component: &str, message: &str);
+    fn progress(&self, component: &str, percent: u8, status: &str, message: &str);
+}
+
+impl EngineEvents for AppHandle {
+    fn log(&self, component: &str, message: &str) {
+        self.emit(
+            "component-log",
+            ComponentLog {
+                component: component.into(),
+                message: message.into(),
+            },
+        )
+        .ok();
+    }
+
+    fn progress(&self, component: &str, percent: u8, status: &str, message: &str) {
+        self.emit(
+            "component-progress",
+            ComponentProgress {
+                component: component.into(),
+                percent,
+                status: status.into(),
+                message: message.into(),
+                eta_seconds: None,
+            },
+        )
+        .ok();
+    }
+}
+
+/// A set of components plus the logic to run them in dependency order.
+pub struct InstallEngine<A = AppHandle> {
+    components: Vec<Box<dyn Component<A>>>,
+}
+
+impl<A: EngineEvents> InstallEngine<A> {
+    pub fn new() -> Self {
+        InstallEngine { components: Vec::new() }
+    }
+
+    pub fn with(mut self, component: impl Component<A> + 'static) -> Self {
+        self.components.push(Box::new(component));
+        self
+    }
+
+    /// Components in an order where every dependency precedes its dependents.
+    /// Registration order is preserved among independent components.
+    pub fn ordered(&self) -> Result<Vec<&dyn Component<A>>, String> {
+        let ids: HashSet<&str> = self.components.iter().map(|c| c.id()).collect();
+        for c in &self.components {
+            for dep in c.depends_on() {
+                if !ids.contains(dep.as_str()) {
+                    return Err(format!(
+                        "Component '{}' depends on unknown component '{}'",
+                        c.id(),
+                        dep
+                    ));
+                }
+            }
+        }
+
+        let mut placed: HashSet<String> = HashSet::new();
+        let mut order: Vec<&dyn Component<A>> = Vec::with_capacity(self.components.len());
+
+        while order.len() < self.components.len() {
+            let next = self.components.iter().find(|c| {
+                !placed.contains(c.id()) && c.depends_on().iter().all(|d| placed.contains(d))
+            });
+            match next {
+                Some(c) => {
+                    placed.insert(c.id().to_string());
+                    order.push(c.as_ref());
+                }
+                None => {
+                    let stuck: Vec<&str> = self
+                        .components
+                        .iter()
+                        .map(|c| c.id())
+                        .filter(|id| !placed.contains(*id))
+                        .collect();
+                    return Err(format!("Dependency cycle between: {}", stuck.join(", ")));
+                }
+            }
+        }
+
+        Ok(order)
+    }
+
+    /// Run the graph. `on_step` is called after each component so front-ends
+    /// can forward the result to their own legacy events.
+    pub fn run<F>(&self, app: &A, mode: EngineMode, mut on_step: F) -> Result<EngineReport, String>
+    where
+        F: FnMut(&StepReport),
+    {
+        let order = self.ordered()?;
+        let total_weight: u32 = order.iter().map(|c| c.weight() as u32).sum::<u32>().max(1);
+        let mut done_weight = 0u32;
+        let mut failed: HashMap<String, String> = HashMap::new();
+        let mut steps = Vec::with_capacity(order.len());
+
+        for component in order {
+            let name = component.display_name().to_string();
+
+            let (outcome, message) = if let Some(dep_name) = blocked_by(component, &failed) {
+                (
+                    StepOutcome::Blocked,
+                    format!("⏭ Skipped — prerequisite '{}' did not complete.", dep_name),
+                )
+            } else {
+                app.progress(&name, 0, "running", &format!("⏳ Checking {}...", name));
+                self.run_one(app, component, mode)
+            };
+
+            match outcome {
+                StepOutcome::Failed | StepOutcome::Blocked => {
+                    failed.insert(component.id().to_string(), name.clone());
+                }
+                StepOutcome::AlreadySatisfied | StepOutcome::Installed if mode == EngineMode::Install => {
+                    component.record();
+                }
+                _ => {}
+            }
+
+            done_weight += component.weight() as u32;
+            let status = match outcome {
+                StepOutcome::AlreadySatisfied | StepOutcome::Installed => "done",
+                StepOutcome::Missing => "missing",
+                StepOutcome::Blocked => "skipped",
+                StepOutcome::Failed => "failed",
+            };
+            app.progress(&name, 100, status, &message);
+            app.log(&name, &message);
+
+            let report = StepReport {
+                id: component.id().to_string(),
+                component: name,
+                outcome,
+                message,
+                overall_percent: ((done_weight * 100) / total_weight).min(100) as u8,
+            };
+            on_step(&report);
+            steps.push(report);
+        }
+
+        let success = steps.iter().all(|s| {
+            matches!(
+                s.outcome,
+                StepOutcome::AlreadySatisfied | StepOutcome::Installed
+            )
+        });
+        Ok(EngineReport { success, steps })
+    }
+
+    fn run_one(
+        &self,
+        app: &A,
+        component: &dyn Component<A>,
+        mode: EngineMode,
+    ) -> (StepOutcome, String) {
+        let name = component.display_name();
+
+        match component.detect(app) {
+            Detection::Satisfied { version } => {
+                let suffix = version.map(|v| format!(" ({})", v)).unwrap_or_default();
+                return (
+                    StepOutcome::AlreadySatisfied,
+                    format!("✅ {} already installed{}.", name, suffix),
+                );
+            }
+            Detection::Outdated { found } if mode == EngineMode::Check => {
+                return (
+                    StepOutcome::Missing,
+                    format!("⚠ {} is outdated ({}).", name, found),
+                );
+            }
+            Detection::Missing if mode == EngineMode::Check => {
+                return (StepOutcome::Missing, format!("⚠ {} not found.", name));
+            }
+            _ => {}
+        }
+
+        app.log(name, &format!("⬇ Installing {}...", name));
+        let mut step_progress = |percent: u8, message: &str| app.progress(name, percent, "running", message);
+        if let Err(e) = component.install(app, &mut step_progress) {
+            return (StepOutcome::Failed, format!("❌ {} failed to install: {}", name, e));
+        }
+
+        match component.verify(app) {
+            Ok(summary) => (
+                StepOutcome::Installed,
+                format!("✅ {} installed ({}).", name, summary),
+            ),
+            Err(e) => (
+                StepOutcome::Failed,
+                format!("❌ {} installed but did not verify: {}", name, e),
+            ),
+        }
+    }
+}
+
+/// Display name of the first dependency of `component` that failed or was
+/// blocked itself; `failed` maps ids to display names.
+fn blocked_by<A>(component: &dyn Component<A>, failed: &HashMap<String, String>) -> Option<String> {
+    component
+        .depends_on()
+        .into_iter()
+        .find_map(|d| failed.get(&d).cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// Stands in for the `AppHandle`: records events and installs.
+    #[derive(Default)]
+    struct Recorder {
+        installed: RefCell<Vec<String>>,
+        progress: RefCell<Vec<(String, u8, String)>>,
+    }
+
+    impl EngineEvents for Recorder {
+        fn log(&self, _component: &str, _message: &str) {}
+
+        fn progress(&self, component: &str, percent: u8, status: &str, _message: &str) {
+            self.progress
+                .borrow_mut()
+                .push((component.to_string(), percent, status.to_string()));
+        }
+    }
+
+    struct Fake {
+        id: &'static str,
+        deps: &'static [&'static str],
+        fails: bool,
+    }
+
+    impl Component<Recorder> for Fake {
+        fn id(&self) -> &str {
+            self.id
+        }
+        fn display_name(&self) -> &str {
+            self.id
+        }
+        fn weight(&self) -> u8 {
+            1
+        }
+        fn depends_on(&self) -> Vec<String> {
+            self.deps.iter().map(|d| d.to_string()).collect()
+        }
+        fn detect(&self, _app: &Recorder) -> Detection {
+            Detection::Missing
+        }
+        fn install(&self, app: &Recorder, progress: &mut dyn FnMut(u8, &str)) -> Result<(), String> {
+            app.installed.borrow_mut().push(self.id.to_string());
+            progress(50, "halfway");
+            if self.fails {
+                Err("disk full".into())
+            } else {
+                Ok(())
+            }
+        }
+        fn verify(&self, _app: &Recorder) -> Result<String, String> {
+            Ok(String::new())
+        }
+        fn uninstall(&self, _app: &Recorder) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    fn engine(nodes: &[(&'static str, &'static [&'static str])]) -> InstallEngine<Recorder> {
+        failing_engine(nodes, &[])
+    }
+
+    fn failing_engine(
+        nodes: &[(&'static str, &'static [&'static str])],
+        failing: &[&str],
+    ) -> InstallEngine<Recorder> {
+        nodes.iter().fold(InstallEngine::new(), |e, &(id, deps)| {
+            e.with(Fake {
+                id,
+                deps,
+                fails: failing.contains(&id),
+            })
+        })
+    }
+
+    fn ids(engine: &InstallEngine<Recorder>) -> Result<Vec<String>, String> {
+        engine
+            .ordered()
+            .map(|order| order.iter().map(|c| c.id().to_string()).collect())
+    }
+
+    #[test]
+    fn dependencies_come_first_and_registration_order_is_kept() {
+        let e = engine(&[
+            ("model", &["ollama"]),
+            ("n8n", &["node"]),
+            ("ollama", &[]),
+            ("node", &[]),
+        ]);
+        assert_eq!(ids(&e).unwrap(), ["ollama", "model", "node", "n8n"]);
+    }
+
+    #[test]
+    fn unknown_dependencies_are_rejected() {
+        let e = engine(&[("n8n", &["node"])]);
+        let err = ids(&e).unwrap_err();
+        assert!(err.contains("'n8n'") && err.contains("'node'"), "{}", err);
+    }
+
+    #[test]
+    fn cycles_are_reported_with_their_members() {
+        let e = engine(&[("ollama", &[]), ("a", &["b"]), ("b", &["a"])]);
+        assert_eq!(ids(&e).unwrap_err(), "Dependency cycle between: a, b");
+    }
+
+    #[test]
+    fn failures_block_every_dependent_transitively() {
+        let e = failing_engine(
+            &[
+                ("node", &[]),
+                ("n8n", &["node"]),
+                ("workflows", &["n8n"]),
+                ("ollama", &[]),
+            ],
+            &["node"],
+        );
+        let app = Recorder::default();
+        let mut seen = Vec::new();
+        let report = e
+            .run(&app, EngineMode::Install, |step| seen.push(step.id.clone()))
+            .unwrap();
+
+        assert!(!report.success);
+        let outcomes: Vec<(&str, StepOutcome)> =
+            report.steps.iter().map(|s| (s.id.as_str(), s.outcome)).collect();
+        assert_eq!(
+            outcomes,
+            [
+                ("node", StepOutcome::Failed),
+                ("n8n", StepOutcome::Blocked),
+                ("workflows", StepOutcome::Blocked),
+                ("ollama", StepOutcome::Installed),
+            ]
+        );
+        assert!(report.steps[0].message.contains("disk full"));
+        assert!(report.steps[1].message.contains("'node'"), "{}", report.steps[1].message);
+        assert!(report.steps[2].message.contains("'n8n'"), "{}", report.steps[2].message);
+        assert_eq!(report.steps[3].overall_percent, 100);
+        assert_eq!(seen, ["node", "n8n", "workflows", "ollama"]);
+
+        // Blocked components are never attempted and end as `skipped`
+        assert_eq!(*app.installed.borrow(), ["node", "ollama"]);
+        let finals: Vec<(String, String)> = app
+            .progress
+            .borrow()
+            .iter()
+            .filter(|(_, percent, _)| *percent == 100)
+            .map(|(id, _, status)| (id.clone(), status.clone()))
+            .collect();
+        assert_eq!(
+            finals,
+            [
+                ("node".to_string(), "failed".to_string()),
+                ("n8n".to_string(), "skipped".to_string()),
+                ("workflows".to_string(), "skipped".to_string()),
+                ("ollama".to_string(), "done".to_string()),
+            ]
+        );
+        assert!(report.summary().contains("node, n8n, workflows"));
+    }
+
+    #[test]
+    fn install_progress_reaches_the_step_events() {
+        let e = engine(&[("model", &[])]);
+        let app = Recorder::default();
+        e.run(&app, EngineMode::Install, |_| {}).unwrap();
+        assert_eq!(
+            *app.progress.borrow(),
+            [
+                ("model".to_string(), 0, "running".to_string()),
+                ("model".to_string(), 50, "running".to_string()),
+                ("model".to_string(), 100, "done".to_string()),
+            ]
+        );
+    }
+}
@@ -1,9 +1,12 @@
 use serde::Serialize;
-use std::process::Command;
-use std::time::Duration;
 use tauri::{AppHandle, Emitter, WebviewWindowBuilder};
 use tauri::WebviewUrl;
 
+use crate::discovery::{self, Tool};
+use crate::installer::install_n8n_real::installed_n8n;
+use crate::installer::node_runtime::managed_runtime;
+use crate::version::{self, CompatibilityReport};
+
 #[derive(Serialize, Debug)]
 pub struct EnvironmentStatus {
     pub node_installed: bool,
@@ -12,57 +15,42 @@ pub struct EnvironmentStatus {
     pub n8n_version: Option<String>,
     pub ollama_installed: bool,
     pub ollama_version: Option<String>,
+    /// Installed versions checked against the compatibility matrix.
+    pub compatibility: CompatibilityReport,
 }
 
 #[tauri::command]
 pub fn validate_environment() -> EnvironmentStatus {
+    // n8n lives in the Workbench's private prefix, not on PATH
+    let n8n = installed_n8n(managed_runtime().as_ref());
+    let node = discovery::locate_candidate(Tool::Node);
+    let ollama = discovery::locate_candidate(Tool::Ollama);
     EnvironmentStatus {
-        node_installed: check_exists("node"),
-        node_version: get_version("node", "-v"),
-        n8n_installed: check_exists("n8n"),
-        n8n_version: get_version("n8n", "--version"),
-        ollama_installed: check_exists("ollama"),
-        ollama_version: get_version("ollama", "--version"),
+        node_installed: node.is_some(),
+        node_version: node.and_then(|c| c.version),
+        n8n_installed: n8n.is_some(),
+        n8n_version: n8n.map(|(_, version)| version),
+        ollama_installed: ollama.is_some(),
+        ollama_version: ollama.and_then(|c| c.version),
+        compatibility: version::report(version::check_installed()),
     }
 }
 
-/// ✅ Quick check: does a binary exist in PATH?
-fn check_exists(cmd: &str) -> bool {
-    Command::new(cmd)
-        .arg("--version")
-        .output()
-        .map(|o| o.status.success())
-        .unwrap_or(false)
-}
-
-/// 🧾 Capture version output
-fn get_version(cmd: &str, arg: &str) -> Option<String> {
-    Command::new(cmd)
-        .arg(arg)
-        .output()
-        .ok()
-        .and_then(|o| String::from_utf8(o.stdout).ok())
-        .map(|s| s.trim().to_string())
-}
-
 /// 🧠 Launch n8n inside an embedded WebView (Tauri 2 syntax)
 #[tauri::command]
 pub async fn launch_n8n_internally(app: AppHandle) -> Result<(), String> {
     // Check if n8n is installed
-    if !check_exists("n8n") {
+    if installed_n8n(managed_runtime().as_ref()).is_none() {
         return Err("n8n is not installed.".into());
     }
 
-    // Start n8n background process
-    tauri::async_runtime::spawn(async move {
-        let _ = Command::new("n8n")
-            .arg("start")
-            .spawn()
-            .expect("Failed to start n8n background process");
-    });
-
-    // Wait for server to boot
-    std::thread::sleep(Duration::from_secs(5));
+    // Start n8n under supervision (no-op if already up) and wait for /healthz
+    let port = tauri::async_runtime::spawn_blocking({
+        let app = app.clone();
+        move || crate::n8n_manager::start_n8n(&app)
+    })
+    .await
+    .map_err(|e| format!("n8n launch task failed: {}", e))??;
 
     // Notify frontend
     app.emit(
@@ -72,7 +60,11 @@ pub async fn launch_n8n_internally(app: AppHandle) -> Result<(), String> {
     .ok();
 
     // ✅ Create a new Webview window pointing to the external URL
-    let url = WebviewUrl::External("http://localhost:5678".parse().unwrap());
+    let url = WebviewUrl::External(
+        format!("http://localhost:{}", port)
+            .parse()
+            .map_err(|e| format!("Invalid n8n URL: {}", e))?,
+    );
 
     WebviewWindowBuilder::new(&app, "n8n_webview", url)
         .title("Gignaati Workbench — Agentic Platform")
@@ -1,9 +1,12 @@
 use tauri::{AppHandle, Emitter};
 use serde::Serialize;
-use std::process::{Command, Stdio};
-use std::io::{BufRead, BufReader};
-use std::thread;
-use std::path::Path;
+use std::process::Command;
+use std::path::{Path, PathBuf};
+
+use crate::config::AppConfig;
+use crate::discovery::{self, Tool};
+use crate::installer::n8n_versions::install_version;
+use crate::installer::node_runtime::{managed_runtime, NodeRuntime};
 
 #[derive(Serialize, Clone)]
 struct ComponentLog {
@@ -20,182 +23,138 @@ pub fn install_n8n_real(app: AppHandle) -> Result<(), String> {
         message: "⬇ Checking Agentic Platform (n8n) installation...".into(),
     }).ok();
 
-    // === Step 1: Detect existing n8n ===
-    if let Some(existing_path) = detect_existing_n8n() {
-        if let Ok(output) = Command::new(&existing_path).arg("--version").output() {
-            if output.status.success() {
-                let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                app.emit("component-log", ComponentLog {
-                    component: component_name.into(),
-                    message: format!(
-                        "✅ n8n already installed at '{}' (version {}). Skipping reinstall.",
-                        existing_path, version
-                    ),
-                }).ok();
-
-                // ✨ Friendly summary for UI
-                app.emit("component-log", ComponentLog {
-                    component: component_name.into(),
-                    message: "✅ Already installed — no action required.".into(),
-                }).ok();
-
-                app.emit("component-progress", serde_json::json!({
-                    "component": component_name,
-                    "percent": 100,
-                    "status": "done",
-                    "message": "Agentic Platform already installed.",
-                    "eta_seconds": 0
-                })).ok();
-
-                return Ok(());
-            }
-        }
-    }
+    let runtime = managed_runtime();
 
-    // === Step 2: Locate npm ===
-    let npm_cmd = detect_npm_path().ok_or_else(|| {
+    // === Step 1: Detect existing n8n ===
+    if let Some((existing_path, version)) = installed_n8n(runtime.as_ref()) {
         app.emit("component-log", ComponentLog {
             component: component_name.into(),
-            message: "⚠ npm not found. Ensure Node.js is installed and added to PATH.".into(),
+            message: format!(
+                "✅ n8n already installed at '{}' (version {}). Skipping reinstall.",
+                existing_path, version
+            ),
         }).ok();
-        "npm not found in PATH or standard locations.".to_string()
-    })?;
-
-    app.emit("component-log", ComponentLog {
-        component: component_name.into(),
-        message: format!("🧠 Using npm from '{}'", npm_cmd),
-    }).ok();
 
-    // === Step 3: Run installation ===
-    let mut cmd = Command::new(&npm_cmd)
-        .args(["install", "-g", "n8n@latest", "--legacy-peer-deps", "--force"])
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| format!("Failed to start npm: {}", e))?;
-
-    let stdout = cmd.stdout.take().ok_or("Failed to capture stdout")?;
-    let stderr = cmd.stderr.take().ok_or("Failed to capture stderr")?;
-
-    let app_out = app.clone();
-    let comp_out = component_name.to_string();
-    thread::spawn(move || {
-        let reader = BufReader::new(stdout);
-        for line in reader.lines().flatten() {
-            if let Some(filtered) = filter_log_line(&line, false) {
-                app_out.emit("component-log", ComponentLog {
-                    component: comp_out.clone(),
-                    message: filtered,
-                }).ok();
-            }
-        }
-    });
-
-    let app_err = app.clone();
-    let comp_err = component_name.to_string();
-    thread::spawn(move || {
-        let reader = BufReader::new(stderr);
-        for line in reader.lines().flatten() {
-            if let Some(filtered) = filter_log_line(&line, false) {
-                app_err.emit("component-log", ComponentLog {
-                    component: comp_err.clone(),
-                    message: filtered,
-                }).ok();
-            }
-        }
-    });
-
-    let status = cmd.wait().map_err(|e| format!("Failed to wait on npm: {}", e))?;
-
-    if status.success() {
+        // ✨ Friendly summary for UI
         app.emit("component-log", ComponentLog {
             component: component_name.into(),
-            message: "✅ n8n successfully installed globally!".into(),
+            message: "✅ Already installed — no action required.".into(),
         }).ok();
 
         app.emit("component-progress", serde_json::json!({
             "component": component_name,
             "percent": 100,
             "status": "done",
-            "message": "Agentic Platform (n8n) installation complete.",
+            "message": "Agentic Platform already installed.",
             "eta_seconds": 0
         })).ok();
 
-        Ok(())
-    } else {
-        app.emit("component-progress", serde_json::json!({
-            "component": component_name,
-            "percent": 100,
-            "status": "failed",
-            "message": "n8n installation failed.",
-            "eta_seconds": 0
-        })).ok();
-        Err(format!("❌ n8n installation failed with status {:?}", status))
+        return Ok(());
     }
-}
 
-/// ✅ Detect existing n8n installation (handles user & global npm folders)
-fn detect_existing_n8n() -> Option<String> {
-    if Command::new("n8n").arg("--version").output().is_ok() {
-        return Some("n8n".into());
-    }
+    // === Step 2: Install the pinned (or latest) version into a private prefix ===
+    // Finds npm (preferring the installer-owned runtime) and backs up existing
+    // n8n data first; a new version migrates it on first start
+    let requested = AppConfig::load_or_default()
+        .n8n_pinned_version
+        .unwrap_or_else(|| "latest".into());
+    let state = install_version(&app, &requested)?;
 
-    if let Ok(appdata) = std::env::var("APPDATA") {
-        let path = format!("{appdata}\\npm\\n8n.cmd");
-        if Path::new(&path).exists() {
-            println!("✅ Found n8n at {}", path);
-            return Some(path);
-        }
-    }
+    app.emit("component-log", ComponentLog {
+        component: component_name.into(),
+        message: format!(
+            "✅ n8n {} installed into the Workbench's private directory.",
+            state.active.as_deref().unwrap_or(&requested)
+        ),
+    }).ok();
 
-    let candidates = [
-        r"C:\ProgramData\npm\n8n.cmd",
-        r"C:\Program Files\nodejs\n8n.cmd",
-        r"C:\Program Files (x86)\nodejs\n8n.cmd",
-        "/usr/local/bin/n8n",
-        "/usr/bin/n8n",
-    ];
-    for c in candidates {
-        if Path::new(c).exists() {
-            println!("✅ Found n8n at {}", c);
-            return Some(c.to_string());
-        }
-    }
+    Ok(())
+}
 
-    println!("⚠ n8n not found in any known paths");
-    None
+/// ✅ Locate a working n8n and return `(path, version)`
+pub(crate) fn installed_n8n(runtime: Option<&NodeRuntime>) -> Option<(String, String)> {
+    let path = recorded_n8n()?;
+    let version = probe_n8n(&path, runtime)?;
+    Some((path.to_string_lossy().to_string(), version))
 }
 
-/// 🔍 Detect npm binary
-fn detect_npm_path() -> Option<String> {
-    if Command::new("npm").arg("-v").output().is_ok() {
-        return Some("npm".to_string());
+/// ✅ The n8n recorded in AppConfig: the Workbench-managed version, or an
+/// existing install the user opted into. Nothing else is guessed.
+pub(crate) fn recorded_n8n() -> Option<PathBuf> {
+    let cfg = AppConfig::load_or_default();
+    if cfg.n8n_version.is_none() && !cfg.n8n_use_existing {
+        return None;
+    }
+    let path = PathBuf::from(cfg.n8n_path?);
+    if path.exists() {
+        return Some(path);
     }
+    // Older configs recorded a bare command name found on PATH
+    which::which(&path).ok()
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ExistingN8n {
+    pub path: String,
+    pub version: String,
+}
 
-    let candidates = [
-        r"C:\Program Files\nodejs\npm.cmd",
-        r"C:\Program Files (x86)\nodejs\npm.cmd",
-        r"C:\Users\%USERNAME%\AppData\Roaming\npm\npm.cmd",
-        "/usr/local/bin/npm",
-        "/usr/bin/npm",
-        "/opt/homebrew/bin/npm",
-    ];
-    for c in candidates {
-        let expanded = shellexpand::full(c).unwrap_or_else(|_| c.into()).to_string();
-        if Path::new(&expanded).exists() {
-            return Some(expanded);
+/// 🔎 n8n installs outside the Workbench (PATH, version managers, global
+/// npm prefix) that the user can opt into with `use_existing_n8n`.
+#[tauri::command]
+pub fn find_existing_n8n() -> Vec<ExistingN8n> {
+    let mut found: Vec<ExistingN8n> = discovery::candidates(Tool::N8n, true)
+        .into_iter()
+        .filter_map(|c| {
+            Some(ExistingN8n {
+                path: c.path,
+                version: c.version?,
+            })
+        })
+        .collect();
+
+    // Earlier Workbench releases installed into the managed runtime's global prefix
+    let runtime = managed_runtime();
+    if let Some(rt) = &runtime {
+        let legacy = rt.global_bin("n8n");
+        let known = found.iter().any(|e| Path::new(&e.path) == legacy);
+        if !known {
+            if let Some(version) = probe_n8n(&legacy, runtime.as_ref()) {
+                found.push(ExistingN8n {
+                    path: legacy.to_string_lossy().to_string(),
+                    version,
+                });
+            }
         }
     }
-    None
+    found
 }
 
-/// 🧹 Filter noisy npm logs
-fn filter_log_line(line: &str, dev: bool) -> Option<String> {
-    if dev { return Some(line.to_string()); }
-    let l = line.trim();
-    if l.starts_with("npm WARN") || l.contains("deprecated") { return None; }
-    if l.contains("added ") { return Some(format!("📦 {}", l)); }
-    if l.contains("up to date") { return Some(format!("✅ {}", l)); }
-    if l.contains("ERR!") { return Some(format!("❌ {}", l)); }
-    if !l.is_empty() { Some(l.to_string()) } else { None }
+/// 📌 Use an n8n installed outside the Workbench instead of a managed version
+#[tauri::command]
+pub fn use_existing_n8n(path: String) -> Result<ExistingN8n, String> {
+    let version = probe_n8n(Path::new(&path), managed_runtime().as_ref())
+        .ok_or_else(|| format!("'{}' did not respond to n8n --version", path))?;
+
+    AppConfig::modify(|cfg| {
+        cfg.n8n_use_existing = true;
+        cfg.n8n_path = Some(path.clone());
+        cfg.n8n_version = None;
+        cfg.n8n_installed = true;
+    })?;
+    discovery::invalidate(Tool::N8n);
+    Ok(ExistingN8n { path, version })
+}
+
+fn probe_n8n(path: &Path, runtime: Option<&NodeRuntime>) -> Option<String> {
+    let mut probe = Command::new(path);
+    probe.arg("--version");
+    if let Some(rt) = runtime {
+        rt.apply_env(&mut probe);
+    }
+    let output = probe.output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
@@ -3,6 +3,8 @@
 
 // === Modules ===
 pub mod nodejs;
+pub mod node_runtime;      // ✅ app-private Node.js provisioning
+pub mod download;          // ✅ shared download / checksum / unpack helpers
 pub mod n8n;
 pub mod ollama;
 pub mod runner;
@@ -10,11 +12,16 @@ pub mod smart;
 pub mod progress;
 pub mod cleanup;
 pub mod install_n8n_real;  // ✅ real npm-based n8n installer
+pub mod n8n_versions;      // ✅ side-by-side n8n versions with rollback
 pub mod ollama_real;       // ✅ guided Ollama installer
+pub mod ollama_runtime;    // ✅ app-private Ollama provisioning and user service
 pub mod environment;
+pub mod engine;            // ✅ dependency-ordered installation engine
+pub mod components;        // ✅ Node.js / n8n / Ollama / model components
 
 // === Re-exports for lib.rs ===
 pub use nodejs::check_nodejs_installed;
+pub use node_runtime::install_node_runtime;
 pub use n8n::{check_n8n_installed, install_n8n};
 pub use ollama::{check_ollama_installed, install_ollama};
 pub use runner::run_installation;
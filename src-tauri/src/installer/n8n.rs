@@ -1,8 +1,9 @@
-use std::process::{Command, Stdio};
-use std::io::{BufRead, BufReader};
 use serde::Serialize;
 use tauri::{AppHandle, Emitter};
 
+use crate::installer::install_n8n_real::{install_n8n_real, installed_n8n};
+use crate::installer::node_runtime::managed_runtime;
+
 #[derive(Serialize)]
 pub struct N8nStatus {
     pub installed: bool,
@@ -12,22 +13,13 @@ pub struct N8nStatus {
 
 #[tauri::command]
 pub fn check_n8n_installed() -> Result<N8nStatus, String> {
-    let output = if cfg!(target_os = "windows") {
-        Command::new("cmd").args(["/C", "n8n --version"]).output()
-    } else {
-        Command::new("sh").arg("-c").arg("n8n --version").output()
-    };
-
-    match output {
-        Ok(out) if out.status.success() => {
-            let version = String::from_utf8_lossy(&out.stdout).trim().to_string();
-            Ok(N8nStatus {
-                installed: true,
-                version: Some(version),
-                message: "✅ Agentic Platform detected.".into(),
-            })
-        }
-        _ => Ok(N8nStatus {
+    match installed_n8n(managed_runtime().as_ref()) {
+        Some((_, version)) => Ok(N8nStatus {
+            installed: true,
+            version: Some(version),
+            message: "✅ Agentic Platform detected.".into(),
+        }),
+        None => Ok(N8nStatus {
             installed: false,
             version: None,
             message: "⚠ Agentic Platform not found.".into(),
@@ -35,41 +27,17 @@ pub fn check_n8n_installed() -> Result<N8nStatus, String> {
     }
 }
 
+/// Legacy entry point; installs into the Workbench's private prefix like
+/// `install_n8n_real` rather than the global npm tree.
 #[tauri::command]
 pub fn install_n8n(app: AppHandle) -> Result<(), String> {
     app.emit("install-log", "⬇ Installing Agentic Platform...").ok();
 
-    let mut cmd = if cfg!(target_os = "windows") {
-        Command::new("cmd")
-            .args(["/C", "npm install -g n8n"])
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .map_err(|e| format!("Failed to start install: {}", e))?
-    } else {
-        Command::new("sh")
-            .arg("-c")
-            .arg("npm install -g n8n")
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .map_err(|e| format!("Failed to start install: {}", e))?
-    };
-
-    let stdout = cmd.stdout.take().ok_or("No stdout from install")?;
-    let reader = BufReader::new(stdout);
-
-    for line in reader.lines() {
-        let line = line.unwrap_or_default();
-        app.emit("install-log", format!("[Agentic] {}", line)).ok();
-    }
-
-    let output = cmd.wait_with_output().map_err(|e| e.to_string())?;
-    if output.status.success() {
-        app.emit("install-log", "✅ Agentic Platform installation completed.").ok();
-        Ok(())
-    } else {
-        let err = String::from_utf8_lossy(&output.stderr);
-        Err(format!("Agentic Platform install failed: {}", err))
+    match install_n8n_real(app.clone()) {
+        Ok(()) => {
+            app.emit("install-log", "✅ Agentic Platform installation completed.").ok();
+            Ok(())
+        }
+        Err(e) => Err(format!("Agentic Platform install failed: {}", e)),
     }
 }
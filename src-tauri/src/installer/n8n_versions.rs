@@ -0,0 +1,529 @@
+// 🧩 Gignaati Workbench Installer
+// 🔧 n8n Version Manager — list, install, pin and roll back n8n releases
+//
+// Each n8n version is installed with `npm install --prefix` into its own
+// directory under `<app data>/n8n/versions/<version>`. The active version and
+// the one before it are kept on disk, so a rollback is just switching the
+// recorded binary back. Versions come from the npm registry, or from a mirror
+// set as `npm_registry` in AppConfig. An existing n8n is backed up before any
+// version change.
+
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::config::{redact_url, redact_urls, AppConfig};
+use crate::discovery::{self, Tool};
+use crate::installer::node_runtime::managed_runtime;
+use crate::n8n_backup;
+use crate::n8n_manager::start_n8n;
+use crate::supervisor::{self, ServiceKind};
+use crate::version::{self, Verdict};
+
+const DEFAULT_NPM_REGISTRY: &str = "https://registry.npmjs.org";
+const COMPONENT: &str = "Agentic Platform";
+const DEFAULT_LIST_LIMIT: usize = 50;
+
+#[derive(Serialize, Clone)]
+struct ComponentLog {
+    component: String,
+    message: String,
+}
+
+#[derive(Serialize, Clone)]
+struct ComponentProgress {
+    component: String,
+    percent: u8,
+    status: String,
+    message: String,
+    eta_seconds: Option<u32>,
+}
+
+/// The parts of an npm packument we use.
+#[derive(Deserialize)]
+struct Packument {
+    #[serde(rename = "dist-tags", default)]
+    dist_tags: BTreeMap<String, String>,
+    #[serde(default)]
+    versions: BTreeMap<String, PackageVersion>,
+}
+
+#[derive(Deserialize)]
+struct PackageVersion {
+    #[serde(default)]
+    engines: BTreeMap<String, String>,
+    #[serde(default)]
+    deprecated: Option<serde_json::Value>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct N8nVersionInfo {
+    pub version: String,
+    /// Registry dist-tags pointing here (`latest`, `next`, ...).
+    pub tags: Vec<String>,
+    /// `engines.node` range from the package.
+    pub node_engine: Option<String>,
+    pub deprecated: bool,
+    pub installed: bool,
+    pub active: bool,
+    pub previous: bool,
+    pub pinned: bool,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct N8nVersionState {
+    pub active: Option<String>,
+    /// Kept on disk for rollback.
+    pub previous: Option<String>,
+    pub pinned: Option<String>,
+    pub registry: String,
+    /// Versions present under the versions directory.
+    pub installed: Vec<String>,
+}
+
+// === Registry ===
+
+pub(crate) fn registry_url(cfg: &AppConfig) -> String {
+    cfg.npm_registry
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+        .unwrap_or(DEFAULT_NPM_REGISTRY)
+        .trim_end_matches('/')
+        .to_string()
+}
+
+fn fetch_packument(registry: &str) -> Result<Packument, String> {
+    let url = format!("{}/n8n", registry);
+    let response = ureq::AgentBuilder::new()
+        .timeout_connect(Duration::from_secs(15))
+        .timeout_read(Duration::from_secs(60))
+        .build()
+        .get(&url)
+        // Abbreviated metadata: versions, dist-tags and engines only
+        .set("Accept", "application/vnd.npm.install-v1+json")
+        .call()
+        .map_err(|e| redact_urls(&format!("Failed to query {}: {}", url, e)))?;
+
+    // Too large for `into_string`'s limit; stream it instead
+    serde_json::from_reader(response.into_reader())
+        .map_err(|e| format!("Unexpected response from {}: {}", redact_url(&url), e))
+}
+
+/// Turn `latest`, a dist-tag or an exact version into a published version.
+fn resolve_version(packument: &Packument, requested: &str) -> Result<String, String> {
+    let requested = requested.trim().trim_start_matches('v');
+    if packument.versions.contains_key(requested) {
+        return Ok(requested.to_string());
+    }
+    if let Some(version) = packument.dist_tags.get(requested) {
+        return Ok(version.clone());
+    }
+    Err(format!(
+        "n8n {} is not published on this registry (latest is {})",
+        requested,
+        packument
+            .dist_tags
+            .get("latest")
+            .map(String::as_str)
+            .unwrap_or("unknown")
+    ))
+}
+
+/// Ordering key for registry versions; unparsable tags sort last.
+fn version_key(version: &str) -> Option<Version> {
+    Version::parse(version).ok()
+}
+
+fn is_exact_version(version: &str) -> bool {
+    Version::parse(version).is_ok()
+}
+
+// === Local installs ===
+
+pub(crate) fn versions_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join("n8n")
+        .join("versions"))
+}
+
+/// The `n8n` launcher inside a `--prefix` install.
+pub(crate) fn prefix_bin(prefix: &Path) -> PathBuf {
+    let bin = prefix.join("node_modules").join(".bin");
+    if cfg!(target_os = "windows") {
+        bin.join("n8n.cmd")
+    } else {
+        bin.join("n8n")
+    }
+}
+
+fn installed_versions(dir: &Path) -> Vec<String> {
+    let mut versions: Vec<String> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|e| e.file_name().into_string().ok())
+                .filter(|name| !name.starts_with('.') && prefix_bin(&dir.join(name)).exists())
+                .collect()
+        })
+        .unwrap_or_default();
+    versions.sort_by_key(|v| std::cmp::Reverse(version_key(v)));
+    versions
+}
+
+/// `n8n --version` for a binary, using the managed Node.js when present.
+fn probe_version(bin: &Path) -> Option<String> {
+    let mut cmd = Command::new(bin);
+    cmd.arg("--version");
+    if let Some(rt) = managed_runtime() {
+        rt.apply_env(&mut cmd);
+    }
+    let output = cmd.output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Install `version` into its own prefix unless it is already there.
+fn ensure_installed(app: &AppHandle, cfg: &AppConfig, version: &str) -> Result<PathBuf, String> {
+    let dir = versions_dir(app)?;
+    let prefix = dir.join(version);
+    let bin = prefix_bin(&prefix);
+    if probe_version(&bin).is_some() {
+        log(app, &format!("✅ n8n {} is already installed.", version));
+        return Ok(bin);
+    }
+
+    let runtime = managed_runtime();
+    let npm = runtime
+        .as_ref()
+        .map(|rt| rt.npm.to_string_lossy().to_string())
+        .or_else(|| discovery::locate(Tool::Npm))
+        .ok_or("npm not found. Provision Node.js first.")?;
+
+    // Install next to the final location, then rename: a half-finished
+    // install never looks like a usable version
+    let partial = dir.join(format!(".partial-{}", version));
+    let _ = fs::remove_dir_all(&partial);
+    fs::create_dir_all(&partial)
+        .map_err(|e| format!("Failed to create '{}': {}", partial.display(), e))?;
+
+    log(app, &format!("⬇ Installing n8n {} into '{}'...", version, prefix.display()));
+    progress(app, 20, "running", &format!("Installing n8n {}...", version));
+
+    let mut cmd = Command::new(&npm);
+    cmd.arg("install")
+        .arg("--prefix")
+        .arg(&partial)
+        .args(["--no-audit", "--no-fund", "--omit=dev"])
+        .arg(format!("--registry={}", registry_url(cfg)))
+        .arg(format!("n8n@{}", version));
+    if let Some(rt) = &runtime {
+        rt.apply_env(&mut cmd);
+    }
+    let output = cmd
+        .output()
+        .map_err(|e| format!("Failed to run npm: {}", e))?;
+    if !output.status.success() {
+        let _ = fs::remove_dir_all(&partial);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let errors: Vec<&str> = stderr.lines().filter(|l| l.contains("ERR!")).collect();
+        return Err(format!(
+            "❌ npm could not install n8n {}: {}",
+            version,
+            errors[errors.len().saturating_sub(5)..].join(" | ")
+        ));
+    }
+
+    let _ = fs::remove_dir_all(&prefix);
+    fs::rename(&partial, &prefix)
+        .map_err(|e| format!("Failed to finalize '{}': {}", prefix.display(), e))?;
+
+    let reported = probe_version(&bin)
+        .ok_or_else(|| format!("n8n {} was installed but does not start", version))?;
+    log(app, &format!("✅ n8n {} installed (reports '{}').", version, reported));
+    Ok(bin)
+}
+
+/// Make `version` the active one and keep the previous active for rollback.
+/// n8n is restarted if it was running.
+fn activate(app: &AppHandle, version: &str, bin: &Path) -> Result<(), String> {
+    let was_running = supervisor::stop(app, ServiceKind::N8n);
+
+    AppConfig::modify(|cfg| {
+        if cfg.n8n_version.as_deref() != Some(version) {
+            cfg.n8n_previous_version = cfg.n8n_version.take();
+        }
+        cfg.n8n_version = Some(version.to_string());
+        cfg.n8n_path = Some(bin.to_string_lossy().to_string());
+        cfg.n8n_installed = true;
+        cfg.n8n_use_existing = false;
+    })?;
+    log(app, &format!("🔀 n8n {} is now active.", version));
+
+    prune(app);
+
+    if was_running {
+        start_n8n(app)?;
+    }
+    Ok(())
+}
+
+/// Remove versions other than the active and previous one, and leftovers
+/// from interrupted installs.
+fn prune(app: &AppHandle) {
+    let Ok(dir) = versions_dir(app) else {
+        return;
+    };
+    let cfg = AppConfig::load_or_default();
+    let keep = [cfg.n8n_version, cfg.n8n_previous_version];
+
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !keep.iter().flatten().any(|k| *k == name) {
+            let _ = fs::remove_dir_all(entry.path());
+            if !name.starts_with('.') {
+                log(app, &format!("🧹 Removed n8n {}.", name));
+            }
+        }
+    }
+}
+
+fn state(app: &AppHandle) -> Result<N8nVersionState, String> {
+    let cfg = AppConfig::load()?;
+    Ok(N8nVersionState {
+        installed: installed_versions(&versions_dir(app)?),
+        registry: registry_url(&cfg),
+        active: cfg.n8n_version,
+        previous: cfg.n8n_previous_version,
+        pinned: cfg.n8n_pinned_version,
+    })
+}
+
+/// Install (if needed) and activate `requested` — an exact version, `latest`
+/// or another dist-tag. Refuses to move off a pinned version.
+pub fn install_version(app: &AppHandle, requested: &str) -> Result<N8nVersionState, String> {
+    let cfg = AppConfig::load()?;
+    let registry = registry_url(&cfg);
+
+    log(app, &format!("🔍 Resolving n8n '{}' on {}...", requested, redact_url(&registry)));
+    progress(app, 5, "running", "Resolving n8n version...");
+    let version = resolve_version(&fetch_packument(&registry)?, requested)?;
+
+    check_pin(&cfg, &version, "installing")?;
+    if cfg.n8n_version.as_deref() == Some(version.as_str()) {
+        log(app, &format!("✅ n8n {} is already active.", version));
+        progress(app, 100, "done", "n8n already at the requested version.");
+        return state(app);
+    }
+
+    // The Node.js n8n will run on must be in the range this release supports
+    if let (Some(node), Ok(target)) = (version::located(Tool::Node), Version::parse(&version)) {
+        let finding = version::check_node(&node, Some(&target));
+        match finding.verdict {
+            Verdict::Blocking => {
+                progress(app, 100, "failed", &finding.message);
+                return Err(finding.message);
+            }
+            Verdict::UpgradeRecommended => log(app, &format!("⚠ {}", finding.message)),
+            Verdict::Ok => {}
+        }
+    }
+
+    // A new version migrates the database on first start; keep a way back
+    if let Some(backup) = n8n_backup::backup_before_upgrade(app)? {
+        log(app, &format!("💾 Backed up n8n data as '{}'.", backup.id));
+    }
+    if let Some(active) = &cfg.n8n_version {
+        if version_key(&version) < version_key(active) {
+            log(
+                app,
+                &format!(
+                    "⚠ Downgrading from {} to {}: n8n does not migrate its database backwards. \
+                     If it fails to start, restore the pre-upgrade backup.",
+                    active, version
+                ),
+            );
+        }
+    }
+
+    let bin = ensure_installed(app, &cfg, &version).inspect_err(|e| {
+        progress(app, 100, "failed", e);
+    })?;
+    activate(app, &version, &bin)?;
+
+    progress(app, 100, "done", &format!("n8n {} active.", version));
+    state(app)
+}
+
+/// Switch back to the previous version, unless a different one is pinned.
+pub fn rollback(app: &AppHandle) -> Result<N8nVersionState, String> {
+    let cfg = AppConfig::load()?;
+    let previous = cfg
+        .n8n_previous_version
+        .clone()
+        .ok_or("There is no previous n8n version to roll back to.")?;
+    let bin = prefix_bin(&versions_dir(app)?.join(&previous));
+    if probe_version(&bin).is_none() {
+        return Err(format!("n8n {} is no longer installed; install it again instead.", previous));
+    }
+    check_pin(&cfg, &previous, "rolling back to")?;
+
+    if let Some(backup) = n8n_backup::backup_before_upgrade(app)? {
+        log(app, &format!("💾 Backed up n8n data as '{}'.", backup.id));
+    }
+    log(
+        app,
+        &format!(
+            "⏪ Rolling back n8n from {} to {}...",
+            cfg.n8n_version.as_deref().unwrap_or("unknown"),
+            previous
+        ),
+    );
+    activate(app, &previous, &bin)?;
+    state(app)
+}
+
+/// A pinned version may only be replaced by itself; `action` completes
+/// "unpin it before ...".
+fn check_pin(cfg: &AppConfig, version: &str, action: &str) -> Result<(), String> {
+    match cfg.n8n_pinned_version.as_deref() {
+        Some(pinned) if pinned != version => Err(format!(
+            "n8n is pinned to {}; unpin it before {} {}.",
+            pinned, action, version
+        )),
+        _ => Ok(()),
+    }
+}
+
+// === Commands ===
+
+/// 📋 List n8n versions from the registry (newest first), marking the
+/// installed, active, previous and pinned ones.
+#[tauri::command]
+pub async fn list_n8n_versions(
+    app: AppHandle,
+    include_prereleases: Option<bool>,
+    limit: Option<usize>,
+) -> Result<Vec<N8nVersionInfo>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let cfg = AppConfig::load()?;
+        let packument = fetch_packument(&registry_url(&cfg))?;
+        let installed = installed_versions(&versions_dir(&app)?);
+
+        let mut versions: Vec<N8nVersionInfo> = packument
+            .versions
+            .into_iter()
+            .filter(|(v, _)| include_prereleases.unwrap_or(false) || !v.contains('-'))
+            .map(|(version, meta)| N8nVersionInfo {
+                tags: packument
+                    .dist_tags
+                    .iter()
+                    .filter(|(_, v)| **v == version)
+                    .map(|(tag, _)| tag.clone())
+                    .collect(),
+                node_engine: meta.engines.get("node").cloned(),
+                deprecated: meta.deprecated.is_some(),
+                installed: installed.contains(&version),
+                active: cfg.n8n_version.as_deref() == Some(version.as_str()),
+                previous: cfg.n8n_previous_version.as_deref() == Some(version.as_str()),
+                pinned: cfg.n8n_pinned_version.as_deref() == Some(version.as_str()),
+                version,
+            })
+            .collect();
+        versions.sort_by_key(|v| std::cmp::Reverse(version_key(&v.version)));
+        versions.truncate(limit.unwrap_or(DEFAULT_LIST_LIMIT));
+        Ok(versions)
+    })
+    .await
+    .map_err(|e| format!("Version listing failed: {}", e))?
+}
+
+/// ⬇ Install and activate an n8n version (exact, `latest` or a dist-tag)
+#[tauri::command]
+pub async fn install_n8n_version(app: AppHandle, version: String) -> Result<N8nVersionState, String> {
+    tauri::async_runtime::spawn_blocking(move || install_version(&app, &version))
+        .await
+        .map_err(|e| format!("n8n install task failed: {}", e))?
+}
+
+/// ⏪ Re-activate the previously active n8n version
+#[tauri::command]
+pub async fn rollback_n8n(app: AppHandle) -> Result<N8nVersionState, String> {
+    tauri::async_runtime::spawn_blocking(move || rollback(&app))
+        .await
+        .map_err(|e| format!("n8n rollback task failed: {}", e))?
+}
+
+/// 📌 Pin n8n to a certified version (`None` to unpin)
+#[tauri::command]
+pub fn pin_n8n_version(version: Option<String>) -> Result<(), String> {
+    let version = version.map(|v| v.trim().trim_start_matches('v').to_string());
+    if let Some(v) = &version {
+        if !is_exact_version(v) {
+            return Err(format!("'{}' is not an exact version like 1.64.3", v));
+        }
+    }
+    Ok(AppConfig::modify(|cfg| cfg.n8n_pinned_version = version)?)
+}
+
+/// 📋 Active, previous, pinned and installed n8n versions
+#[tauri::command]
+pub fn get_n8n_versions(app: AppHandle) -> Result<N8nVersionState, String> {
+    state(&app)
+}
+
+fn log(app: &AppHandle, message: &str) {
+    app.emit(
+        "component-log",
+        ComponentLog {
+            component: COMPONENT.into(),
+            message: message.into(),
+        },
+    )
+    .ok();
+}
+
+fn progress(app: &AppHandle, percent: u8, status: &str, message: &str) {
+    app.emit(
+        "component-progress",
+        ComponentProgress {
+            component: COMPONENT.into(),
+            percent,
+            status: status.into(),
+            message: message.into(),
+            eta_seconds: None,
+        },
+    )
+    .ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_pinned_version_may_be_activated() {
+        let mut cfg = AppConfig::defaults();
+        assert!(check_pin(&cfg, "1.64.3", "rolling back to").is_ok());
+
+        cfg.n8n_pinned_version = Some("1.70.1".into());
+        assert!(check_pin(&cfg, "1.70.1", "rolling back to").is_ok());
+        assert_eq!(
+            check_pin(&cfg, "1.64.3", "rolling back to").unwrap_err(),
+            "n8n is pinned to 1.70.1; unpin it before rolling back to 1.64.3."
+        );
+    }
+}
@@ -0,0 +1,307 @@
+// 🧩 Gignaati Workbench Installer
+// 🔧 Node.js Runtime Provisioner
+//
+// Downloads a pinned Node.js LTS build into an app-private directory,
+// verifies it against the published SHASUMS256.txt, unpacks it and records
+// the resulting node/npm paths in AppConfig. The mirror is configurable
+// (`node_mirror` in AppConfig or GIGNAATI_NODE_MIRROR) so provisioning can
+// run against a local HTTP mirror.
+
+use serde::Serialize;
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::config::{redact_url, AppConfig};
+use crate::discovery::{self, Tool};
+use crate::installer::download;
+
+/// Node.js LTS release the Workbench is certified against.
+pub const NODE_LTS_VERSION: &str = "20.18.0";
+const DEFAULT_NODE_MIRROR: &str = "https://nodejs.org/dist";
+const COMPONENT: &str = "Node.js";
+
+#[derive(Serialize, Clone)]
+struct ComponentLog {
+    component: String,
+    message: String,
+}
+
+#[derive(Serialize, Clone)]
+struct ComponentProgress {
+    component: String,
+    percent: u8,
+    status: String,
+    message: String,
+    eta_seconds: Option<u32>,
+}
+
+/// An installer-owned Node.js runtime.
+#[derive(Serialize, Clone, Debug)]
+pub struct NodeRuntime {
+    pub version: String,
+    pub root: PathBuf,
+    pub node: PathBuf,
+    pub npm: PathBuf,
+}
+
+impl NodeRuntime {
+    fn at(root: PathBuf, version: &str) -> Self {
+        let bin = bin_dir(&root);
+        let (node, npm) = if cfg!(target_os = "windows") {
+            (bin.join("node.exe"), bin.join("npm.cmd"))
+        } else {
+            (bin.join("node"), bin.join("npm"))
+        };
+        NodeRuntime {
+            version: version.to_string(),
+            root,
+            node,
+            npm,
+        }
+    }
+
+    /// Directory holding `node`, `npm` and globally installed package shims.
+    pub fn bin_dir(&self) -> PathBuf {
+        bin_dir(&self.root)
+    }
+
+    /// Path of a shim installed by `npm install -g` with this runtime.
+    pub fn global_bin(&self, name: &str) -> PathBuf {
+        if cfg!(target_os = "windows") {
+            self.bin_dir().join(format!("{}.cmd", name))
+        } else {
+            self.bin_dir().join(name)
+        }
+    }
+
+    /// PATH with the runtime's bin directory in front, so child scripts
+    /// (`#!/usr/bin/env node`) resolve to the private node.
+    pub fn path_env(&self) -> OsString {
+        let mut paths = vec![self.bin_dir()];
+        if let Some(existing) = env::var_os("PATH") {
+            paths.extend(env::split_paths(&existing));
+        }
+        env::join_paths(paths).unwrap_or_else(|_| self.bin_dir().into_os_string())
+    }
+
+    /// Point a child process at this runtime.
+    pub fn apply_env(&self, cmd: &mut Command) {
+        cmd.env("PATH", self.path_env());
+    }
+}
+
+fn bin_dir(root: &Path) -> PathBuf {
+    if cfg!(target_os = "windows") {
+        root.to_path_buf()
+    } else {
+        root.join("bin")
+    }
+}
+
+/// Return the runtime recorded in AppConfig, if its binaries still exist.
+pub fn managed_runtime() -> Option<NodeRuntime> {
+    let cfg = AppConfig::load_or_default();
+    let root = PathBuf::from(cfg.node_runtime_dir?);
+    let version = cfg
+        .node_version
+        .map(|v| v.trim_start_matches('v').to_string())
+        .unwrap_or_else(|| NODE_LTS_VERSION.to_string());
+    let runtime = NodeRuntime::at(root, &version);
+    (runtime.node.exists() && runtime.npm.exists()).then_some(runtime)
+}
+
+/// ⬇ Provision the pinned Node.js runtime (Tauri command).
+#[tauri::command]
+pub fn install_node_runtime(app: AppHandle) -> Result<NodeRuntime, String> {
+    provision_node(&app)
+}
+
+/// Download, verify and unpack the pinned Node.js build, then record it in AppConfig.
+/// Returns immediately when a matching runtime is already provisioned.
+pub fn provision_node(app: &AppHandle) -> Result<NodeRuntime, String> {
+    provision(app).inspect_err(|e| {
+        log(app, format!("❌ Node.js provisioning failed: {}", e));
+        progress(app, 100, "failed", "Node.js provisioning failed.");
+    })
+}
+
+fn provision(app: &AppHandle) -> Result<NodeRuntime, String> {
+    if let Some(existing) = managed_runtime() {
+        if existing.version == NODE_LTS_VERSION && node_version(&existing).is_some() {
+            log(
+                app,
+                format!(
+                    "✅ Private Node.js v{} already provisioned at '{}'.",
+                    existing.version,
+                    existing.root.display()
+                ),
+            );
+            progress(app, 100, "done", "Node.js runtime already provisioned.");
+            return Ok(existing);
+        }
+    }
+
+    let artifact = platform_artifact(NODE_LTS_VERSION)?;
+    let mirror = node_mirror();
+    let release_url = format!("{}/v{}", mirror.trim_end_matches('/'), NODE_LTS_VERSION);
+
+    let runtime_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join("runtime");
+    let archive_path = runtime_dir.join("downloads").join(&artifact);
+
+    log(
+        app,
+        format!("🔍 Fetching checksums for Node.js v{} from {}", NODE_LTS_VERSION, redact_url(&mirror)),
+    );
+    progress(app, 0, "running", "Fetching Node.js checksums...");
+
+    let shasums = download::fetch_text(&format!("{}/SHASUMS256.txt", release_url))?;
+    let expected = download::find_checksum(&shasums, &artifact)
+        .ok_or_else(|| format!("No checksum published for {}", artifact))?;
+
+    log(app, format!("⬇ Downloading {}...", artifact));
+    let mut last_pct = 0u8;
+    download::download_file(
+        &format!("{}/{}", release_url, artifact),
+        &archive_path,
+        |done, total| {
+            if let Some(total) = total.filter(|t| *t > 0) {
+                let pct = ((done as f64 / total as f64) * 80.0) as u8;
+                if pct != last_pct {
+                    last_pct = pct;
+                    progress(app, pct, "running", &format!("Downloading Node.js... {}%", pct));
+                }
+            }
+        },
+    )?;
+
+    progress(app, 85, "running", "Verifying checksum...");
+    download::verify_sha256(&archive_path, &expected)?;
+    log(app, "🔐 Checksum verified.".into());
+
+    progress(app, 90, "running", "Unpacking Node.js...");
+    let dir_name = artifact
+        .trim_end_matches(".tar.gz")
+        .trim_end_matches(".zip")
+        .to_string();
+    let staging = runtime_dir.join(format!(".staging-{}", dir_name));
+    let _ = fs::remove_dir_all(&staging);
+    download::extract_archive(&archive_path, &staging)?;
+
+    let unpacked = staging.join(&dir_name);
+    if !unpacked.is_dir() {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(format!("Archive did not contain expected folder '{}'", dir_name));
+    }
+
+    let target = runtime_dir.join(&dir_name);
+    let _ = fs::remove_dir_all(&target);
+    fs::rename(&unpacked, &target)
+        .map_err(|e| format!("Failed to move runtime into place: {}", e))?;
+    let _ = fs::remove_dir_all(&staging);
+    let _ = fs::remove_file(&archive_path);
+
+    let runtime = NodeRuntime::at(target, NODE_LTS_VERSION);
+    let reported = node_version(&runtime)
+        .ok_or_else(|| format!("Provisioned node at '{}' does not run", runtime.node.display()))?;
+    if reported.trim_start_matches('v') != NODE_LTS_VERSION {
+        return Err(format!(
+            "Provisioned node reports {} but v{} was expected",
+            reported, NODE_LTS_VERSION
+        ));
+    }
+    let npm_version = npm_version(&runtime);
+
+    let mut cfg = AppConfig::load()?;
+    cfg.node_runtime_dir = Some(runtime.root.to_string_lossy().to_string());
+    cfg.node_path = Some(runtime.node.to_string_lossy().to_string());
+    cfg.npm_path = Some(runtime.npm.to_string_lossy().to_string());
+    cfg.node_version = Some(reported.clone());
+    cfg.npm_version = npm_version;
+    cfg.save()?;
+    discovery::invalidate(Tool::Node);
+    discovery::invalidate(Tool::Npm);
+
+    log(
+        app,
+        format!("✅ Node.js {} ready at '{}'", reported, runtime.root.display()),
+    );
+    progress(app, 100, "done", "Node.js runtime provisioned.");
+
+    Ok(runtime)
+}
+
+/// Archive name published for this OS/arch, e.g. `node-v20.18.0-linux-x64.tar.gz`.
+fn platform_artifact(version: &str) -> Result<String, String> {
+    let os = match env::consts::OS {
+        "linux" => "linux",
+        "macos" => "darwin",
+        "windows" => "win",
+        other => return Err(format!("Unsupported OS for Node.js provisioning: {}", other)),
+    };
+    let arch = match env::consts::ARCH {
+        "x86_64" => "x64",
+        "aarch64" => "arm64",
+        other => return Err(format!("Unsupported architecture for Node.js: {}", other)),
+    };
+    let ext = if os == "win" { "zip" } else { "tar.gz" };
+    Ok(format!("node-v{}-{}-{}.{}", version, os, arch, ext))
+}
+
+pub(crate) fn node_mirror() -> String {
+    AppConfig::load_or_default()
+        .node_mirror
+        .filter(|m| !m.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_NODE_MIRROR.to_string())
+}
+
+fn node_version(runtime: &NodeRuntime) -> Option<String> {
+    let output = Command::new(&runtime.node).arg("-v").output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn npm_version(runtime: &NodeRuntime) -> Option<String> {
+    let mut cmd = Command::new(&runtime.npm);
+    cmd.arg("-v");
+    runtime.apply_env(&mut cmd);
+    let output = cmd.output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn log(app: &AppHandle, message: String) {
+    app.emit(
+        "component-log",
+        ComponentLog {
+            component: COMPONENT.into(),
+            message,
+        },
+    )
+    .ok();
+}
+
+fn progress(app: &AppHandle, percent: u8, status: &str, message: &str) {
+    app.emit(
+        "component-progress",
+        ComponentProgress {
+            component: COMPONENT.into(),
+            percent,
+            status: status.into(),
+            message: message.into(),
+            eta_seconds: None,
+        },
+    )
+    .ok();
+}
@@ -1,61 +1,56 @@
 // 🧩 Gignaati Workbench Installer
 // 🔧 Node.js Detection Module (Phase 3.4.2)
 
-use std::process::Command;
 use serde::Serialize;
 
+use crate::discovery::{self, Tool};
+use crate::installer::install_n8n_real::installed_n8n;
+use crate::installer::node_runtime::managed_runtime;
+use crate::version::{self, Verdict};
+
 #[derive(Serialize)]
 pub struct NodeCheckResult {
     pub installed: bool,
     pub version: Option<String>,
     pub compatible: bool,
+    /// Compatibility verdict against the installed n8n; `None` if not found.
+    pub verdict: Option<Verdict>,
     pub message: String,
 }
 
 #[tauri::command]
 pub fn check_nodejs_installed() -> NodeCheckResult {
-    let output = Command::new("node")
-        .arg("-v")
-        .output();
-
-    match output {
-        Ok(result) => {
-            if result.status.success() {
-                let version_raw = String::from_utf8_lossy(&result.stdout).trim().to_string();
-
-                // Basic version compatibility check (>= 18.0.0)
-                let compatible = version_raw
-                    .trim_start_matches('v')
-                    .split('.')
-                    .next()
-                    .and_then(|v| v.parse::<u32>().ok())
-                    .map(|major| major >= 18)
-                    .unwrap_or(false);
-
-                NodeCheckResult {
-                    installed: true,
-                    version: Some(version_raw.clone()),
-                    compatible,
-                    message: if compatible {
-                        format!("✅ Using existing Node.js {}", version_raw)
-                    } else {
-                        format!("⚠ Node.js {} is outdated (v18+ required)", version_raw)
-                    },
-                }
-            } else {
-                NodeCheckResult {
-                    installed: false,
-                    version: None,
-                    compatible: false,
-                    message: "⚠ Node.js not found on system PATH.".to_string(),
-                }
-            }
-        }
-        Err(_) => NodeCheckResult {
+    let Some(version_raw) = discovery::locate_candidate(Tool::Node).and_then(|c| c.version) else {
+        return NodeCheckResult {
             installed: false,
             version: None,
             compatible: false,
-            message: "⚠ Node.js not found on system PATH.".to_string(),
+            verdict: None,
+            message: "⚠ Node.js not found on this system.".to_string(),
+        };
+    };
+
+    // Against the installed n8n, or the general minimum before n8n is installed
+    let n8n = installed_n8n(managed_runtime().as_ref()).and_then(|(_, raw)| version::parse(&raw));
+    let Some(finding) = version::parse(&version_raw).map(|node| version::check_node(&node, n8n.as_ref())) else {
+        return NodeCheckResult {
+            installed: true,
+            version: Some(version_raw.clone()),
+            compatible: false,
+            verdict: None,
+            message: format!("⚠ Could not read the Node.js version from '{}'", version_raw),
+        };
+    };
+    let compatible = finding.verdict != Verdict::Blocking;
+
+    NodeCheckResult {
+        installed: true,
+        version: Some(version_raw.clone()),
+        compatible,
+        verdict: Some(finding.verdict),
+        message: match finding.verdict {
+            Verdict::Ok => format!("✅ Using existing Node.js {}", version_raw),
+            Verdict::UpgradeRecommended | Verdict::Blocking => format!("⚠ {}", finding.message),
         },
     }
 }
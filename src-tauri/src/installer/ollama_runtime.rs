@@ -0,0 +1,495 @@
+// 🧩 Gignaati Workbench Installer
+// 🔧 Ollama Runtime Provisioner
+//
+// Downloads the pinned Ollama release archive for this OS/arch, verifies it
+// against the release's sha256sum.txt and unpacks it into an app-private
+// directory — no root, no install script. On Linux the server is registered
+// as a systemd user service, on macOS as a LaunchAgent; where that is not
+// available the Workbench starts `ollama serve` itself. The mirror is
+// configurable (`ollama_mirror` in AppConfig or GIGNAATI_OLLAMA_MIRROR) so
+// provisioning can run against a local HTTP mirror laid out like the GitHub
+// releases: `<mirror>/v<version>/<archive>`.
+
+use serde::Serialize;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::config::{redact_url, AppConfig};
+use crate::discovery::{self, Tool};
+use crate::installer::download;
+use crate::ports::registry;
+use crate::version;
+
+/// Ollama release the Workbench is certified against.
+pub const OLLAMA_VERSION: &str = "0.5.7";
+const DEFAULT_OLLAMA_MIRROR: &str = "https://github.com/ollama/ollama/releases/download";
+const COMPONENT: &str = "AI Brain (Ollama)";
+const SERVICE_NAME: &str = "gignaati-ollama";
+const LAUNCH_AGENT: &str = "ai.gignaati.ollama";
+
+#[derive(Serialize, Clone)]
+struct ComponentLog {
+    component: String,
+    message: String,
+}
+
+#[derive(Serialize, Clone)]
+struct ComponentProgress {
+    component: String,
+    percent: u8,
+    status: String,
+    message: String,
+    eta_seconds: Option<u32>,
+}
+
+/// A provisioned Ollama and how its server is run.
+#[derive(Serialize, Clone, Debug)]
+pub struct OllamaRuntime {
+    pub version: String,
+    pub root: PathBuf,
+    pub binary: PathBuf,
+    /// `systemd-user`, `launch-agent`, or `None` when the Workbench
+    /// starts the server itself.
+    pub service: Option<String>,
+}
+
+/// Release archive for this OS/arch and the binary's path inside it.
+fn platform_artifact() -> Result<(&'static str, &'static str), String> {
+    artifact_for(env::consts::OS, env::consts::ARCH)
+}
+
+fn artifact_for(os: &str, arch: &str) -> Result<(&'static str, &'static str), String> {
+    match (os, arch) {
+        ("linux", "x86_64") => Ok(("ollama-linux-amd64.tgz", "bin/ollama")),
+        ("linux", "aarch64") => Ok(("ollama-linux-arm64.tgz", "bin/ollama")),
+        // Universal app bundle; the CLI inside runs `serve` without the menu-bar app
+        ("macos", _) => Ok(("Ollama-darwin.zip", "Ollama.app/Contents/Resources/ollama")),
+        ("windows", "x86_64") => Ok(("ollama-windows-amd64.zip", "ollama.exe")),
+        ("windows", "aarch64") => Ok(("ollama-windows-arm64.zip", "ollama.exe")),
+        (os, arch) => Err(format!("No Ollama release for {} / {}", os, arch)),
+    }
+}
+
+pub(crate) fn ollama_mirror() -> String {
+    AppConfig::load_or_default()
+        .ollama_mirror
+        .filter(|m| !m.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_OLLAMA_MIRROR.to_string())
+}
+
+/// `<app data>/ollama`, parent of one directory per provisioned version.
+pub(crate) fn ollama_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join("ollama"))
+}
+
+/// Download, verify and unpack the pinned Ollama, record it in AppConfig and
+/// register the user service. Returns immediately when it is already there.
+pub fn provision_ollama(app: &AppHandle) -> Result<OllamaRuntime, String> {
+    provision(app).inspect_err(|e| {
+        log(app, format!("❌ Ollama installation failed: {}", e));
+        progress(app, 100, "failed", "Ollama installation failed.");
+    })
+}
+
+fn provision(app: &AppHandle) -> Result<OllamaRuntime, String> {
+    let (artifact, binary_in_archive) = platform_artifact()?;
+    let root = ollama_dir(app)?;
+    let target = root.join(OLLAMA_VERSION);
+    let binary = target.join(binary_in_archive);
+
+    if probe(&binary).as_deref() == Some(OLLAMA_VERSION) {
+        log(app, format!("✅ Ollama {} already installed at '{}'.", OLLAMA_VERSION, target.display()));
+    } else {
+        download_release(app, &root, &target, artifact)?;
+        let found = probe(&binary)
+            .ok_or_else(|| format!("Installed Ollama at '{}' does not run", binary.display()))?;
+        if found != OLLAMA_VERSION {
+            return Err(format!("Installed Ollama reports {} but {} was expected", found, OLLAMA_VERSION));
+        }
+    }
+
+    AppConfig::modify(|cfg| {
+        cfg.ollama_installed = true;
+        cfg.ollama_path = Some(binary.to_string_lossy().to_string());
+        cfg.ollama_version = Some(OLLAMA_VERSION.to_string());
+    })?;
+    discovery::invalidate(Tool::Ollama);
+    remove_old_versions(&root);
+
+    progress(app, 95, "running", "Registering the Ollama service...");
+    let service = match register_service(&binary) {
+        Ok(service) => {
+            if let Some(kind) = &service {
+                log(app, format!("🔁 Ollama registered as a {} service.", kind));
+            }
+            service
+        }
+        Err(e) => {
+            // Not fatal: start_ollama_server runs it under the Workbench instead
+            log(app, format!("⚠ Could not register the Ollama service ({}); the Workbench will start it.", e));
+            None
+        }
+    };
+
+    log(app, format!("✅ Ollama {} ready at '{}'", OLLAMA_VERSION, binary.display()));
+    progress(app, 100, "done", "Ollama installed.");
+    Ok(OllamaRuntime {
+        version: OLLAMA_VERSION.to_string(),
+        root: target,
+        binary,
+        service,
+    })
+}
+
+fn download_release(app: &AppHandle, root: &Path, target: &Path, artifact: &str) -> Result<(), String> {
+    let mirror = ollama_mirror();
+    let release_url = format!("{}/v{}", mirror.trim_end_matches('/'), OLLAMA_VERSION);
+
+    log(
+        app,
+        format!("⬇ Downloading Ollama {} ({}) from {}", OLLAMA_VERSION, artifact, redact_url(&mirror)),
+    );
+    fetch_release(&release_url, root, target, artifact, |pct, message| {
+        progress(app, pct, "running", message)
+    })?;
+    log(app, "🔐 Checksum verified.".into());
+    Ok(())
+}
+
+/// Download `artifact` from `release_url`, check it against the release's
+/// `sha256sum.txt` and unpack it as `target`. `on_progress` gets 0–90 %.
+fn fetch_release<F>(release_url: &str, root: &Path, target: &Path, artifact: &str, mut on_progress: F) -> Result<(), String>
+where
+    F: FnMut(u8, &str),
+{
+    let archive_path = root.join("downloads").join(artifact);
+
+    on_progress(0, "Fetching Ollama checksums...");
+    let shasums = download::fetch_text(&format!("{}/sha256sum.txt", release_url))?;
+    let expected = download::find_checksum(&shasums, artifact)
+        .ok_or_else(|| format!("No checksum published for {}", artifact))?;
+
+    let mut last_pct = 0u8;
+    download::download_file(&format!("{}/{}", release_url, artifact), &archive_path, |done, total| {
+        if let Some(total) = total.filter(|t| *t > 0) {
+            let pct = ((done as f64 / total as f64) * 80.0) as u8;
+            if pct != last_pct {
+                last_pct = pct;
+                on_progress(pct, &format!("Downloading Ollama... {}%", pct));
+            }
+        }
+    })?;
+
+    on_progress(85, "Verifying checksum...");
+    download::verify_sha256(&archive_path, &expected)?;
+
+    on_progress(90, "Unpacking Ollama...");
+    let staging = root.join(format!(".staging-{}", OLLAMA_VERSION));
+    let _ = fs::remove_dir_all(&staging);
+    download::extract_archive(&archive_path, &staging)?;
+
+    let _ = fs::remove_dir_all(target);
+    fs::rename(&staging, target).map_err(|e| format!("Failed to move Ollama into place: {}", e))?;
+    let _ = fs::remove_file(&archive_path);
+    Ok(())
+}
+
+/// Version reported by `binary --version`, if it runs.
+fn probe(binary: &Path) -> Option<String> {
+    if !binary.is_file() {
+        return None;
+    }
+    make_executable(binary);
+    let output = Command::new(binary).arg("--version").output().ok()?;
+    let text = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    version::parse_output(Tool::Ollama, &text).map(|v| v.to_string())
+}
+
+/// Zip archives do not always carry the executable bit.
+#[cfg(unix)]
+fn make_executable(binary: &Path) {
+    use std::os::unix::fs::PermissionsExt;
+    if let Ok(meta) = fs::metadata(binary) {
+        let mut perms = meta.permissions();
+        if perms.mode() & 0o111 == 0 {
+            perms.set_mode(perms.mode() | 0o755);
+            let _ = fs::set_permissions(binary, perms);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn make_executable(_binary: &Path) {}
+
+/// Keep only the pinned version.
+fn remove_old_versions(root: &Path) {
+    let Ok(entries) = fs::read_dir(root) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name != OLLAMA_VERSION && name != "downloads" && entry.path().is_dir() {
+            let _ = fs::remove_dir_all(entry.path());
+        }
+    }
+}
+
+// === User service ===
+
+fn systemd_unit_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("systemd").join("user").join(format!("{}.service", SERVICE_NAME)))
+}
+
+fn launch_agent_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join("Library").join("LaunchAgents").join(format!("{}.plist", LAUNCH_AGENT)))
+}
+
+/// Run `ollama serve` as a per-user service on the reserved Ollama port.
+/// `Ok(None)` where the platform has no user service manager the Workbench
+/// uses.
+fn register_service(binary: &Path) -> Result<Option<String>, String> {
+    if !matches!(env::consts::OS, "linux" | "macos") {
+        return Ok(None);
+    }
+    // The service starts right away and must be able to bind the port
+    let host = format!("127.0.0.1:{}", registry::release_for_launch(registry::OLLAMA)?);
+    match env::consts::OS {
+        "linux" => register_systemd(binary, &host).map(|_| Some("systemd-user".into())),
+        _ => register_launch_agent(binary, &host).map(|_| Some("launch-agent".into())),
+    }
+}
+
+fn register_systemd(binary: &Path, host: &str) -> Result<(), String> {
+    // No user manager (containers, WSL without systemd, ...)
+    systemctl(&["--user", "show-environment"])?;
+
+    let unit_path = systemd_unit_path().ok_or("No user config directory")?;
+    let unit = format!(
+        "[Unit]\n\
+         Description=Ollama (Gignaati Workbench)\n\
+         After=network-online.target\n\
+         \n\
+         [Service]\n\
+         ExecStart=\"{}\" serve\n\
+         Environment=\"OLLAMA_HOST={}\"\n\
+         Restart=on-failure\n\
+         RestartSec=3\n\
+         \n\
+         [Install]\n\
+         WantedBy=default.target\n",
+        binary.display(),
+        host
+    );
+    write_file(&unit_path, &unit)?;
+
+    systemctl(&["--user", "daemon-reload"])?;
+    systemctl(&["--user", "enable", "--now", &format!("{}.service", SERVICE_NAME)])
+}
+
+fn register_launch_agent(binary: &Path, host: &str) -> Result<(), String> {
+    let plist_path = launch_agent_path().ok_or("No home directory")?;
+    let plist = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{}</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>{}</string>
+        <string>serve</string>
+    </array>
+    <key>EnvironmentVariables</key>
+    <dict>
+        <key>OLLAMA_HOST</key>
+        <string>{}</string>
+    </dict>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>KeepAlive</key>
+    <true/>
+</dict>
+</plist>
+"#,
+        LAUNCH_AGENT,
+        binary.display(),
+        host
+    );
+    // Reload so a changed binary path takes effect
+    let _ = launchctl(&["unload", &plist_path.to_string_lossy()]);
+    write_file(&plist_path, &plist)?;
+    launchctl(&["load", "-w", &plist_path.to_string_lossy()])
+}
+
+/// Stop and remove the user service, if one was registered.
+pub(crate) fn unregister_service() {
+    if let Some(unit) = systemd_unit_path().filter(|p| p.exists()) {
+        let _ = systemctl(&["--user", "disable", "--now", &format!("{}.service", SERVICE_NAME)]);
+        let _ = fs::remove_file(unit);
+        let _ = systemctl(&["--user", "daemon-reload"]);
+    }
+    if let Some(plist) = launch_agent_path().filter(|p| p.exists()) {
+        let _ = launchctl(&["unload", "-w", &plist.to_string_lossy()]);
+        let _ = fs::remove_file(plist);
+    }
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn systemctl(args: &[&str]) -> Result<(), String> {
+    run("systemctl", args)
+}
+
+fn launchctl(args: &[&str]) -> Result<(), String> {
+    run("launchctl", args)
+}
+
+fn run(program: &str, args: &[&str]) -> Result<(), String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| format!("{} not available: {}", program, e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} {} failed: {}",
+            program,
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+fn log(app: &AppHandle, message: String) {
+    app.emit(
+        "component-log",
+        ComponentLog {
+            component: COMPONENT.into(),
+            message,
+        },
+    )
+    .ok();
+}
+
+fn progress(app: &AppHandle, percent: u8, status: &str, message: &str) {
+    app.emit(
+        "component-progress",
+        ComponentProgress {
+            component: COMPONENT.into(),
+            percent,
+            status: status.into(),
+            message: message.into(),
+            eta_seconds: None,
+        },
+    )
+    .ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{serve, Reply};
+    use sha2::{Digest, Sha256};
+
+    const SCRIPT: &[u8] = b"#!/bin/sh\necho 'ollama version is 0.5.7'\n";
+
+    /// A Linux release archive holding `bin/ollama`.
+    fn release_tgz() -> Vec<u8> {
+        let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        let mut builder = tar::Builder::new(encoder);
+        let mut header = tar::Header::new_gnu();
+        header.set_size(SCRIPT.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        builder.append_data(&mut header, "bin/ollama", SCRIPT).unwrap();
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    /// Serve `archive` as the linux-amd64 release of the pinned version.
+    fn mirror(archive: Vec<u8>, shasums: String) -> String {
+        let base = serve(vec![
+            ("/v0.5.7/ollama-linux-amd64.tgz", Reply::ok(archive)),
+            ("/v0.5.7/sha256sum.txt", Reply::ok(shasums)),
+        ]);
+        format!("{}/v{}", base, OLLAMA_VERSION)
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn provisions_the_release_from_a_local_mirror() {
+        let archive = release_tgz();
+        let shasums = format!(
+            "{}  ./ollama-darwin.zip\n{}  ./ollama-linux-amd64.tgz\n",
+            "0".repeat(64),
+            hex::encode(Sha256::digest(&archive))
+        );
+        let release_url = mirror(archive, shasums);
+        let tmp = tempfile::tempdir().unwrap();
+        let target = tmp.path().join(OLLAMA_VERSION);
+
+        let (artifact, binary) = artifact_for("linux", "x86_64").unwrap();
+        let mut last = 0;
+        fetch_release(&release_url, tmp.path(), &target, artifact, |pct, _| last = pct).unwrap();
+
+        assert_eq!(last, 90);
+        assert_eq!(probe(&target.join(binary)).as_deref(), Some(OLLAMA_VERSION));
+        assert!(!tmp.path().join("downloads").join(artifact).exists());
+    }
+
+    #[test]
+    fn a_checksum_mismatch_installs_nothing() {
+        let shasums = format!("{}  ollama-linux-amd64.tgz\n", "0".repeat(64));
+        let release_url = mirror(release_tgz(), shasums);
+        let tmp = tempfile::tempdir().unwrap();
+        let target = tmp.path().join(OLLAMA_VERSION);
+
+        let err = fetch_release(&release_url, tmp.path(), &target, "ollama-linux-amd64.tgz", |_, _| {}).unwrap_err();
+        assert!(err.contains("Checksum mismatch"), "{}", err);
+        assert!(!target.exists());
+    }
+
+    #[test]
+    fn an_unlisted_archive_is_refused() {
+        let shasums = format!("{}  ollama-linux-arm64.tgz\n", "0".repeat(64));
+        let release_url = mirror(release_tgz(), shasums);
+        let tmp = tempfile::tempdir().unwrap();
+
+        let err = fetch_release(
+            &release_url,
+            tmp.path(),
+            &tmp.path().join(OLLAMA_VERSION),
+            "ollama-linux-amd64.tgz",
+            |_, _| {},
+        )
+        .unwrap_err();
+        assert_eq!(err, "No checksum published for ollama-linux-amd64.tgz");
+    }
+
+    #[test]
+    fn every_platform_knows_where_the_binary_is() {
+        assert_eq!(
+            artifact_for("macos", "aarch64"),
+            Ok(("Ollama-darwin.zip", "Ollama.app/Contents/Resources/ollama"))
+        );
+        assert_eq!(artifact_for("windows", "x86_64"), Ok(("ollama-windows-amd64.zip", "ollama.exe")));
+        assert!(artifact_for("freebsd", "x86_64").is_err());
+    }
+}
@@ -1,11 +1,12 @@
 // 🧩 Gignaati Workbench Installer
-// 🔧 Installation Runner — Handles sequential setup steps
+// 🔧 Installation Runner — wizard front-end over the installation engine
 
-use std::process::{Command, Stdio};
-use std::io::{BufRead, BufReader};
 use tauri::{AppHandle, Emitter};
 use serde::Serialize;
 
+use crate::installer::components::run_standard;
+use crate::installer::engine::EngineMode;
+
 #[derive(Serialize, Clone)]
 pub struct InstallProgress {
     step: String,
@@ -14,53 +15,32 @@ pub struct InstallProgress {
 }
 
 #[tauri::command]
-pub fn run_installation(app_handle: AppHandle) -> Result<(), String> {
-    let steps = vec![
-        ("System Setup", "echo Preparing system..."),
-        ("Node Setup", "node -v"),
-        ("Git Setup", "git --version"),
-        ("Python Setup", "python --version"),
-    ];
-
-    for (i, (step_name, cmd)) in steps.iter().enumerate() {
-        let progress = ((i + 1) as f32 / steps.len() as f32 * 100.0) as u8;
+pub async fn run_installation(app_handle: AppHandle) -> Result<(), String> {
+    // Downloads and npm block; keep them off the async runtime
+    tauri::async_runtime::spawn_blocking(move || install(&app_handle))
+        .await
+        .map_err(|e| format!("Installation failed: {}", e))?
+}
 
+fn install(app_handle: &AppHandle) -> Result<(), String> {
+    let report = run_standard(app_handle, EngineMode::Install, |step| {
         app_handle
             .emit(
                 "install-progress",
                 InstallProgress {
-                    step: step_name.to_string(),
-                    message: format!("Running `{}`...", cmd),
-                    progress,
+                    step: step.component.clone(),
+                    message: step.message.clone(),
+                    progress: step.overall_percent,
                 },
             )
-            .map_err(|e| e.to_string())?;
-
-        let mut output = if cfg!(target_os = "windows") {
-            Command::new("cmd")
-                .args(["/C", cmd])
-                .stdout(Stdio::piped())
-                .spawn()
-                .map_err(|e| e.to_string())?
-        } else {
-            Command::new("sh")
-                .arg("-c")
-                .arg(cmd)
-                .stdout(Stdio::piped())
-                .spawn()
-                .map_err(|e| e.to_string())?
-        };
-
-        let reader = BufReader::new(output.stdout.take().ok_or("Failed to read output")?);
-
-        for line in reader.lines() {
-            let msg = line.unwrap_or_default();
-            app_handle
-                .emit("install-log", format!("[{}] {}", step_name, msg))
-                .map_err(|e| e.to_string())?;
-        }
+            .ok();
+        app_handle
+            .emit("install-log", format!("[{}] {}", step.component, step.message))
+            .ok();
+    })?;
 
-        std::thread::sleep(std::time::Duration::from_millis(500));
+    if !report.success {
+        return Err(report.summary());
     }
 
     app_handle
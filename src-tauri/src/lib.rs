@@ -11,22 +11,38 @@ mod system;
 mod config;            // ✅ Global configuration manager
 mod ports;             // ✅ Port allocation and detection logic
 mod installer;         // ✅ Installation orchestration (Node, n8n, Ollama)
+mod discovery;         // ✅ Node / npm / n8n / Ollama binary discovery
+mod version;           // ✅ Version parsing and compatibility matrix
 mod ollama_server;     // ✅ Ollama runtime manager (serve, stop, models)
+mod ollama_client;     // ✅ Typed Ollama REST API client
+mod ollama_models;     // ✅ Structured local model inventory
+mod model_recommender; // ✅ Hardware-aware model ranking
+mod model_downloads;   // ✅ Queued, resumable model pulls
 mod n8n_manager;       // ✅ Agentic Platform controller (n8n + Ollama bridge)
+mod n8n_database;      // ✅ PostgreSQL backend checks and SQLite → Postgres migration
+mod n8n_backup;        // ✅ Workflow / credential backups with retention and restore
+mod supervisor;        // ✅ Supervised n8n / Ollama processes with auto-restart
+mod readiness;         // ✅ Health-endpoint polling after launches
+mod preflight;         // ✅ Parallel preflight checks with remediation hints
+mod diagnostics;       // ✅ Support bundle (config, preflight, logs, ports, models)
+mod logging;           // ✅ Rotating per-component log files
+#[cfg(test)]
+mod test_support;      // 🧪 Local HTTP mirror for tests
 
 // === Imports ===
-use tauri::AppHandle;
+use tauri::{AppHandle, Manager};
 use system::detector::validate_requirements;
-use crate::ports::manager::{allocate_ports, PortConfig};
+use crate::ports::manager::{allocate_ports, reserve_service_port, PortConfig};
 use ollama_server::*;
 use installer::{
     check_nodejs_installed,
+    install_node_runtime, // ✅ pinned, checksum-verified private Node.js
     check_n8n_installed,
     check_ollama_installed,
     validate_environment,
-    install_n8n,          // legacy simulated install (kept for fallback)
+    install_n8n,          // legacy entry point, same private-prefix install
     install_n8n_real,     // ✅ real npm-based installer
-    install_ollama,       // simulated fallback
+    install_ollama,       // legacy entry point, same verified release install
     install_ollama_real,  // ✅ guided safe installer
     run_installation,
     smart_installer,
@@ -53,7 +69,21 @@ fn allocate_ports_command(app: AppHandle) -> Result<PortConfig, String> {
 /// ✅ Load and return global configuration for the Workbench.
 #[tauri::command]
 fn get_config_command() -> Result<crate::config::AppConfig, String> {
-    Ok(crate::config::AppConfig::load())
+    Ok(crate::config::AppConfig::load()?)
+}
+
+/// ✅ Report where each effective config value came from
+/// (default, system file, user file, environment or CLI) and whether it is locked.
+#[tauri::command]
+fn get_config_provenance_command() -> Result<Vec<crate::config::layers::FieldSource>, String> {
+    Ok(crate::config::provenance()?)
+}
+
+/// ✅ Report values in the configuration that will not work
+/// (out-of-range ports, unknown default model, ...). Empty when valid.
+#[tauri::command]
+fn validate_config_command() -> Result<Vec<crate::config::ConfigIssue>, String> {
+    Ok(crate::config::AppConfig::load()?.validate())
 }
 
 // === Main Entry Point ===
@@ -61,17 +91,41 @@ fn get_config_command() -> Result<crate::config::AppConfig, String> {
 pub fn run() {
     let builder = tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .setup(|app| {
+            // Mirror every component-log event to disk
+            logging::init(app.handle());
+
+            // Import legacy .gwconfig ports and hold every service port for this session
+            if let Ok(dir) = app.handle().path().app_data_dir() {
+                ports::registry::migrate_legacy(&dir);
+            }
+            if let Err(e) = ports::registry::reserve_all() {
+                eprintln!("⚠ Port reservation failed: {}", e);
+            }
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             // --- Utility / System ---
             greet,
             validate_requirements,
+            preflight::run_preflight,           // 🛫 All preflight checks, in parallel
+            preflight::export_preflight_report, // 💾 Last report as JSON
+            diagnostics::collect_diagnostics,   // 🩺 Support bundle zip
+            logging::read_logs,                 // 📜 Tail / search persisted logs
+            logging::list_log_components,       // 📂 Components with log files
 
             // --- Config & Port Layer ---
             allocate_ports_command,   // returns PortConfig
+            reserve_service_port,     // extra named service ports
             get_config_command,       // returns AppConfig
+            validate_config_command,  // returns Vec<ConfigIssue>
+            get_config_provenance_command, // returns Vec<FieldSource>
+            ports::ownership::inspect_port,   // 🔎 Who holds a port
+            ports::ownership::release_port,   // 🛑 Stop it (user-confirmed only)
 
             // --- Core Installers (Node.js + n8n + Ollama) ---
             check_nodejs_installed,
+            install_node_runtime,
             check_n8n_installed,
             check_ollama_installed,
             validate_environment,
@@ -79,6 +133,16 @@ pub fn run() {
             install_n8n_real,
             install_ollama,
             install_ollama_real,
+            installer::n8n_versions::list_n8n_versions,   // 📋 Registry versions
+            installer::n8n_versions::install_n8n_version, // ⬇ Install + activate a version
+            installer::n8n_versions::rollback_n8n,        // ⏪ Back to the previous version
+            installer::n8n_versions::pin_n8n_version,     // 📌 Pin / unpin
+            installer::n8n_versions::get_n8n_versions,    // 📋 Active / previous / pinned
+            installer::install_n8n_real::find_existing_n8n, // 🔎 n8n installed outside the Workbench
+            installer::install_n8n_real::use_existing_n8n,  // 📌 Opt into one of those
+            discovery::discover_binaries,                   // 🔎 Every node / npm / n8n / ollama found
+            discovery::set_preferred_binary,                // 📌 Pin one of them per tool
+            version::check_compatibility,                   // 🧮 Installed versions vs. the compatibility matrix
 
             // --- Execution Flow ---
             run_installation,
@@ -95,12 +159,25 @@ pub fn run() {
             repair_ollama_model,
             remove_ollama_model,
             cancel_ollama_download,
+            model_downloads::pause_ollama_download,
+            model_downloads::resume_ollama_download,
+            model_downloads::list_ollama_downloads,
+            model_recommender::recommend_models, // 🧠 Catalog models ranked for this machine
 
             // --- Agentic Platform / n8n Integration ---
             n8n_manager::launch_n8n_with_ollama,   // 🚀 Launch n8n bound to Ollama port
             n8n_manager::stop_n8n,                 // 🛑 Stop n8n process
             n8n_manager::check_n8n_health,         // 🔎 Check n8n health
             n8n_manager::launch_agentic_platform,  // 🌐 Open Agentic Platform UI in main webview
+            n8n_manager::preview_n8n_env,          // 👁 Env n8n will receive (secrets redacted)
+            n8n_database::check_n8n_database,      // 🔍 PostgreSQL connectivity for n8n
+            n8n_database::migrate_n8n_to_postgres, // 🗄 Move workflows + credentials off SQLite
+            n8n_backup::backup_n8n,                // 💾 Timestamped backup archive
+            n8n_backup::list_n8n_backups,          // 📋 Backups, newest first
+            n8n_backup::restore_n8n_backup,        // ⏪ Restore by ID or point in time
+
+            // --- Service Supervision ---
+            supervisor::get_service_status,       // 📋 State of n8n / Ollama processes
 
             // --- Internal Launch (n8n UI) ---
             launch_n8n_internally,
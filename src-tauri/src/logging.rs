@@ -0,0 +1,449 @@
+// 🧩 Gignaati Workbench — Persistent logs
+//
+// Every `component-log` event is mirrored to `<app log dir>/<component>.log`
+// as `<YYYYMMDD-HHMMSSZ> <LEVEL> <message>`. The level is the event's `level`
+// field; events without one are classified by the message's emoji prefix
+// (❌ error, ⚠ warn). A file is rotated to
+// `<component>.<stamp>.log` once it passes `MAX_FILE_BYTES` or on the first
+// write of a new UTC day; rotated files are pruned by count and age.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Listener, Manager};
+
+use crate::n8n_backup::utc_stamp;
+
+const MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
+/// Rotated files kept per component.
+const KEEP_ROTATED: usize = 10;
+/// Rotated files older than this are deleted regardless of count.
+const RETENTION: Duration = Duration::from_secs(14 * 86_400);
+const DEFAULT_LIMIT: usize = 200;
+
+static LOG_DIR: Lazy<Mutex<Option<PathBuf>>> = Lazy::new(|| Mutex::new(None));
+static WRITERS: Lazy<Mutex<HashMap<String, Writer>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Deserialize)]
+struct ComponentLog {
+    component: String,
+    message: String,
+    #[serde(default)]
+    level: Option<LogLevel>,
+}
+
+impl ComponentLog {
+    fn level(&self) -> LogLevel {
+        self.level.unwrap_or_else(|| LogLevel::of(&self.message))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    fn of(message: &str) -> Self {
+        let message = message.trim_start();
+        if message.starts_with('❌') || message.starts_with('⛔') {
+            LogLevel::Error
+        } else if message.starts_with('⚠') {
+            LogLevel::Warn
+        } else {
+            LogLevel::Info
+        }
+    }
+
+    fn tag(self) -> &'static str {
+        match self {
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
+
+    fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "INFO" => Some(LogLevel::Info),
+            "WARN" => Some(LogLevel::Warn),
+            "ERROR" => Some(LogLevel::Error),
+            _ => None,
+        }
+    }
+}
+
+/// Open log file for one component.
+struct Writer {
+    file: File,
+    size: u64,
+    /// `YYYYMMDD` of the file's first line.
+    day: String,
+}
+
+/// Filters for `read_logs`. Times are Unix seconds (UTC).
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct LogQuery {
+    /// Component name as shown in the UI (`Smart Installer`) or its file
+    /// name (`smart-installer`).
+    pub component: Option<String>,
+    pub since: Option<u64>,
+    pub until: Option<u64>,
+    /// Case-insensitive substring of the message.
+    pub contains: Option<String>,
+    pub min_level: Option<LogLevel>,
+    /// Newest matching lines to return (default 200).
+    pub limit: Option<usize>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct LogEntry {
+    pub component: String,
+    /// `YYYYMMDD-HHMMSSZ`.
+    pub time: String,
+    pub level: LogLevel,
+    pub message: String,
+}
+
+/// Resolve the log directory and start mirroring `component-log` events.
+pub fn init(app: &AppHandle) {
+    let dir = match app.path().app_log_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            eprintln!("⚠ File logging disabled: {}", e);
+            return;
+        }
+    };
+    if let Err(e) = fs::create_dir_all(&dir) {
+        eprintln!("⚠ File logging disabled: cannot create '{}': {}", dir.display(), e);
+        return;
+    }
+    for component in components_in(&dir) {
+        prune(&dir, &component);
+    }
+    *LOG_DIR.lock().unwrap() = Some(dir);
+
+    app.listen("component-log", |event| {
+        if let Ok(entry) = serde_json::from_str::<ComponentLog>(event.payload()) {
+            record(&entry.component, entry.level(), &entry.message);
+        }
+    });
+}
+
+/// Append `message` to `component`'s log file. Multi-line messages get one
+/// header per line so every line stays searchable on its own.
+pub fn record(component: &str, level: LogLevel, message: &str) {
+    let Some(dir) = log_dir() else {
+        return;
+    };
+    let slug = slug(component);
+    let stamp = utc_stamp(now());
+
+    let mut writers = WRITERS.lock().unwrap();
+    if writers
+        .get(&slug)
+        .is_some_and(|w| w.size >= MAX_FILE_BYTES || w.day != stamp[..8])
+    {
+        writers.remove(&slug);
+        rotate(&dir, &slug, &stamp);
+    }
+    let writer = match writers.get_mut(&slug) {
+        Some(writer) => writer,
+        None => match open(&dir, &slug, &stamp) {
+            Ok(writer) => writers.entry(slug.clone()).or_insert(writer),
+            Err(e) => {
+                eprintln!("⚠ Cannot write log for '{}': {}", component, e);
+                return;
+            }
+        },
+    };
+
+    let mut text = String::new();
+    for line in message.lines() {
+        text.push_str(&format!("{} {:<5} {}\n", stamp, level.tag(), line));
+    }
+    if writer.file.write_all(text.as_bytes()).is_ok() {
+        writer.size += text.len() as u64;
+    }
+}
+
+/// Current log file of every component, for support bundles.
+pub(crate) fn current_files() -> Vec<(String, PathBuf)> {
+    let Some(dir) = log_dir() else {
+        return Vec::new();
+    };
+    components_in(&dir)
+        .into_iter()
+        .map(|c| (c.clone(), dir.join(format!("{}.log", c))))
+        .filter(|(_, path)| path.is_file())
+        .collect()
+}
+
+/// Matching lines, oldest first, at most `limit` of the newest.
+pub fn read(query: &LogQuery) -> Result<Vec<LogEntry>, String> {
+    let dir = log_dir().ok_or("File logging is not available")?;
+    let wanted = query.component.as_deref().map(slug);
+    let since = query.since.map(utc_stamp);
+    let until = query.until.map(utc_stamp);
+    let needle = query.contains.as_deref().map(str::to_lowercase);
+
+    let mut entries = Vec::new();
+    for path in log_files(&dir) {
+        let Some((component, rotated_at)) = parse_file_name(&path) else {
+            continue;
+        };
+        if wanted.as_ref().is_some_and(|w| *w != component) {
+            continue;
+        }
+        // A rotated file holds nothing newer than its rotation stamp
+        if let (Some(rotated_at), Some(since)) = (&rotated_at, &since) {
+            if rotated_at < since {
+                continue;
+            }
+        }
+
+        let Ok(file) = File::open(&path) else {
+            continue;
+        };
+        for line in BufReader::new(file).lines().map_while(Result::ok) {
+            let Some(entry) = parse_line(&component, &line) else {
+                continue;
+            };
+            let keep = since.as_ref().is_none_or(|s| entry.time >= *s)
+                && until.as_ref().is_none_or(|u| entry.time <= *u)
+                && query.min_level.is_none_or(|l| entry.level >= l)
+                && needle
+                    .as_ref()
+                    .is_none_or(|n| entry.message.to_lowercase().contains(n));
+            if keep {
+                entries.push(entry);
+            }
+        }
+    }
+
+    // Stable: lines from the same second keep their file order
+    entries.sort_by(|a, b| a.time.cmp(&b.time));
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+    let skip = entries.len().saturating_sub(limit);
+    Ok(entries.split_off(skip))
+}
+
+// === Files ===
+
+fn log_dir() -> Option<PathBuf> {
+    LOG_DIR.lock().unwrap().clone()
+}
+
+fn open(dir: &Path, slug: &str, stamp: &str) -> Result<Writer, String> {
+    let path = dir.join(format!("{}.log", slug));
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| e.to_string())?;
+    let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let day = first_line_day(&path).unwrap_or_else(|| stamp[..8].to_string());
+    Ok(Writer { file, size, day })
+}
+
+fn first_line_day(path: &Path) -> Option<String> {
+    let file = File::open(path).ok()?;
+    let mut line = String::new();
+    BufReader::new(file).read_line(&mut line).ok()?;
+    line.get(..8)
+        .filter(|day| day.bytes().all(|b| b.is_ascii_digit()))
+        .map(str::to_string)
+}
+
+/// `<slug>.log` → `<slug>.<stamp>.log`, then prune.
+fn rotate(dir: &Path, slug: &str, stamp: &str) {
+    let current = dir.join(format!("{}.log", slug));
+    let mut target = dir.join(format!("{}.{}.log", slug, stamp));
+    let mut n = 1;
+    while target.exists() {
+        n += 1;
+        target = dir.join(format!("{}.{}-{}.log", slug, stamp, n));
+    }
+    if let Err(e) = fs::rename(&current, &target) {
+        eprintln!("⚠ Failed to rotate '{}': {}", current.display(), e);
+    }
+    prune(dir, slug);
+}
+
+/// Keep the newest `KEEP_ROTATED` rotated files younger than `RETENTION`.
+fn prune(dir: &Path, slug: &str) {
+    let mut rotated: Vec<PathBuf> = log_files(dir)
+        .into_iter()
+        .filter(|p| matches!(parse_file_name(p), Some((c, Some(_))) if c == slug))
+        .collect();
+    // Stamps sort chronologically; newest first
+    rotated.reverse();
+
+    for (i, path) in rotated.iter().enumerate() {
+        let expired = fs::metadata(path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.elapsed().ok())
+            .is_some_and(|age| age > RETENTION);
+        if i >= KEEP_ROTATED || expired {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// Every `.log` file in `dir`, sorted by name (rotated files before the
+/// current one for each component).
+fn log_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|ext| ext == "log"))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+fn components_in(dir: &Path) -> BTreeSet<String> {
+    log_files(dir)
+        .iter()
+        .filter_map(|p| parse_file_name(p))
+        .map(|(component, _)| component)
+        .collect()
+}
+
+/// `smart-installer.log` → (`smart-installer`, None);
+/// `smart-installer.20250101-120000Z.log` → (…, Some(stamp)).
+fn parse_file_name(path: &Path) -> Option<(String, Option<String>)> {
+    let stem = path.file_stem()?.to_str()?;
+    Some(match stem.split_once('.') {
+        Some((component, stamp)) => (component.to_string(), Some(stamp.to_string())),
+        None => (stem.to_string(), None),
+    })
+}
+
+fn parse_line(component: &str, line: &str) -> Option<LogEntry> {
+    let (time, rest) = line.split_once(' ')?;
+    if time.len() != 16 || !time.ends_with('Z') {
+        return None;
+    }
+    let (tag, message) = rest.split_once(' ')?;
+    Some(LogEntry {
+        component: component.to_string(),
+        time: time.to_string(),
+        level: LogLevel::from_tag(tag)?,
+        message: message.trim_start().to_string(),
+    })
+}
+
+/// `n8n Backups` → `n8n-backups`.
+fn slug(component: &str) -> String {
+    let mut slug = String::new();
+    for c in component.trim().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('-') && !slug.is_empty() {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-').to_string();
+    if slug.is_empty() {
+        "general".into()
+    } else {
+        slug
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// === Commands ===
+
+/// 📜 Tail or search the persisted component logs
+#[tauri::command]
+pub async fn read_logs(query: Option<LogQuery>) -> Result<Vec<LogEntry>, String> {
+    tauri::async_runtime::spawn_blocking(move || read(&query.unwrap_or_default()))
+        .await
+        .map_err(|e| format!("Reading logs failed: {}", e))?
+}
+
+/// 📂 Components that have log files
+#[tauri::command]
+pub fn list_log_components() -> Vec<String> {
+    log_dir()
+        .map(|dir| components_in(&dir).into_iter().collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slug_flattens_component_names() {
+        assert_eq!(slug("Smart Installer"), "smart-installer");
+        assert_eq!(slug("Agentic Platform (n8n)"), "agentic-platform-n8n");
+        assert_eq!(slug("  Node.js -- Runtime "), "node-js-runtime");
+        assert_eq!(slug("🧹"), "general");
+    }
+
+    #[test]
+    fn written_lines_parse_back() {
+        for (level, message) in [(LogLevel::Info, "✅ ready"), (LogLevel::Warn, "⚠ slow disk")] {
+            let line = format!("{} {:<5} {}", "20250101-120000Z", level.tag(), message);
+            let entry = parse_line("ollama-server", &line).unwrap();
+            assert_eq!(entry.time, "20250101-120000Z");
+            assert_eq!(entry.level, level);
+            assert_eq!(entry.message, message);
+        }
+    }
+
+    #[test]
+    fn an_explicit_level_wins_over_the_emoji() {
+        let event = |json: &str| serde_json::from_str::<ComponentLog>(json).unwrap().level();
+        assert_eq!(event(r#"{"component":"Ollama","message":"❌ failed"}"#), LogLevel::Error);
+        assert_eq!(event(r#"{"component":"Ollama","message":"listening"}"#), LogLevel::Info);
+        assert_eq!(
+            event(r#"{"component":"Ollama","message":"💥 exited","level":"warn"}"#),
+            LogLevel::Warn
+        );
+        assert_eq!(
+            event(r#"{"component":"Ollama","message":"⚠ not a warning","level":"info"}"#),
+            LogLevel::Info
+        );
+    }
+
+    #[test]
+    fn foreign_lines_are_skipped() {
+        assert!(parse_line("x", "plain text without a header").is_none());
+        assert!(parse_line("x", "20250101-120000Z DEBUG hello").is_none());
+        assert!(parse_line("x", "2025-01-01 INFO hello").is_none());
+    }
+
+    #[test]
+    fn rotated_file_names_carry_their_stamp() {
+        assert_eq!(
+            parse_file_name(Path::new("/logs/smart-installer.log")),
+            Some(("smart-installer".into(), None))
+        );
+        assert_eq!(
+            parse_file_name(Path::new("/logs/smart-installer.20250101-120000Z.log")),
+            Some(("smart-installer".into(), Some("20250101-120000Z".into())))
+        );
+    }
+}
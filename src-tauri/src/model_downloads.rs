@@ -0,0 +1,630 @@
+// 🧩 Gignaati Workbench — Model download manager
+//
+// Queues `ollama pull` requests and runs up to `model_download_concurrency`
+// of them at a time over the REST API. Each model can be cancelled, paused
+// and resumed independently (Ollama keeps partial blobs, so a resumed pull
+// continues where it stopped), and failed pulls are retried with
+// exponential backoff. Progress is reported as structured
+// `model-pull-progress` events.
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+use crate::config::AppConfig;
+use crate::discovery::Tool;
+use crate::ollama_client::{OllamaClient, OllamaError, PullOutcome, PullProgress};
+use crate::version::{self, Verdict};
+
+const DEFAULT_CONCURRENCY: usize = 2;
+const DEFAULT_RETRIES: u32 = 3;
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+const EMIT_INTERVAL: Duration = Duration::from_millis(250);
+const CONTROL_POLL: Duration = Duration::from_millis(200);
+const COMPONENT: &str = "Ollama Model Pull";
+
+// Per-job control signal, polled while a pull runs or a retry waits
+const CONTROL_RUN: u8 = 0;
+const CONTROL_PAUSE: u8 = 1;
+const CONTROL_CANCEL: u8 = 2;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadState {
+    Queued,
+    Downloading,
+    Retrying,
+    Paused,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl DownloadState {
+    fn is_active(self) -> bool {
+        matches!(
+            self,
+            DownloadState::Queued | DownloadState::Downloading | DownloadState::Retrying
+        )
+    }
+}
+
+/// Snapshot of one queued or finished download.
+#[derive(Serialize, Debug, Clone)]
+pub struct DownloadStatus {
+    pub model: String,
+    pub state: DownloadState,
+    pub attempt: u32,
+    pub completed: u64,
+    pub total: u64,
+    pub error: Option<String>,
+}
+
+/// Payload of the `model-pull-progress` event.
+#[derive(Serialize, Debug, Clone)]
+pub struct ModelPullProgress {
+    pub model: String,
+    pub state: DownloadState,
+    pub status: String,
+    pub digest: Option<String>,
+    pub completed: u64,
+    pub total: u64,
+    pub speed_bytes_per_sec: u64,
+    pub eta_seconds: Option<u64>,
+}
+
+#[derive(Serialize, Clone)]
+struct ComponentLog {
+    component: String,
+    message: String,
+}
+
+struct Job {
+    status: DownloadStatus,
+    control: Arc<AtomicU8>,
+}
+
+#[derive(Default)]
+struct Queue {
+    jobs: Vec<Job>,
+    running: usize,
+}
+
+impl Queue {
+    fn job_mut(&mut self, model: &str) -> Option<&mut Job> {
+        self.jobs.iter_mut().find(|j| j.status.model == model)
+    }
+}
+
+static DOWNLOADS: Lazy<Mutex<Queue>> = Lazy::new(|| Mutex::new(Queue::default()));
+
+enum Finish {
+    Completed,
+    Paused,
+    Cancelled,
+    Failed(String),
+}
+
+// === Public API ===
+
+/// Add a model to the queue (or re-queue a paused / finished one).
+pub fn enqueue(app: &AppHandle, model: &str) -> Result<(), String> {
+    let model = model.trim();
+    if model.is_empty() {
+        return Err("Model name must not be empty".into());
+    }
+    if let Some(ollama) = version::located(Tool::Ollama) {
+        if let Some(finding) = version::check_model(model, &ollama) {
+            if finding.verdict == Verdict::Blocking {
+                return Err(finding.message);
+            }
+        }
+    }
+
+    {
+        let mut queue = DOWNLOADS.lock().unwrap();
+        match queue.job_mut(model) {
+            Some(job) if job.status.state.is_active() => {
+                return Err(format!("'{}' is already queued or downloading", model));
+            }
+            Some(job) => {
+                job.status.state = DownloadState::Queued;
+                job.status.attempt = 0;
+                job.status.error = None;
+                job.control = Arc::new(AtomicU8::new(CONTROL_RUN));
+            }
+            None => queue.jobs.push(Job {
+                status: DownloadStatus {
+                    model: model.to_string(),
+                    state: DownloadState::Queued,
+                    attempt: 0,
+                    completed: 0,
+                    total: 0,
+                    error: None,
+                },
+                control: Arc::new(AtomicU8::new(CONTROL_RUN)),
+            }),
+        }
+    }
+
+    emit_state(app, model, DownloadState::Queued, "queued");
+    pump(app);
+    Ok(())
+}
+
+/// Cancel one model, or every queued / running download when `model` is `None`.
+/// Returns the models that were cancelled.
+pub fn cancel(app: &AppHandle, model: Option<&str>) -> Vec<String> {
+    signal(app, model, CONTROL_CANCEL, DownloadState::Cancelled)
+}
+
+/// Pause a queued or running download; partial layers stay on disk.
+pub fn pause(app: &AppHandle, model: &str) -> Result<(), String> {
+    if signal(app, Some(model), CONTROL_PAUSE, DownloadState::Paused).is_empty() {
+        return Err(format!("'{}' is not queued or downloading", model));
+    }
+    Ok(())
+}
+
+/// Resume a paused download.
+pub fn resume(app: &AppHandle, model: &str) -> Result<(), String> {
+    let paused = {
+        let mut queue = DOWNLOADS.lock().unwrap();
+        queue
+            .job_mut(model)
+            .is_some_and(|j| j.status.state == DownloadState::Paused)
+    };
+    if !paused {
+        return Err(format!("'{}' is not paused", model));
+    }
+    enqueue(app, model)
+}
+
+pub fn snapshot() -> Vec<DownloadStatus> {
+    DOWNLOADS
+        .lock()
+        .unwrap()
+        .jobs
+        .iter()
+        .map(|j| j.status.clone())
+        .collect()
+}
+
+// === Tauri commands ===
+
+/// ⏸ Pause a model download
+#[tauri::command]
+pub fn pause_ollama_download(app: AppHandle, model_name: String) -> Result<(), String> {
+    pause(&app, &model_name)
+}
+
+/// ▶ Resume a paused model download
+#[tauri::command]
+pub fn resume_ollama_download(app: AppHandle, model_name: String) -> Result<(), String> {
+    resume(&app, &model_name)
+}
+
+/// 📋 Current download queue
+#[tauri::command]
+pub fn list_ollama_downloads() -> Vec<DownloadStatus> {
+    snapshot()
+}
+
+// === Scheduling ===
+
+/// Start queued jobs until the concurrency limit is reached.
+fn pump(app: &AppHandle) {
+    let limit = concurrency();
+    loop {
+        let next = {
+            let mut queue = DOWNLOADS.lock().unwrap();
+            if queue.running >= limit {
+                return;
+            }
+            let Some(job) = queue
+                .jobs
+                .iter_mut()
+                .find(|j| j.status.state == DownloadState::Queued)
+            else {
+                return;
+            };
+            job.status.state = DownloadState::Downloading;
+            let next = (job.status.model.clone(), job.control.clone());
+            queue.running += 1;
+            next
+        };
+
+        let app = app.clone();
+        thread::spawn(move || run_job(app, next.0, next.1));
+    }
+}
+
+fn signal(app: &AppHandle, model: Option<&str>, control: u8, idle_state: DownloadState) -> Vec<String> {
+    let mut affected = Vec::new();
+    let mut idle = Vec::new();
+    {
+        let mut queue = DOWNLOADS.lock().unwrap();
+        for job in queue.jobs.iter_mut() {
+            if model.is_some_and(|m| m != job.status.model) {
+                continue;
+            }
+            match job.status.state {
+                // Not started yet: change state directly
+                DownloadState::Queued => {
+                    job.status.state = idle_state;
+                    idle.push(job.status.model.clone());
+                }
+                // Running: the worker notices the signal and winds down
+                DownloadState::Downloading | DownloadState::Retrying => {
+                    job.control.store(control, Ordering::SeqCst);
+                }
+                DownloadState::Paused if control == CONTROL_CANCEL => {
+                    job.status.state = DownloadState::Cancelled;
+                    idle.push(job.status.model.clone());
+                }
+                _ => continue,
+            }
+            affected.push(job.status.model.clone());
+        }
+    }
+
+    for m in idle {
+        emit_state(app, &m, idle_state, "stopped before start");
+    }
+    affected
+}
+
+fn run_job(app: AppHandle, model: String, control: Arc<AtomicU8>) {
+    log(&app, format!("⬇ Starting download for model '{}'...", model));
+
+    let retries = max_retries();
+    let mut attempt = 0u32;
+
+    let finish = loop {
+        attempt += 1;
+        update(&model, |s| {
+            s.attempt = attempt;
+            s.state = DownloadState::Downloading;
+        });
+
+        match watched_pull(&app, &model, &control) {
+            Ok(PullOutcome::Completed) => break Finish::Completed,
+            Ok(PullOutcome::Stopped) => break stopped(&control),
+            Err(e) if attempt <= retries => {
+                let delay = backoff(attempt);
+                update(&model, |s| {
+                    s.state = DownloadState::Retrying;
+                    s.error = Some(e.to_string());
+                });
+                emit_state(
+                    &app,
+                    &model,
+                    DownloadState::Retrying,
+                    &format!("{} — retrying in {}s", e, delay.as_secs()),
+                );
+                if !sleep_unless_signalled(delay, &control) {
+                    break stopped(&control);
+                }
+            }
+            Err(e) => break Finish::Failed(e.to_string()),
+        }
+    };
+
+    let (state, error) = match &finish {
+        Finish::Completed => (DownloadState::Completed, None),
+        Finish::Paused => (DownloadState::Paused, None),
+        Finish::Cancelled => (DownloadState::Cancelled, None),
+        Finish::Failed(e) => (DownloadState::Failed, Some(e.clone())),
+    };
+
+    {
+        let mut queue = DOWNLOADS.lock().unwrap();
+        if let Some(job) = queue.job_mut(&model) {
+            job.status.state = state;
+            job.status.error = error;
+        }
+        queue.running = queue.running.saturating_sub(1);
+    }
+
+    match finish {
+        Finish::Completed => {
+            emit_state(&app, &model, state, "success");
+            log(&app, format!("✅ Finished pulling '{}'", model));
+        }
+        Finish::Paused => {
+            emit_state(&app, &model, state, "paused");
+            log(&app, format!("⏸ Download of '{}' paused.", model));
+        }
+        Finish::Cancelled => {
+            emit_state(&app, &model, state, "cancelled");
+            log(&app, format!("⏹ Download of '{}' cancelled.", model));
+        }
+        Finish::Failed(e) => {
+            emit_state(&app, &model, state, &e);
+            log(
+                &app,
+                format!("❌ Model pull failed for '{}': {}. 💡 Try the Repair Model Pull option.", model, e),
+            );
+        }
+    }
+
+    pump(&app);
+}
+
+/// Run one pull on its own thread and wait for it, polling `control` so a
+/// pause or cancel takes effect even while the stream is stalled. An
+/// abandoned pull sees the signal at its next chunk (or hits the read
+/// timeout) and reports nothing further.
+fn watched_pull(app: &AppHandle, model: &str, control: &Arc<AtomicU8>) -> Result<PullOutcome, OllamaError> {
+    let (tx, rx) = mpsc::channel();
+    {
+        let (app, model, control) = (app.clone(), model.to_string(), control.clone());
+        thread::spawn(move || {
+            let mut rate = RateTracker::default();
+            let result = OllamaClient::from_config().pull(&model, |progress| {
+                if control.load(Ordering::SeqCst) != CONTROL_RUN {
+                    return false;
+                }
+                rate.observe(progress);
+                if rate.should_emit() {
+                    let completed = progress.completed.unwrap_or(0);
+                    let total = progress.total.unwrap_or(0);
+                    update(&model, |s| {
+                        s.completed = completed;
+                        s.total = total;
+                    });
+                    emit_progress(&app, &model, DownloadState::Downloading, progress, &rate);
+                }
+                true
+            });
+            let _ = tx.send(result);
+        });
+    }
+
+    loop {
+        match rx.recv_timeout(CONTROL_POLL) {
+            Ok(result) => return result,
+            Err(RecvTimeoutError::Timeout) if control.load(Ordering::SeqCst) != CONTROL_RUN => {
+                return Ok(PullOutcome::Stopped);
+            }
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => {
+                return Err(OllamaError::Remote("the pull worker stopped unexpectedly".into()));
+            }
+        }
+    }
+}
+
+fn stopped(control: &AtomicU8) -> Finish {
+    if control.load(Ordering::SeqCst) == CONTROL_PAUSE {
+        Finish::Paused
+    } else {
+        Finish::Cancelled
+    }
+}
+
+/// 2s, 4s, 8s … capped at `MAX_BACKOFF`.
+fn backoff(attempt: u32) -> Duration {
+    let secs = 2u64.saturating_pow(attempt.min(16));
+    Duration::from_secs(secs).min(MAX_BACKOFF)
+}
+
+/// Sleep for `delay`, waking early if the job is paused or cancelled.
+/// Returns `false` when interrupted.
+fn sleep_unless_signalled(delay: Duration, control: &AtomicU8) -> bool {
+    let until = Instant::now() + delay;
+    while Instant::now() < until {
+        if control.load(Ordering::SeqCst) != CONTROL_RUN {
+            return false;
+        }
+        thread::sleep(CONTROL_POLL);
+    }
+    true
+}
+
+fn update(model: &str, f: impl FnOnce(&mut DownloadStatus)) {
+    if let Some(job) = DOWNLOADS.lock().unwrap().job_mut(model) {
+        f(&mut job.status);
+    }
+}
+
+fn concurrency() -> usize {
+    AppConfig::load_or_default()
+        .model_download_concurrency
+        .map(|n| n.max(1) as usize)
+        .unwrap_or(DEFAULT_CONCURRENCY)
+}
+
+fn max_retries() -> u32 {
+    AppConfig::load_or_default()
+        .model_download_retries
+        .map(u32::from)
+        .unwrap_or(DEFAULT_RETRIES)
+}
+
+// === Progress accounting ===
+
+/// Smoothed transfer rate for the layer currently being pulled.
+#[derive(Default)]
+struct RateTracker {
+    digest: Option<String>,
+    status: String,
+    sample_at: Option<Instant>,
+    sample_bytes: u64,
+    bytes_per_sec: f64,
+    remaining: u64,
+    last_emit: Option<Instant>,
+    status_changed: bool,
+}
+
+impl RateTracker {
+    fn observe(&mut self, p: &PullProgress) {
+        self.status_changed = p.status != self.status;
+        self.status = p.status.clone();
+
+        if p.digest != self.digest {
+            self.digest = p.digest.clone();
+            self.sample_at = None;
+            self.bytes_per_sec = 0.0;
+        }
+
+        let completed = p.completed.unwrap_or(0);
+        self.remaining = p.total.unwrap_or(0).saturating_sub(completed);
+
+        let now = Instant::now();
+        match self.sample_at {
+            None => {
+                self.sample_at = Some(now);
+                self.sample_bytes = completed;
+            }
+            Some(at) => {
+                let elapsed = now.duration_since(at).as_secs_f64();
+                if elapsed >= 0.5 {
+                    let instant = completed.saturating_sub(self.sample_bytes) as f64 / elapsed;
+                    self.bytes_per_sec = if self.bytes_per_sec == 0.0 {
+                        instant
+                    } else {
+                        0.7 * self.bytes_per_sec + 0.3 * instant
+                    };
+                    self.sample_at = Some(now);
+                    self.sample_bytes = completed;
+                }
+            }
+        }
+    }
+
+    fn should_emit(&mut self) -> bool {
+        let due = self
+            .last_emit
+            .is_none_or(|t| t.elapsed() >= EMIT_INTERVAL);
+        if due || self.status_changed {
+            self.last_emit = Some(Instant::now());
+            return true;
+        }
+        false
+    }
+
+    fn eta_seconds(&self) -> Option<u64> {
+        (self.bytes_per_sec >= 1.0).then(|| (self.remaining as f64 / self.bytes_per_sec) as u64)
+    }
+}
+
+fn emit_progress(
+    app: &AppHandle,
+    model: &str,
+    state: DownloadState,
+    progress: &PullProgress,
+    rate: &RateTracker,
+) {
+    app.emit(
+        "model-pull-progress",
+        ModelPullProgress {
+            model: model.to_string(),
+            state,
+            status: progress.status.clone(),
+            digest: progress.digest.clone(),
+            completed: progress.completed.unwrap_or(0),
+            total: progress.total.unwrap_or(0),
+            speed_bytes_per_sec: rate.bytes_per_sec as u64,
+            eta_seconds: rate.eta_seconds(),
+        },
+    )
+    .ok();
+}
+
+fn emit_state(app: &AppHandle, model: &str, state: DownloadState, status: &str) {
+    let (completed, total) = DOWNLOADS
+        .lock()
+        .unwrap()
+        .job_mut(model)
+        .map(|j| (j.status.completed, j.status.total))
+        .unwrap_or((0, 0));
+
+    app.emit(
+        "model-pull-progress",
+        ModelPullProgress {
+            model: model.to_string(),
+            state,
+            status: status.to_string(),
+            digest: None,
+            completed,
+            total,
+            speed_bytes_per_sec: 0,
+            eta_seconds: None,
+        },
+    )
+    .ok();
+}
+
+fn log(app: &AppHandle, message: String) {
+    app.emit(
+        "component-log",
+        ComponentLog {
+            component: COMPONENT.into(),
+            message,
+        },
+    )
+    .ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(digest: &str, completed: u64, total: u64) -> PullProgress {
+        PullProgress {
+            status: format!("pulling {}", digest),
+            digest: Some(digest.into()),
+            total: Some(total),
+            completed: Some(completed),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let secs: Vec<u64> = (1..7).map(|n| backoff(n).as_secs()).collect();
+        assert_eq!(secs, [2, 4, 8, 16, 30, 30]);
+        assert_eq!(backoff(u32::MAX), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn rate_and_eta_follow_the_transfer() {
+        let mut rate = RateTracker::default();
+        rate.observe(&chunk("sha256:a", 0, 10_000_000));
+        assert_eq!(rate.eta_seconds(), None, "no rate before the first sample window");
+
+        thread::sleep(Duration::from_millis(600));
+        rate.observe(&chunk("sha256:a", 3_000_000, 10_000_000));
+        // ~5 MB/s over the 0.6 s window (a little less if the sleep overshot)
+        assert!((3_500_000.0..=5_000_000.0).contains(&rate.bytes_per_sec), "{}", rate.bytes_per_sec);
+        let eta = rate.eta_seconds().unwrap();
+        assert!((1..=2).contains(&eta), "{}", eta);
+    }
+
+    #[test]
+    fn a_new_layer_resets_the_rate() {
+        let mut rate = RateTracker::default();
+        rate.observe(&chunk("sha256:a", 0, 100));
+        rate.bytes_per_sec = 42.0;
+        rate.observe(&chunk("sha256:b", 0, 100));
+        assert_eq!(rate.bytes_per_sec, 0.0);
+        assert_eq!(rate.eta_seconds(), None);
+    }
+
+    #[test]
+    fn emits_are_throttled_unless_the_status_changes() {
+        let mut rate = RateTracker::default();
+        rate.observe(&chunk("sha256:a", 1, 100));
+        assert!(rate.should_emit());
+        rate.observe(&chunk("sha256:a", 2, 100));
+        assert!(!rate.should_emit());
+        rate.observe(&chunk("sha256:b", 0, 100));
+        assert!(rate.should_emit(), "a status change is always reported");
+    }
+}
@@ -0,0 +1,518 @@
+// 🧩 Gignaati Workbench — Model recommendations
+//
+// Ranks a curated catalog of Ollama models for this machine. For every model
+// and quantization the memory footprint is estimated from the parameter
+// count, checked against total / available RAM and the free space on the
+// models volume, and given a rough tokens/sec class from the CPU (cores,
+// AVX / AVX2 / AVX-512, Apple Silicon). The best fit can be stored as
+// `ollama_default_model`.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use sysinfo::System;
+
+use crate::config::AppConfig;
+use crate::discovery::Tool;
+use crate::ollama_client::OllamaClient;
+use crate::ollama_models::ollama_models_dir;
+use crate::version::{self, Verdict};
+
+const GB: f64 = 1_000_000_000.0;
+/// Runtime buffers outside weights and KV cache.
+const RUNTIME_OVERHEAD_GB: f64 = 0.4;
+/// KV cache at the default 2k-4k context, per billion parameters.
+const KV_GB_PER_B_PARAMS: f64 = 0.08;
+/// Disk kept free after a download.
+const DISK_MARGIN_GB: f64 = 2.0;
+
+struct CatalogEntry {
+    /// Default tag (`q4_K_M` in the Ollama library).
+    model: &'static str,
+    /// Tag prefix for explicit quantizations, e.g. `llama3.2:3b-instruct-`.
+    quant_prefix: &'static str,
+    params_b: f64,
+    purpose: &'static str,
+}
+
+const CATALOG: &[CatalogEntry] = &[
+    CatalogEntry { model: "qwen2.5:0.5b", quant_prefix: "qwen2.5:0.5b-instruct-", params_b: 0.49, purpose: "general" },
+    CatalogEntry { model: "llama3.2:1b", quant_prefix: "llama3.2:1b-instruct-", params_b: 1.24, purpose: "general" },
+    CatalogEntry { model: "qwen2.5:1.5b", quant_prefix: "qwen2.5:1.5b-instruct-", params_b: 1.54, purpose: "general" },
+    CatalogEntry { model: "gemma2:2b", quant_prefix: "gemma2:2b-instruct-", params_b: 2.61, purpose: "general" },
+    CatalogEntry { model: "qwen2.5:3b", quant_prefix: "qwen2.5:3b-instruct-", params_b: 3.09, purpose: "general" },
+    CatalogEntry { model: "llama3.2:3b", quant_prefix: "llama3.2:3b-instruct-", params_b: 3.21, purpose: "general" },
+    CatalogEntry { model: "phi3:3.8b", quant_prefix: "phi3:3.8b-mini-4k-instruct-", params_b: 3.82, purpose: "general" },
+    CatalogEntry { model: "qwen2.5:7b", quant_prefix: "qwen2.5:7b-instruct-", params_b: 7.62, purpose: "general" },
+    CatalogEntry { model: "qwen2.5-coder:7b", quant_prefix: "qwen2.5-coder:7b-instruct-", params_b: 7.62, purpose: "coding" },
+    CatalogEntry { model: "deepseek-r1:7b", quant_prefix: "deepseek-r1:7b-qwen-distill-", params_b: 7.62, purpose: "reasoning" },
+    CatalogEntry { model: "llama3.1:8b", quant_prefix: "llama3.1:8b-instruct-", params_b: 8.03, purpose: "general" },
+    CatalogEntry { model: "gemma2:9b", quant_prefix: "gemma2:9b-instruct-", params_b: 9.24, purpose: "general" },
+    CatalogEntry { model: "qwen2.5:14b", quant_prefix: "qwen2.5:14b-instruct-", params_b: 14.8, purpose: "general" },
+    CatalogEntry { model: "qwen2.5:32b", quant_prefix: "qwen2.5:32b-instruct-", params_b: 32.8, purpose: "general" },
+    CatalogEntry { model: "llama3.3:70b", quant_prefix: "llama3.3:70b-instruct-", params_b: 70.6, purpose: "general" },
+];
+
+/// Quantizations considered, best quality first, with effective bits per weight.
+const QUANTIZATIONS: &[(&str, f64)] = &[("q8_0", 8.5), ("q5_K_M", 5.69), ("q4_K_M", 4.85)];
+/// The quantization the Ollama library serves under the default tag.
+const DEFAULT_QUANT: &str = "q4_K_M";
+
+#[derive(Serialize, Debug, Clone)]
+pub struct HardwareProfile {
+    pub total_ram_gb: f64,
+    pub available_ram_gb: f64,
+    pub physical_cores: Option<usize>,
+    pub logical_cores: usize,
+    pub cpu_brand: String,
+    pub arch: String,
+    pub avx: bool,
+    pub avx2: bool,
+    pub avx512: bool,
+    /// Apple Silicon: unified memory, Metal acceleration.
+    pub apple_silicon: bool,
+    pub models_dir: Option<String>,
+    pub free_disk_gb: Option<f64>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Fit {
+    /// Fits in available RAM with room for n8n and the rest of the system.
+    Comfortable,
+    /// Fits in RAM only if other applications are closed.
+    Tight,
+    TooLarge,
+    NotEnoughDisk,
+    /// The installed Ollama is too old for this model family.
+    UnsupportedOllama,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum SpeedClass {
+    Fast,
+    Usable,
+    Slow,
+    VerySlow,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct QuantEstimate {
+    pub quantization: String,
+    /// Tag to pull for this quantization.
+    pub tag: String,
+    pub memory_gb: f64,
+    pub download_gb: f64,
+    pub fit: Fit,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct RecommendedModel {
+    pub rank: usize,
+    /// Tag to pull: the default tag for `q4_K_M`, otherwise the explicit one.
+    pub model: String,
+    pub purpose: String,
+    pub parameters_b: f64,
+    /// Quantization picked for this machine.
+    pub quantization: String,
+    pub memory_gb: f64,
+    pub download_gb: f64,
+    pub fit: Fit,
+    pub speed: SpeedClass,
+    pub est_tokens_per_sec: f64,
+    pub installed: bool,
+    pub reason: String,
+    pub quantizations: Vec<QuantEstimate>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ModelRecommendation {
+    pub hardware: HardwareProfile,
+    pub models: Vec<RecommendedModel>,
+    /// Set when `ollama_default_model` was pre-selected by this call.
+    pub selected_default: Option<String>,
+}
+
+// === Hardware ===
+
+pub fn hardware_profile() -> HardwareProfile {
+    let mut sys = System::new();
+    sys.refresh_memory();
+    sys.refresh_cpu();
+
+    let (avx, avx2, avx512) = cpu_features();
+    let models_dir = ollama_models_dir();
+    let free_disk_gb = models_dir
+        .as_deref()
+        .and_then(existing_ancestor)
+        .and_then(|dir| fs2::available_space(dir).ok())
+        .map(|bytes| round1(bytes as f64 / GB));
+
+    HardwareProfile {
+        total_ram_gb: round1(sys.total_memory() as f64 / GB),
+        available_ram_gb: round1(sys.available_memory() as f64 / GB),
+        physical_cores: sys.physical_core_count(),
+        logical_cores: std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+        cpu_brand: sys
+            .cpus()
+            .first()
+            .map(|c| c.brand().trim().to_string())
+            .unwrap_or_default(),
+        arch: std::env::consts::ARCH.to_string(),
+        avx,
+        avx2,
+        avx512,
+        apple_silicon: cfg!(all(target_os = "macos", target_arch = "aarch64")),
+        models_dir: models_dir.map(|d| d.to_string_lossy().to_string()),
+        free_disk_gb,
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn cpu_features() -> (bool, bool, bool) {
+    (
+        std::arch::is_x86_feature_detected!("avx"),
+        std::arch::is_x86_feature_detected!("avx2"),
+        std::arch::is_x86_feature_detected!("avx512f"),
+    )
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+fn cpu_features() -> (bool, bool, bool) {
+    (false, false, false)
+}
+
+/// The models directory may not exist before the first pull.
+fn existing_ancestor(path: &Path) -> Option<PathBuf> {
+    path.ancestors().find(|p| p.exists()).map(Path::to_path_buf)
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+// === Estimates ===
+
+/// Rough memory bandwidth for CPU inference in GB/s; token generation is
+/// bandwidth-bound, so tokens/sec ≈ bandwidth / weights.
+fn bandwidth_gbps(hw: &HardwareProfile) -> f64 {
+    let base = if hw.apple_silicon {
+        70.0
+    } else if hw.avx512 {
+        40.0
+    } else if hw.avx2 {
+        30.0
+    } else if hw.avx {
+        15.0
+    } else if hw.arch == "aarch64" {
+        20.0
+    } else {
+        // x86 without AVX: Ollama falls back to a slow generic build
+        6.0
+    };
+    let cores = hw.physical_cores.unwrap_or(hw.logical_cores);
+    if cores < 4 {
+        base * 0.5
+    } else {
+        base
+    }
+}
+
+fn speed_class(tokens_per_sec: f64) -> SpeedClass {
+    match tokens_per_sec {
+        t if t >= 20.0 => SpeedClass::Fast,
+        t if t >= 8.0 => SpeedClass::Usable,
+        t if t >= 3.0 => SpeedClass::Slow,
+        _ => SpeedClass::VerySlow,
+    }
+}
+
+fn weights_gb(params_b: f64, bits: f64) -> f64 {
+    params_b * bits / 8.0
+}
+
+fn memory_gb(params_b: f64, bits: f64) -> f64 {
+    weights_gb(params_b, bits) + params_b * KV_GB_PER_B_PARAMS + RUNTIME_OVERHEAD_GB
+}
+
+fn fit(hw: &HardwareProfile, memory: f64, download: f64) -> Fit {
+    if let Some(free) = hw.free_disk_gb {
+        if download + DISK_MARGIN_GB > free {
+            return Fit::NotEnoughDisk;
+        }
+    }
+    if memory <= hw.available_ram_gb && memory <= hw.total_ram_gb * 0.6 {
+        Fit::Comfortable
+    } else if memory <= hw.total_ram_gb * 0.85 {
+        Fit::Tight
+    } else {
+        Fit::TooLarge
+    }
+}
+
+fn tag_for(entry: &CatalogEntry, quant: &str) -> String {
+    if quant == DEFAULT_QUANT {
+        entry.model.to_string()
+    } else {
+        format!("{}{}", entry.quant_prefix, quant)
+    }
+}
+
+fn estimate(entry: &CatalogEntry, hw: &HardwareProfile, blocked_by: Option<&str>, installed: &[String]) -> RecommendedModel {
+    let quantizations: Vec<QuantEstimate> = QUANTIZATIONS
+        .iter()
+        .map(|(quant, bits)| {
+            let memory = memory_gb(entry.params_b, *bits);
+            let download = weights_gb(entry.params_b, *bits);
+            QuantEstimate {
+                quantization: quant.to_string(),
+                tag: tag_for(entry, quant),
+                memory_gb: round1(memory),
+                download_gb: round1(download),
+                fit: fit(hw, memory, download),
+            }
+        })
+        .collect();
+
+    let speed_at = |quant: &str| {
+        let bits = QUANTIZATIONS
+            .iter()
+            .find(|(q, _)| *q == quant)
+            .map(|(_, bits)| *bits)
+            .unwrap_or(4.85);
+        round1(bandwidth_gbps(hw) * 0.6 / weights_gb(entry.params_b, bits))
+    };
+
+    // Higher precision only when it fits comfortably and costs no speed
+    // class; otherwise the library default
+    let default = quantizations
+        .iter()
+        .find(|q| q.quantization == DEFAULT_QUANT)
+        .expect("default quantization is listed");
+    let default_speed = speed_class(speed_at(DEFAULT_QUANT));
+    let chosen = quantizations
+        .iter()
+        .find(|q| q.fit == Fit::Comfortable && speed_class(speed_at(&q.quantization)) == default_speed)
+        .unwrap_or(default)
+        .clone();
+    let tokens_per_sec = speed_at(&chosen.quantization);
+    let speed = speed_class(tokens_per_sec);
+    let fit = if blocked_by.is_some() { Fit::UnsupportedOllama } else { chosen.fit };
+
+    let cpu = cpu_summary(hw);
+    let reason = match (fit, blocked_by) {
+        (Fit::UnsupportedOllama, Some(message)) => message.to_string(),
+        (Fit::Comfortable, _) => format!(
+            "Fits comfortably: ~{:.1} GB of {:.1} GB RAM ({:.1} GB free); ~{:.0} tokens/s on {}.",
+            chosen.memory_gb, hw.total_ram_gb, hw.available_ram_gb, tokens_per_sec, cpu
+        ),
+        (Fit::Tight, _) if chosen.memory_gb <= hw.available_ram_gb => format!(
+            "Tight fit: ~{:.1} GB is most of the {:.1} GB RAM, leaving little for n8n and other apps. ~{:.0} tokens/s on {}.",
+            chosen.memory_gb, hw.total_ram_gb, tokens_per_sec, cpu
+        ),
+        (Fit::Tight, _) => format!(
+            "Tight fit: needs ~{:.1} GB but only {:.1} GB is free; close other apps. ~{:.0} tokens/s on {}.",
+            chosen.memory_gb, hw.available_ram_gb, tokens_per_sec, cpu
+        ),
+        (Fit::NotEnoughDisk, _) => format!(
+            "Needs ~{:.1} GB of disk for the download; {:.1} GB is free on the models volume.",
+            chosen.download_gb,
+            hw.free_disk_gb.unwrap_or(0.0)
+        ),
+        _ => format!(
+            "Too large: needs ~{:.1} GB of RAM even at {}; this machine has {:.1} GB.",
+            chosen.memory_gb, chosen.quantization, hw.total_ram_gb
+        ),
+    };
+
+    RecommendedModel {
+        rank: 0,
+        installed: installed.contains(&chosen.tag),
+        model: chosen.tag,
+        purpose: entry.purpose.to_string(),
+        parameters_b: entry.params_b,
+        quantization: chosen.quantization,
+        memory_gb: chosen.memory_gb,
+        download_gb: chosen.download_gb,
+        fit,
+        speed,
+        est_tokens_per_sec: tokens_per_sec,
+        reason,
+        quantizations,
+    }
+}
+
+fn cpu_summary(hw: &HardwareProfile) -> String {
+    let cores = hw.physical_cores.unwrap_or(hw.logical_cores);
+    let features = if hw.apple_silicon {
+        "Apple Silicon"
+    } else if hw.avx512 {
+        "AVX-512"
+    } else if hw.avx2 {
+        "AVX2"
+    } else if hw.avx {
+        "AVX"
+    } else {
+        "no AVX"
+    };
+    let plural = if cores == 1 { "" } else { "s" };
+    format!("{} core{}, {}", cores, plural, features)
+}
+
+/// The catalog ranked for `hw`: models that fit come first, and among those
+/// the largest one that still runs at a usable speed.
+pub fn rank(hw: &HardwareProfile, ollama: Option<&semver::Version>, installed: &[String]) -> Vec<RecommendedModel> {
+    let mut models: Vec<RecommendedModel> = CATALOG
+        .iter()
+        .map(|entry| {
+            let blocked = ollama
+                .and_then(|v| version::check_model(entry.model, v))
+                .filter(|f| f.verdict == Verdict::Blocking)
+                .map(|f| f.message);
+            estimate(entry, hw, blocked.as_deref(), installed)
+        })
+        .collect();
+
+    models.sort_by(|a, b| {
+        let too_slow = |m: &RecommendedModel| m.speed > SpeedClass::Usable;
+        a.fit
+            .cmp(&b.fit)
+            .then(too_slow(a).cmp(&too_slow(b)))
+            .then(b.parameters_b.total_cmp(&a.parameters_b))
+            .then(a.model.cmp(&b.model))
+    });
+    for (i, m) in models.iter_mut().enumerate() {
+        m.rank = i + 1;
+    }
+    models
+}
+
+/// Best general-purpose model that fits: the default model candidate.
+pub fn best_default(models: &[RecommendedModel]) -> Option<&RecommendedModel> {
+    models
+        .iter()
+        .find(|m| m.purpose == "general" && matches!(m.fit, Fit::Comfortable | Fit::Tight))
+}
+
+// === Commands ===
+
+/// 🧠 Rank the model catalog for this machine. With `apply_default` the top
+/// general-purpose model becomes `ollama_default_model`.
+#[tauri::command]
+pub async fn recommend_models(apply_default: Option<bool>) -> Result<ModelRecommendation, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let hardware = hardware_profile();
+        let installed: Vec<String> = OllamaClient::from_config()
+            .tags()
+            .map(|tags| tags.models.into_iter().map(|m| m.name).collect())
+            .unwrap_or_default();
+        let models = rank(&hardware, version::located(Tool::Ollama).as_ref(), &installed);
+
+        let mut selected_default = None;
+        if apply_default.unwrap_or(false) {
+            let best = best_default(&models)
+                .ok_or("No catalog model fits this machine")?
+                .model
+                .clone();
+            AppConfig::modify(|cfg| cfg.ollama_default_model = Some(best.clone()))?;
+            selected_default = Some(best);
+        }
+
+        Ok(ModelRecommendation {
+            hardware,
+            models,
+            selected_default,
+        })
+    })
+    .await
+    .map_err(|e| format!("Model recommendation failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn machine(total_ram_gb: f64, available_ram_gb: f64, free_disk_gb: Option<f64>) -> HardwareProfile {
+        HardwareProfile {
+            total_ram_gb,
+            available_ram_gb,
+            physical_cores: Some(8),
+            logical_cores: 16,
+            cpu_brand: "Test CPU".into(),
+            arch: "x86_64".into(),
+            avx: true,
+            avx2: true,
+            avx512: false,
+            apple_silicon: false,
+            models_dir: None,
+            free_disk_gb,
+        }
+    }
+
+    #[test]
+    fn fit_weighs_free_ram_total_ram_and_disk() {
+        let hw = machine(16.0, 12.0, Some(50.0));
+        assert_eq!(fit(&hw, 6.0, 4.0), Fit::Comfortable);
+        // Free, but more than 60 % of all RAM
+        assert_eq!(fit(&hw, 11.0, 8.0), Fit::Tight);
+        assert_eq!(fit(&machine(16.0, 4.0, None), 6.0, 4.0), Fit::Tight);
+        assert_eq!(fit(&hw, 14.0, 9.0), Fit::TooLarge);
+        assert_eq!(fit(&hw, 6.0, 49.0), Fit::NotEnoughDisk);
+    }
+
+    #[test]
+    fn tight_reasons_name_the_actual_constraint() {
+        let reason = |hw: &HardwareProfile, model: &str| {
+            let entry = CATALOG.iter().find(|e| e.model == model).unwrap();
+            let m = estimate(entry, hw, None, &[]);
+            assert_eq!(m.fit, Fit::Tight, "{}", m.reason);
+            m.reason
+        };
+        // qwen2.5:14b at q4_K_M needs ~10.5 GB
+        let over_share = reason(&machine(16.0, 15.0, None), "qwen2.5:14b");
+        assert!(over_share.contains("most of the 16.0 GB RAM"), "{}", over_share);
+        let not_free = reason(&machine(32.0, 6.0, None), "qwen2.5:14b");
+        assert!(not_free.contains("only 6.0 GB is free"), "{}", not_free);
+    }
+
+    #[test]
+    fn rank_puts_the_largest_comfortable_model_first() {
+        let hw = machine(32.0, 24.0, Some(200.0));
+        let models = rank(&hw, None, &[]);
+        assert_eq!(models.len(), CATALOG.len());
+        assert_eq!(models.iter().map(|m| m.rank).collect::<Vec<_>>(), (1..=CATALOG.len()).collect::<Vec<_>>());
+        assert!(models.windows(2).all(|w| w[0].fit <= w[1].fit));
+
+        let top = &models[0];
+        assert_eq!(top.fit, Fit::Comfortable);
+        assert!(top.speed <= SpeedClass::Usable);
+        assert_eq!(models.last().unwrap().model, "llama3.3:70b");
+
+        // Installed means the picked quantization's tag is already pulled
+        let qwen = |models: &[RecommendedModel]| {
+            models.iter().find(|m| m.model.starts_with("qwen2.5:7b")).unwrap().clone()
+        };
+        let tag = qwen(&models).model;
+        assert!(qwen(&rank(&hw, None, &[tag])).installed);
+    }
+
+    #[test]
+    fn rank_marks_models_the_installed_ollama_cannot_run() {
+        let old = semver::Version::new(0, 3, 0);
+        let models = rank(&machine(32.0, 24.0, None), Some(&old), &[]);
+        let r1 = models.iter().find(|m| m.model.starts_with("deepseek-r1:7b")).unwrap();
+        assert_eq!(r1.fit, Fit::UnsupportedOllama);
+        assert!(r1.reason.contains("needs Ollama 0.5.7"), "{}", r1.reason);
+    }
+
+    #[test]
+    fn best_default_is_a_general_model_that_fits() {
+        let models = rank(&machine(32.0, 24.0, None), None, &[]);
+        let best = best_default(&models).unwrap();
+        assert_eq!(best.purpose, "general");
+        assert!(matches!(best.fit, Fit::Comfortable | Fit::Tight));
+
+        let tiny = rank(&machine(0.5, 0.3, None), None, &[]);
+        assert!(best_default(&tiny).is_none());
+    }
+}
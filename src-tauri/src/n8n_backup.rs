@@ -0,0 +1,801 @@
+// 🧩 n8n backups — timestamped archives of workflows and credentials
+//
+// Two methods:
+//   • `cli`      — `n8n export:workflow` / `export:credentials` (one JSON file
+//                  each). Works for SQLite and PostgreSQL; n8n may keep running.
+//   • `snapshot` — the whole `.n8n` folder (SQLite database, encryption key,
+//                  settings). SQLite only; n8n is stopped while it is copied.
+//
+// Each backup is `<backups>/<id>.tar.gz` holding the files plus
+// `manifest.json`, with a copy of the manifest (and the archive's SHA-256)
+// next to it as `<id>.json` so listing does not unpack anything. Credentials
+// stay encrypted with the instance key in both methods; CLI backups record a
+// fingerprint of that key so a restore into an instance with a different key
+// is refused instead of leaving credentials that cannot be decrypted.
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::config::n8n::N8nDatabase;
+use crate::config::AppConfig;
+use crate::installer::download::{extract_archive, sha256_file};
+use crate::installer::install_n8n_real::installed_n8n;
+use crate::installer::node_runtime::managed_runtime;
+use crate::n8n_manager::{run_n8n_cli, start_n8n};
+use crate::supervisor::{self, ServiceKind};
+
+const MANIFEST_FORMAT: u32 = 1;
+const DEFAULT_KEEP: u16 = 10;
+/// Folder inside a snapshot archive that holds the `.n8n` contents.
+const SNAPSHOT_ROOT: &str = "n8n";
+
+#[derive(Serialize, Clone)]
+struct ComponentLog {
+    component: String,
+    message: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupMethod {
+    Cli,
+    Snapshot,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupReason {
+    Manual,
+    PreUpgrade,
+    PreRestore,
+}
+
+impl BackupReason {
+    fn slug(self) -> &'static str {
+        match self {
+            BackupReason::Manual => "manual",
+            BackupReason::PreUpgrade => "pre-upgrade",
+            BackupReason::PreRestore => "pre-restore",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BackupFile {
+    /// Path inside the archive.
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BackupManifest {
+    pub format: u32,
+    pub id: String,
+    /// Unix seconds (UTC).
+    pub created_at: u64,
+    pub method: BackupMethod,
+    pub reason: BackupReason,
+    pub n8n_version: Option<String>,
+    /// `sqlite` or `postgres` at the time of the backup.
+    pub database: String,
+    /// Counts for `cli` backups; `None` for snapshots.
+    pub workflows: Option<usize>,
+    pub credentials: Option<usize>,
+    /// Fingerprint of the encryption key the credentials were exported with
+    /// (`cli` backups; a snapshot carries the key file itself).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_fingerprint: Option<String>,
+    pub files: Vec<BackupFile>,
+    /// Archive file name next to this manifest.
+    pub archive: String,
+    /// Only in the side-car copy (the archive cannot contain its own hash).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_sha256: Option<String>,
+}
+
+// === CLI export / import (shared with the PostgreSQL migration) ===
+
+/// Export all workflows and credentials into `dir/workflows` and
+/// `dir/credentials`. Returns `(workflows, credentials)`.
+pub(crate) fn export_data(cfg: &AppConfig, dir: &Path) -> Result<(usize, usize), String> {
+    let workflows_dir = dir.join("workflows");
+    let credentials_dir = dir.join("credentials");
+    for d in [&workflows_dir, &credentials_dir] {
+        fs::create_dir_all(d).map_err(|e| format!("Failed to create '{}': {}", d.display(), e))?;
+    }
+
+    export(cfg, "export:workflow", &workflows_dir, "No workflows found")?;
+    // Without --decrypted the values stay encrypted with the instance key
+    export(cfg, "export:credentials", &credentials_dir, "No credentials found")?;
+
+    Ok((count_json(&workflows_dir), count_json(&credentials_dir)))
+}
+
+/// Import what `export_data` wrote. Credentials go first so workflows can
+/// resolve their references. Existing items with the same ID are replaced;
+/// items created after the export are left alone.
+pub(crate) fn import_data(cfg: &AppConfig, dir: &Path) -> Result<(), String> {
+    for (command, sub) in [("import:credentials", "credentials"), ("import:workflow", "workflows")] {
+        let input = dir.join(sub);
+        if count_json(&input) > 0 {
+            let input_arg = format!("--input={}", input.display());
+            run_n8n_cli(cfg, &[command, "--separate", &input_arg])?;
+        }
+    }
+    Ok(())
+}
+
+/// Run an n8n export; an empty instance is not an error.
+fn export(cfg: &AppConfig, command: &str, dir: &Path, empty_marker: &str) -> Result<(), String> {
+    let output_arg = format!("--output={}", dir.join("").display());
+    match run_n8n_cli(cfg, &[command, "--all", "--separate", &output_arg]) {
+        Err(e) if e.contains(empty_marker) => Ok(()),
+        other => other.map(|_| ()),
+    }
+}
+
+fn count_json(dir: &Path) -> usize {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.path().extension().is_some_and(|ext| ext == "json"))
+                .count()
+        })
+        .unwrap_or(0)
+}
+
+// === Backup ===
+
+fn backups_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join("backups")
+        .join("n8n"))
+}
+
+/// Create a backup. Without an explicit `method`, the CLI export is tried
+/// first and a folder snapshot is used if n8n's CLI is unavailable (SQLite
+/// only).
+pub fn create_backup(
+    app: &AppHandle,
+    method: Option<BackupMethod>,
+    reason: BackupReason,
+) -> Result<BackupManifest, String> {
+    let cfg = AppConfig::load()?;
+    let dir = backups_dir(app)?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create '{}': {}", dir.display(), e))?;
+
+    let created_at = now();
+    let id = unique_id(&dir, created_at, reason);
+    let archive_name = format!("{}.tar.gz", id);
+    let archive_path = dir.join(&archive_name);
+    let is_postgres = matches!(cfg.n8n.database, N8nDatabase::Postgres(_));
+
+    let mut manifest = BackupManifest {
+        format: MANIFEST_FORMAT,
+        id: id.clone(),
+        created_at,
+        method: method.unwrap_or(BackupMethod::Cli),
+        reason,
+        n8n_version: installed_n8n(managed_runtime().as_ref()).map(|(_, v)| v),
+        database: if is_postgres { "postgres" } else { "sqlite" }.into(),
+        workflows: None,
+        credentials: None,
+        key_fingerprint: None,
+        files: Vec::new(),
+        archive: archive_name,
+        archive_sha256: None,
+    };
+
+    log(app, &format!("💾 Creating n8n backup '{}'...", id));
+
+    let result = match manifest.method {
+        BackupMethod::Cli => match backup_cli(&cfg, &dir, &archive_path, &mut manifest) {
+            Err(e) if method.is_none() && !is_postgres => {
+                log(app, &format!("⚠ CLI export failed ({}); snapshotting the n8n folder instead.", e));
+                manifest.method = BackupMethod::Snapshot;
+                backup_snapshot(app, &cfg, &archive_path, &mut manifest)
+            }
+            other => other,
+        },
+        BackupMethod::Snapshot => backup_snapshot(app, &cfg, &archive_path, &mut manifest),
+    };
+    if let Err(e) = result {
+        let _ = fs::remove_file(&archive_path);
+        log(app, &format!("❌ Backup failed: {}", e));
+        return Err(e);
+    }
+
+    manifest.archive_sha256 = Some(sha256_file(&archive_path)?);
+    write_json(&dir.join(format!("{}.json", id)), &manifest)?;
+
+    let summary = match (manifest.workflows, manifest.credentials) {
+        (Some(w), Some(c)) => format!("{} workflow(s), {} credential(s)", w, c),
+        _ => format!("{} file(s) from the n8n folder", manifest.files.len()),
+    };
+    log(app, &format!("✅ Backup '{}' saved ({}).", id, summary));
+
+    apply_retention(app, &cfg, &dir);
+    Ok(manifest)
+}
+
+fn backup_cli(
+    cfg: &AppConfig,
+    dir: &Path,
+    archive_path: &Path,
+    manifest: &mut BackupManifest,
+) -> Result<(), String> {
+    let staging = dir.join(format!(".staging-{}", manifest.id));
+    let result = export_data(cfg, &staging).and_then(|(workflows, credentials)| {
+        manifest.workflows = Some(workflows);
+        manifest.credentials = Some(credentials);
+        manifest.key_fingerprint = key_fingerprint(cfg);
+        write_archive(archive_path, &staging, "", manifest)
+    });
+    let _ = fs::remove_dir_all(&staging);
+    result
+}
+
+fn backup_snapshot(
+    app: &AppHandle,
+    cfg: &AppConfig,
+    archive_path: &Path,
+    manifest: &mut BackupManifest,
+) -> Result<(), String> {
+    if matches!(cfg.n8n.database, N8nDatabase::Postgres(_)) {
+        return Err("n8n stores its data in PostgreSQL; use the CLI backup method.".into());
+    }
+    let data_dir = cfg.n8n.data_dir();
+    if !data_dir.is_dir() {
+        return Err(format!("n8n data folder '{}' does not exist", data_dir.display()));
+    }
+
+    // A consistent copy of the SQLite file needs n8n stopped
+    let was_running = supervisor::stop(app, ServiceKind::N8n);
+    let result = write_archive(archive_path, &data_dir, SNAPSHOT_ROOT, manifest);
+    if was_running {
+        if let Err(e) = start_n8n(app) {
+            log(app, &format!("⚠ n8n could not be restarted after the snapshot: {}", e));
+        }
+    }
+    result
+}
+
+/// Pack every file under `src` (as `prefix/...`) plus `manifest.json`.
+fn write_archive(
+    archive_path: &Path,
+    src: &Path,
+    prefix: &str,
+    manifest: &mut BackupManifest,
+) -> Result<(), String> {
+    let archive_error = |e: std::io::Error| format!("Failed to write '{}': {}", archive_path.display(), e);
+
+    let file = File::create(archive_path).map_err(archive_error)?;
+    let mut tar = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+
+    for (abs, rel) in walk_files(src) {
+        let name = if prefix.is_empty() {
+            rel
+        } else {
+            format!("{}/{}", prefix, rel)
+        };
+        let size = fs::metadata(&abs).map(|m| m.len()).unwrap_or(0);
+        manifest.files.push(BackupFile {
+            path: name.clone(),
+            size,
+            sha256: sha256_file(&abs)?,
+        });
+        tar.append_path_with_name(&abs, &name).map_err(archive_error)?;
+    }
+
+    let data = serde_json::to_vec_pretty(manifest).map_err(|e| e.to_string())?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(manifest.created_at);
+    header.set_cksum();
+    tar.append_data(&mut header, "manifest.json", data.as_slice())
+        .map_err(archive_error)?;
+
+    tar.into_inner()
+        .and_then(|gz| gz.finish())
+        .and_then(|file| file.sync_all())
+        .map_err(archive_error)
+}
+
+/// Regular files under `root` with `/`-separated relative paths. Skips
+/// symlinks and installed community-node packages (reinstallable, large).
+fn walk_files(root: &Path) -> Vec<(PathBuf, String)> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(kind) = entry.file_type() else {
+                continue;
+            };
+            if kind.is_dir() {
+                if entry.file_name() != "node_modules" {
+                    pending.push(path);
+                }
+            } else if kind.is_file() {
+                if let Ok(rel) = path.strip_prefix(root) {
+                    let rel: Vec<String> = rel
+                        .components()
+                        .map(|c| c.as_os_str().to_string_lossy().to_string())
+                        .collect();
+                    files.push((path.clone(), rel.join("/")));
+                }
+            }
+        }
+    }
+    files.sort_by(|a, b| a.1.cmp(&b.1));
+    files
+}
+
+// === Listing and retention ===
+
+/// All backups, newest first.
+pub fn list_backups(app: &AppHandle) -> Result<Vec<BackupManifest>, String> {
+    let dir = backups_dir(app)?;
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+
+    let mut backups: Vec<BackupManifest> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|p| fs::read_to_string(p).ok())
+        .filter_map(|data| serde_json::from_str::<BackupManifest>(&data).ok())
+        .filter(|m| dir.join(&m.archive).exists())
+        .collect();
+    backups.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+    Ok(backups)
+}
+
+/// Keep the newest `n8n_backup_keep` backups and drop any older than
+/// `n8n_backup_max_age_days`, but never the newest one.
+fn apply_retention(app: &AppHandle, cfg: &AppConfig, dir: &Path) {
+    let Ok(backups) = list_backups(app) else {
+        return;
+    };
+    let keep = cfg.n8n_backup_keep.unwrap_or(DEFAULT_KEEP).max(1) as usize;
+    let cutoff = cfg
+        .n8n_backup_max_age_days
+        .map(|days| now().saturating_sub(days as u64 * 86_400));
+
+    for backup in expired(&backups, keep, cutoff) {
+        let _ = fs::remove_file(dir.join(&backup.archive));
+        let _ = fs::remove_file(dir.join(format!("{}.json", backup.id)));
+        log(app, &format!("🧹 Removed old backup '{}'.", backup.id));
+    }
+}
+
+/// Backups (newest first) that fall outside the retention policy. Nothing
+/// expires right after a pre-restore backup: the backup about to be restored
+/// may well be the oldest one, so pruning waits for the next regular backup.
+fn expired(backups: &[BackupManifest], keep: usize, cutoff: Option<u64>) -> Vec<&BackupManifest> {
+    if backups.first().is_some_and(|b| b.reason == BackupReason::PreRestore) {
+        return Vec::new();
+    }
+    backups
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(i, backup)| *i >= keep || cutoff.is_some_and(|c| backup.created_at < c))
+        .map(|(_, backup)| backup)
+        .collect()
+}
+
+// === Restore ===
+
+/// Restore a backup. The current data is backed up first (`pre_restore`),
+/// so a restore can itself be undone.
+///
+/// A snapshot replaces the whole n8n folder. A CLI backup is imported on top
+/// of the current data: items it contains are reset to their backed-up
+/// state, while workflows and credentials created since are kept.
+pub fn restore_backup(app: &AppHandle, id: &str) -> Result<BackupManifest, String> {
+    let cfg = AppConfig::load()?;
+    let dir = backups_dir(app)?;
+    let manifest = list_backups(app)?
+        .into_iter()
+        .find(|m| m.id == id)
+        .ok_or_else(|| format!("No backup named '{}'", id))?;
+
+    let archive_path = dir.join(&manifest.archive);
+    if let Some(expected) = &manifest.archive_sha256 {
+        let actual = sha256_file(&archive_path)?;
+        if !actual.eq_ignore_ascii_case(expected) {
+            return Err(format!("Backup '{}' is corrupt (archive checksum mismatch)", id));
+        }
+    }
+    let is_postgres = matches!(cfg.n8n.database, N8nDatabase::Postgres(_));
+    if manifest.method == BackupMethod::Snapshot && is_postgres {
+        return Err(format!(
+            "'{}' is a SQLite folder snapshot but n8n now uses PostgreSQL; restore a CLI backup instead.",
+            id
+        ));
+    }
+    if manifest.method == BackupMethod::Cli {
+        let current = key_fingerprint(&cfg);
+        check_key(&manifest, current.as_deref())?;
+        if current.is_none() && manifest.key_fingerprint.is_some() {
+            log(
+                app,
+                "⚠ The current n8n encryption key is unknown; restored credentials only work if it matches the backup's key.",
+            );
+        }
+    }
+
+    log(app, &format!("⏪ Restoring n8n backup '{}'...", id));
+    let safety = if has_data(&cfg) {
+        let safety = create_backup(app, None, BackupReason::PreRestore)
+            .map_err(|e| format!("Could not back up the current data before restoring: {}", e))?;
+        log(app, &format!("🛟 Current data saved as '{}'.", safety.id));
+        Some(safety.id)
+    } else {
+        log(
+            app,
+            &format!(
+                "ℹ No n8n data folder at '{}'; skipping the safety backup.",
+                cfg.n8n.data_dir().display()
+            ),
+        );
+        None
+    };
+
+    let staging = dir.join(format!(".restore-{}", id));
+    let _ = fs::remove_dir_all(&staging);
+    let was_running = supervisor::stop(app, ServiceKind::N8n);
+
+    let result = extract_archive(&archive_path, &staging)
+        .and_then(|_| verify_files(&staging, &manifest))
+        .and_then(|_| match manifest.method {
+            BackupMethod::Cli => import_data(&cfg, &staging),
+            BackupMethod::Snapshot => replace_data_dir(&cfg, &staging.join(SNAPSHOT_ROOT)),
+        });
+    let _ = fs::remove_dir_all(&staging);
+
+    if was_running {
+        if let Err(e) = start_n8n(app) {
+            log(app, &format!("⚠ n8n could not be restarted: {}", e));
+        }
+    }
+
+    match result {
+        Ok(()) => {
+            log(app, &format!("✅ Restored backup '{}'.", id));
+            Ok(manifest)
+        }
+        Err(e) => {
+            let message = match &safety {
+                Some(safety) => format!("❌ Restore failed: {} (your previous data is in backup '{}')", e, safety),
+                None => format!("❌ Restore failed: {}", e),
+            };
+            log(app, &message);
+            Err(e)
+        }
+    }
+}
+
+/// Whether there is current data a restore would overwrite. Not the case on a
+/// fresh machine or after a cleanup removed the SQLite data folder; a
+/// PostgreSQL database is always assumed to hold data.
+fn has_data(cfg: &AppConfig) -> bool {
+    matches!(cfg.n8n.database, N8nDatabase::Postgres(_)) || cfg.n8n.data_dir().is_dir()
+}
+
+/// Refuse to import credentials encrypted with a different key than the one
+/// this instance uses (`current`); n8n could not decrypt them afterwards.
+fn check_key(manifest: &BackupManifest, current: Option<&str>) -> Result<(), String> {
+    match (manifest.key_fingerprint.as_deref(), current) {
+        (Some(backup), Some(current)) if backup != current => Err(format!(
+            "Backup '{}' was made with a different n8n encryption key, so its credentials could not be decrypted. \
+             Set n8n.encryption_key to the original key before restoring.",
+            manifest.id
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn verify_files(staging: &Path, manifest: &BackupManifest) -> Result<(), String> {
+    for file in &manifest.files {
+        let path = staging.join(&file.path);
+        let actual = sha256_file(&path)?;
+        if !actual.eq_ignore_ascii_case(&file.sha256) {
+            return Err(format!("'{}' in backup '{}' is corrupt", file.path, manifest.id));
+        }
+    }
+    Ok(())
+}
+
+/// Swap the `.n8n` folder for the snapshot; the old one is renamed aside.
+fn replace_data_dir(cfg: &AppConfig, snapshot: &Path) -> Result<(), String> {
+    let data_dir = cfg.n8n.data_dir();
+    if data_dir.exists() {
+        let aside = data_dir.with_file_name(format!(".n8n.before-restore-{}", utc_stamp(now())));
+        fs::rename(&data_dir, &aside).map_err(|e| {
+            format!("Failed to move '{}' aside: {}", data_dir.display(), e)
+        })?;
+    }
+
+    for (abs, rel) in walk_files(snapshot) {
+        let dest = data_dir.join(&rel);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create '{}': {}", parent.display(), e))?;
+        }
+        fs::copy(&abs, &dest).map_err(|e| format!("Failed to restore '{}': {}", rel, e))?;
+    }
+    Ok(())
+}
+
+// === Upgrade hook ===
+
+/// Back up before n8n is installed over existing data or upgraded. Returns
+/// `None` when there is nothing to protect yet.
+pub(crate) fn backup_before_upgrade(app: &AppHandle) -> Result<Option<BackupManifest>, String> {
+    let cfg = AppConfig::load()?;
+    let cli_available = installed_n8n(managed_runtime().as_ref()).is_some();
+    let has_sqlite_data = matches!(cfg.n8n.database, N8nDatabase::Sqlite) && cfg.n8n.data_dir().is_dir();
+
+    if !cli_available && !has_sqlite_data {
+        return Ok(None);
+    }
+    create_backup(app, None, BackupReason::PreUpgrade).map(Some)
+}
+
+// === Commands ===
+
+/// 💾 Back up n8n workflows and credentials (method: `cli` or `snapshot`)
+#[tauri::command]
+pub async fn backup_n8n(
+    app: AppHandle,
+    method: Option<BackupMethod>,
+) -> Result<BackupManifest, String> {
+    tauri::async_runtime::spawn_blocking(move || create_backup(&app, method, BackupReason::Manual))
+        .await
+        .map_err(|e| format!("Backup task failed: {}", e))?
+}
+
+/// 📋 List n8n backups, newest first
+#[tauri::command]
+pub fn list_n8n_backups(app: AppHandle) -> Result<Vec<BackupManifest>, String> {
+    list_backups(&app)
+}
+
+/// ⏪ Restore an n8n backup, by ID or as of a point in time (Unix seconds):
+/// `at` picks the newest backup taken at or before that moment. Restoring a
+/// CLI backup keeps items created after it.
+#[tauri::command]
+pub async fn restore_n8n_backup(
+    app: AppHandle,
+    backup_id: Option<String>,
+    at: Option<u64>,
+) -> Result<BackupManifest, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let id = match (backup_id, at) {
+            (Some(id), _) => id,
+            (None, Some(at)) => list_backups(&app)?
+                .into_iter()
+                .find(|m| m.created_at <= at)
+                .map(|m| m.id)
+                .ok_or_else(|| format!("No backup was taken at or before {}", utc_stamp(at)))?,
+            (None, None) => return Err("Choose a backup or a point in time to restore.".into()),
+        };
+        restore_backup(&app, &id)
+    })
+    .await
+    .map_err(|e| format!("Restore task failed: {}", e))?
+}
+
+// === Helpers ===
+
+/// `n8n-20261017-153012Z-manual`, with a counter if that name is taken.
+fn unique_id(dir: &Path, created_at: u64, reason: BackupReason) -> String {
+    let base = format!("n8n-{}-{}", utc_stamp(created_at), reason.slug());
+    let mut id = base.clone();
+    let mut n = 1;
+    while dir.join(format!("{}.tar.gz", id)).exists() {
+        n += 1;
+        id = format!("{}-{}", base, n);
+    }
+    id
+}
+
+/// `YYYYMMDD-HHMMSSZ` in UTC.
+pub(crate) fn utc_stamp(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60
+    )
+}
+
+/// Short SHA-256 fingerprint of the encryption key n8n will use:
+/// `n8n.encryption_key` when set (it overrides the file), otherwise the
+/// `encryptionKey` in `.n8n/config`. `None` before n8n has generated one.
+fn key_fingerprint(cfg: &AppConfig) -> Option<String> {
+    let key = match cfg.n8n.encryption_key.as_deref().filter(|k| !k.is_empty()) {
+        Some(key) => key.to_string(),
+        None => {
+            let data = fs::read_to_string(cfg.n8n.data_dir().join("config")).ok()?;
+            let doc: serde_json::Value = serde_json::from_str(&data).ok()?;
+            doc.get("encryptionKey")?.as_str().filter(|k| !k.is_empty())?.to_string()
+        }
+    };
+    Some(hex::encode(&Sha256::digest(key.as_bytes())[..8]))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn write_json(path: &Path, manifest: &BackupManifest) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(manifest).map_err(|e| e.to_string())?;
+    fs::write(path, data).map_err(|e| format!("Failed to write '{}': {}", path.display(), e))
+}
+
+fn log(app: &AppHandle, message: &str) {
+    app.emit(
+        "component-log",
+        ComponentLog {
+            component: "n8n Backups".into(),
+            message: message.into(),
+        },
+    )
+    .ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn utc_stamp_formats_civil_time() {
+        assert_eq!(utc_stamp(0), "19700101-000000Z");
+        assert_eq!(utc_stamp(951_782_400), "20000229-000000Z");
+        assert_eq!(utc_stamp(1_700_000_000), "20231114-221320Z");
+        assert_eq!(utc_stamp(4_102_444_799), "20991231-235959Z");
+    }
+
+    fn backup(id: &str, created_at: u64, reason: BackupReason) -> BackupManifest {
+        BackupManifest {
+            format: MANIFEST_FORMAT,
+            id: id.into(),
+            created_at,
+            method: BackupMethod::Cli,
+            reason,
+            n8n_version: None,
+            database: "sqlite".into(),
+            workflows: Some(0),
+            credentials: Some(0),
+            key_fingerprint: None,
+            files: Vec::new(),
+            archive: format!("{}.tar.gz", id),
+            archive_sha256: None,
+        }
+    }
+
+    fn ids(backups: Vec<&BackupManifest>) -> Vec<&str> {
+        backups.iter().map(|b| b.id.as_str()).collect()
+    }
+
+    #[test]
+    fn retention_keeps_the_newest_and_drops_old_or_surplus_backups() {
+        let backups = [
+            backup("d", 400, BackupReason::Manual),
+            backup("c", 300, BackupReason::PreUpgrade),
+            backup("b", 200, BackupReason::Manual),
+            backup("a", 100, BackupReason::Manual),
+        ];
+        assert_eq!(ids(expired(&backups, 2, None)), ["b", "a"]);
+        assert_eq!(ids(expired(&backups, 10, Some(250))), ["b", "a"]);
+        // The newest backup survives even when it is past the cutoff
+        assert_eq!(ids(expired(&backups, 10, Some(1_000))), ["c", "b", "a"]);
+    }
+
+    #[test]
+    fn a_pre_restore_backup_prunes_nothing() {
+        // Restoring "a" at the keep limit must not delete "a" first
+        let backups = [
+            backup("safety", 300, BackupReason::PreRestore),
+            backup("b", 200, BackupReason::Manual),
+            backup("a", 100, BackupReason::Manual),
+        ];
+        assert!(expired(&backups, 2, Some(1_000)).is_empty());
+    }
+
+    fn config_with_key_file(folder: &Path, key: &str) -> AppConfig {
+        fs::create_dir_all(folder.join(".n8n")).unwrap();
+        fs::write(
+            folder.join(".n8n").join("config"),
+            format!(r#"{{"encryptionKey": "{}"}}"#, key),
+        )
+        .unwrap();
+        let mut cfg = AppConfig::defaults();
+        cfg.n8n.user_folder = Some(folder.to_string_lossy().to_string());
+        cfg
+    }
+
+    #[test]
+    fn key_fingerprint_prefers_the_configured_key() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut cfg = config_with_key_file(tmp.path(), "key-from-file");
+        let from_file = key_fingerprint(&cfg).unwrap();
+        assert_eq!(from_file.len(), 16);
+        assert!(!from_file.contains("key"));
+
+        cfg.n8n.encryption_key = Some("key-from-settings".into());
+        let from_settings = key_fingerprint(&cfg).unwrap();
+        assert_ne!(from_file, from_settings);
+
+        cfg.n8n.encryption_key = None;
+        cfg.n8n.user_folder = Some(tmp.path().join("empty").to_string_lossy().to_string());
+        assert_eq!(key_fingerprint(&cfg), None);
+    }
+
+    #[test]
+    fn restore_is_refused_only_for_a_different_key() {
+        let mut manifest = backup("a", 100, BackupReason::Manual);
+        manifest.key_fingerprint = Some("0123456789abcdef".into());
+
+        assert!(check_key(&manifest, Some("0123456789abcdef")).is_ok());
+        assert!(check_key(&manifest, None).is_ok());
+        let err = check_key(&manifest, Some("fedcba9876543210")).unwrap_err();
+        assert!(err.contains("different n8n encryption key"), "{}", err);
+
+        manifest.key_fingerprint = None;
+        assert!(check_key(&manifest, Some("fedcba9876543210")).is_ok());
+    }
+
+    #[test]
+    fn safety_backup_needs_existing_data() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut cfg = AppConfig::defaults();
+        cfg.n8n.user_folder = Some(tmp.path().to_string_lossy().to_string());
+        assert!(!has_data(&cfg));
+
+        cfg.n8n.database = N8nDatabase::Postgres(Default::default());
+        assert!(has_data(&cfg));
+
+        let cfg = config_with_key_file(tmp.path(), "k");
+        assert!(has_data(&cfg));
+    }
+}
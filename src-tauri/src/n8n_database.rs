@@ -0,0 +1,487 @@
+// 🧩 n8n database backend — PostgreSQL checks and SQLite → PostgreSQL migration
+//
+// n8n defaults to a SQLite file in its user folder. When `n8n.database` is set
+// to PostgreSQL we verify the server before every launch (reachable, login
+// works, schema exists and is writable), so a bad setting fails with a clear
+// message instead of a crash loop. With SSL enabled the check connects over
+// TLS and verifies the server certificate, as n8n does by default.
+//
+// Migration uses n8n's own CLI: export workflows and credentials from SQLite,
+// import them into PostgreSQL, then switch the config over (see `n8n_backup`
+// for the export format). Credentials stay encrypted with the instance's key
+// throughout; the export folder is kept as a backup.
+
+use postgres::config::SslMode;
+use postgres::{Client, Config, NoTls};
+use rustls::{ClientConfig, RootCertStore};
+use serde::Serialize;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio_postgres_rustls::MakeRustlsConnect;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::config::n8n::{N8nDatabase, PostgresSettings};
+use crate::config::AppConfig;
+use crate::n8n_backup;
+use crate::n8n_manager::start_n8n;
+use crate::supervisor::{self, ServiceKind};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Serialize, Clone)]
+struct ComponentLog {
+    component: String,
+    message: String,
+}
+
+/// Result of a successful connectivity check.
+#[derive(Serialize, Debug, Clone)]
+pub struct PostgresCheck {
+    pub host: String,
+    pub port: u16,
+    pub database: String,
+    pub schema: String,
+    pub server_version: String,
+    pub tls: bool,
+    pub latency_ms: u64,
+}
+
+impl PostgresCheck {
+    pub fn summary(&self) -> String {
+        format!(
+            "PostgreSQL {} reachable at {}:{}{} (database '{}', schema '{}', {} ms)",
+            self.server_version,
+            self.host,
+            self.port,
+            if self.tls { " over TLS" } else { "" },
+            self.database,
+            self.schema,
+            self.latency_ms
+        )
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct MigrationReport {
+    /// Exported JSON files, kept as a backup of the SQLite data.
+    pub export_dir: String,
+    pub workflows: usize,
+    pub credentials: usize,
+    /// Rows found in PostgreSQL afterwards (`None` if it could not be counted).
+    pub workflows_in_postgres: Option<i64>,
+    pub credentials_in_postgres: Option<i64>,
+    pub restarted: bool,
+}
+
+/// Verify that n8n will be able to use this PostgreSQL database.
+pub fn check_postgres(pg: &PostgresSettings) -> Result<PostgresCheck, String> {
+    let started = Instant::now();
+    let mut client = connect(pg)?;
+    let query_error = |e: postgres::Error| format!("PostgreSQL check failed: {}", describe(&e));
+
+    let version: String = client
+        .query_one("SHOW server_version", &[])
+        .map_err(query_error)?
+        .get(0);
+
+    let schema_exists: bool = client
+        .query_one(
+            "SELECT EXISTS (SELECT 1 FROM pg_namespace WHERE nspname = $1)",
+            &[&pg.schema],
+        )
+        .map_err(query_error)?
+        .get(0);
+    if !schema_exists {
+        return Err(format!(
+            "Schema '{}' does not exist in database '{}'. Create it with: CREATE SCHEMA \"{}\";",
+            pg.schema, pg.database, pg.schema
+        ));
+    }
+
+    // n8n creates and migrates its own tables on first start
+    let can_create: bool = client
+        .query_one(
+            "SELECT has_schema_privilege(current_user, $1, 'CREATE')",
+            &[&pg.schema],
+        )
+        .map_err(query_error)?
+        .get(0);
+    if !can_create {
+        return Err(format!(
+            "User '{}' cannot create tables in schema '{}'. Grant it with: GRANT CREATE ON SCHEMA \"{}\" TO \"{}\";",
+            pg.user, pg.schema, pg.schema, pg.user
+        ));
+    }
+
+    Ok(PostgresCheck {
+        host: pg.host.clone(),
+        port: pg.port,
+        database: pg.database.clone(),
+        schema: pg.schema.clone(),
+        server_version: version,
+        tls: pg.ssl,
+        latency_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+fn connect(pg: &PostgresSettings) -> Result<Client, String> {
+    let mut config = Config::new();
+    config
+        .host(&pg.host)
+        .port(pg.port)
+        .dbname(&pg.database)
+        .user(&pg.user)
+        .password(&pg.password)
+        .application_name("gignaati-workbench")
+        .connect_timeout(CONNECT_TIMEOUT);
+
+    let connected = if pg.ssl {
+        config.ssl_mode(SslMode::Require).connect(tls_connector()?)
+    } else {
+        config.connect(NoTls)
+    };
+    connected.map_err(|e| {
+        format!(
+            "Cannot connect to PostgreSQL at {}:{} as '{}': {}",
+            pg.host,
+            pg.port,
+            pg.user,
+            describe(&e)
+        )
+    })
+}
+
+/// Server-side message when there is one ("password authentication failed
+/// for user ..."), otherwise the client error.
+fn describe(e: &postgres::Error) -> String {
+    match e.as_db_error() {
+        Some(db) => db.message().to_string(),
+        None => match std::error::Error::source(e) {
+            Some(cause) => format!("{} ({})", e, cause),
+            None => e.to_string(),
+        },
+    }
+}
+
+/// TLS that verifies the server certificate against the Mozilla root store
+/// (the same CAs Node.js, and so n8n, trusts by default).
+fn tls_connector() -> Result<MakeRustlsConnect, String> {
+    let roots = RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    let config = ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(|e| format!("TLS setup failed: {}", e))?
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    Ok(MakeRustlsConnect::new(config))
+}
+
+/// 🔍 Check PostgreSQL connectivity for n8n (given settings, or the configured ones)
+#[tauri::command]
+pub fn check_n8n_database(settings: Option<PostgresSettings>) -> Result<PostgresCheck, String> {
+    let pg = match settings {
+        Some(pg) => pg,
+        None => match AppConfig::load()?.n8n.database {
+            N8nDatabase::Postgres(pg) => pg,
+            N8nDatabase::Sqlite => {
+                return Err("n8n is using its built-in SQLite database; no PostgreSQL settings to check.".into())
+            }
+        },
+    };
+
+    let issues = pg.validate("database");
+    if !issues.is_empty() {
+        let problems: Vec<String> = issues.into_iter().map(|i| i.message).collect();
+        return Err(problems.join("; "));
+    }
+    check_postgres(&pg)
+}
+
+/// 🗄 Move n8n from SQLite to PostgreSQL.
+///
+/// Stops n8n, exports workflows and credentials with the n8n CLI, imports
+/// them into `settings`, verifies the row counts and switches the config.
+/// n8n is restarted if it was running. On failure the config is unchanged
+/// and n8n keeps using SQLite.
+#[tauri::command]
+pub async fn migrate_n8n_to_postgres(
+    app: AppHandle,
+    settings: PostgresSettings,
+) -> Result<MigrationReport, String> {
+    tauri::async_runtime::spawn_blocking(move || migrate_to_postgres(&app, settings))
+        .await
+        .map_err(|e| format!("Migration task failed: {}", e))?
+}
+
+fn migrate_to_postgres(app: &AppHandle, target: PostgresSettings) -> Result<MigrationReport, String> {
+    let cfg = AppConfig::load()?;
+    if matches!(cfg.n8n.database, N8nDatabase::Postgres(_)) {
+        return Err("n8n already uses PostgreSQL; nothing to migrate.".into());
+    }
+    let issues = target.validate("database");
+    if !issues.is_empty() {
+        let problems: Vec<String> = issues.into_iter().map(|i| i.message).collect();
+        return Err(problems.join("; "));
+    }
+
+    log(app, "🔍 Checking PostgreSQL before migrating...");
+    let check = check_postgres(&target)?;
+    log(app, &format!("✅ {}", check.summary()));
+
+    let export_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join("n8n-migrations")
+        .join(timestamp().to_string());
+
+    // SQLite must not change underneath the export
+    let was_running = supervisor::stop(app, ServiceKind::N8n);
+    if was_running {
+        log(app, "🛑 Stopped n8n for the migration.");
+    }
+
+    let outcome = export_and_import(app, &cfg, &target, &export_dir).and_then(|mut report| {
+        AppConfig::modify(|c| c.n8n.database = N8nDatabase::Postgres(target.clone()))?;
+        log(app, "✅ n8n is now configured to use PostgreSQL.");
+
+        if was_running {
+            start_n8n(app)?;
+            report.restarted = true;
+        }
+        Ok(report)
+    });
+
+    match outcome {
+        Ok(report) => Ok(report),
+        Err(e) => {
+            log(app, &format!("❌ Migration failed: {}", e));
+            // Config still points at SQLite (or was never switched); bring n8n back as it was
+            if was_running && supervisor::status(ServiceKind::N8n).and_then(|s| s.pid).is_none() {
+                if let Err(restart) = start_n8n(app) {
+                    log(app, &format!("⚠ n8n could not be restarted: {}", restart));
+                }
+            }
+            Err(e)
+        }
+    }
+}
+
+fn export_and_import(
+    app: &AppHandle,
+    cfg: &AppConfig,
+    target: &PostgresSettings,
+    export_dir: &Path,
+) -> Result<MigrationReport, String> {
+    let mut sqlite = cfg.clone();
+    sqlite.n8n.database = N8nDatabase::Sqlite;
+    let mut postgres = cfg.clone();
+    postgres.n8n.database = N8nDatabase::Postgres(target.clone());
+
+    log(app, &format!("📤 Exporting from SQLite to {}...", export_dir.display()));
+    let (workflows, credentials) = n8n_backup::export_data(&sqlite, export_dir)?;
+    log(
+        app,
+        &format!("📦 Exported {} workflow(s) and {} credential(s).", workflows, credentials),
+    );
+
+    // Running any n8n command against PostgreSQL also creates the schema
+    log(app, "📥 Importing into PostgreSQL...");
+    n8n_backup::import_data(&postgres, export_dir)?;
+
+    let prefix = cfg
+        .n8n
+        .extra_env
+        .get("DB_TABLE_PREFIX")
+        .map(String::as_str)
+        .unwrap_or("");
+    let (workflows_in_postgres, credentials_in_postgres) =
+        verify_import(target, prefix, workflows, credentials, export_dir)?;
+
+    log(
+        app,
+        &format!(
+            "✅ Imported {} workflow(s) and {} credential(s) into PostgreSQL.",
+            workflows, credentials
+        ),
+    );
+    Ok(MigrationReport {
+        export_dir: export_dir.to_string_lossy().to_string(),
+        workflows,
+        credentials,
+        workflows_in_postgres,
+        credentials_in_postgres,
+        restarted: false,
+    })
+}
+
+/// Count the imported rows and fail if fewer arrived than were exported.
+fn verify_import(
+    target: &PostgresSettings,
+    prefix: &str,
+    workflows: usize,
+    credentials: usize,
+    export_dir: &Path,
+) -> Result<(Option<i64>, Option<i64>), String> {
+    let mut client = connect(target)?;
+    let in_workflows = count_rows(&mut client, target, prefix, "workflow_entity");
+    let in_credentials = count_rows(&mut client, target, prefix, "credentials_entity");
+
+    for (what, exported, imported) in [
+        ("workflows", workflows, in_workflows),
+        ("credentials", credentials, in_credentials),
+    ] {
+        if let Some(n) = imported {
+            if (n as usize) < exported {
+                return Err(format!(
+                    "Only {} of {} {} arrived in PostgreSQL; the export is kept at '{}'",
+                    n,
+                    exported,
+                    what,
+                    export_dir.display()
+                ));
+            }
+        }
+    }
+    Ok((in_workflows, in_credentials))
+}
+
+fn count_rows(client: &mut Client, pg: &PostgresSettings, prefix: &str, table: &str) -> Option<i64> {
+    let sql = format!(
+        "SELECT count(*) FROM {}.{}",
+        quote_ident(&pg.schema),
+        quote_ident(&format!("{}{}", prefix, table))
+    );
+    client.query_one(sql.as_str(), &[]).ok().map(|row| row.get(0))
+}
+
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn log(app: &AppHandle, message: &str) {
+    app.emit(
+        "component-log",
+        ComponentLog {
+            component: "n8n Database".into(),
+            message: message.into(),
+        },
+    )
+    .ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use postgres::config::Host;
+
+    /// Settings for a disposable server from `GIGNAATI_TEST_POSTGRES`, e.g.
+    /// `host=localhost port=5432 user=n8n password=secret dbname=n8n`.
+    /// Run with `cargo test n8n_database -- --ignored`.
+    fn test_server() -> PostgresSettings {
+        let url = std::env::var("GIGNAATI_TEST_POSTGRES").expect("GIGNAATI_TEST_POSTGRES is not set");
+        let config: Config = url.parse().expect("GIGNAATI_TEST_POSTGRES is not a connection string");
+        let host = match config.get_hosts().first() {
+            Some(Host::Tcp(host)) => host.clone(),
+            _ => "localhost".into(),
+        };
+        PostgresSettings {
+            host,
+            port: config.get_ports().first().copied().unwrap_or(5432),
+            database: config.get_dbname().unwrap_or("postgres").into(),
+            user: config.get_user().unwrap_or("postgres").into(),
+            password: config
+                .get_password()
+                .map(|p| String::from_utf8_lossy(p).to_string())
+                .unwrap_or_default(),
+            schema: "public".into(),
+            ssl: false,
+        }
+    }
+
+    /// A fresh schema per test so they can run in parallel.
+    fn with_schema(name: &str) -> PostgresSettings {
+        let mut pg = test_server();
+        let mut client = connect(&pg).unwrap();
+        client
+            .batch_execute(&format!(
+                "DROP SCHEMA IF EXISTS {0} CASCADE; CREATE SCHEMA {0};",
+                quote_ident(name)
+            ))
+            .unwrap();
+        pg.schema = name.into();
+        pg
+    }
+
+    #[test]
+    #[ignore = "needs GIGNAATI_TEST_POSTGRES"]
+    fn check_reports_the_server_version() {
+        let pg = with_schema("gw_check");
+        let check = check_postgres(&pg).unwrap();
+        assert!(!check.server_version.is_empty());
+        assert!(!check.tls);
+        assert!(check.summary().contains("schema 'gw_check'"), "{}", check.summary());
+    }
+
+    #[test]
+    #[ignore = "needs GIGNAATI_TEST_POSTGRES"]
+    fn check_rejects_a_missing_schema() {
+        let mut pg = test_server();
+        pg.schema = "gw_no_such_schema".into();
+        let err = check_postgres(&pg).unwrap_err();
+        assert!(err.contains("Schema 'gw_no_such_schema' does not exist"), "{}", err);
+    }
+
+    #[test]
+    #[ignore = "needs GIGNAATI_TEST_POSTGRES"]
+    fn check_rejects_a_wrong_password() {
+        let mut pg = test_server();
+        pg.password = format!("{}-wrong", pg.password);
+        let err = check_postgres(&pg).unwrap_err();
+        assert!(err.starts_with("Cannot connect to PostgreSQL"), "{}", err);
+    }
+
+    #[test]
+    #[ignore = "needs GIGNAATI_TEST_POSTGRES"]
+    fn check_with_ssl_really_negotiates_tls() {
+        // The test server has no certificate; a plain-text fallback would pass
+        let mut pg = test_server();
+        pg.ssl = true;
+        let err = check_postgres(&pg).unwrap_err();
+        assert!(err.contains("TLS"), "{}", err);
+    }
+
+    #[test]
+    #[ignore = "needs GIGNAATI_TEST_POSTGRES"]
+    fn verify_import_compares_row_counts() {
+        let pg = with_schema("gw_import");
+        let mut client = connect(&pg).unwrap();
+        client
+            .batch_execute(
+                "CREATE TABLE gw_import.n8n_workflow_entity (id int);
+                 CREATE TABLE gw_import.n8n_credentials_entity (id int);
+                 INSERT INTO gw_import.n8n_workflow_entity VALUES (1), (2);
+                 INSERT INTO gw_import.n8n_credentials_entity VALUES (1);",
+            )
+            .unwrap();
+        let export_dir = Path::new("/tmp/export");
+
+        assert_eq!(
+            verify_import(&pg, "n8n_", 2, 1, export_dir).unwrap(),
+            (Some(2), Some(1))
+        );
+        let err = verify_import(&pg, "n8n_", 3, 1, export_dir).unwrap_err();
+        assert!(err.starts_with("Only 2 of 3 workflows"), "{}", err);
+        // Without the prefix the tables are not found and nothing can be compared
+        assert_eq!(verify_import(&pg, "", 3, 1, export_dir).unwrap(), (None, None));
+    }
+}
@@ -1,14 +1,18 @@
 use tauri::{AppHandle, Emitter, Manager}; // Manager removed — not used in Tauri v2
 use serde::Serialize;
-use std::process::{Command, Stdio};
-use std::io::{BufRead, BufReader};
-use std::thread;
-use std::time::Duration;
-use std::sync::{Arc, Mutex};
-use std::net::TcpStream;
-use once_cell::sync::Lazy;
-
-use crate::config::AppConfig;
+use std::ffi::OsString;
+use std::process::Command;
+
+use crate::config::n8n::{EnvVar, N8nDatabase};
+use crate::config::{AppConfig, ConfigIssue};
+use crate::installer::install_n8n_real::recorded_n8n;
+use crate::installer::node_runtime::managed_runtime;
+use crate::n8n_database;
+use crate::ports::ownership::{resolve_port, PortResolution};
+use crate::ports::registry;
+use crate::readiness::{self, N8N_READY_TIMEOUT};
+use crate::supervisor::{self, ServiceKind, ServiceSpec};
+use crate::version;
 
 #[derive(Serialize, Clone)]
 struct ComponentLog {
@@ -16,193 +20,242 @@ struct ComponentLog {
     message: String,
 }
 
-// Global handle for n8n child process
-static N8N_PROCESS: Lazy<Arc<Mutex<Option<std::process::Child>>>> =
-    Lazy::new(|| Arc::new(Mutex::new(None)));
-
-// Utility: check whether something is listening on the given port (127.0.0.1)
-fn is_listening(port: u16) -> bool {
-    TcpStream::connect(("127.0.0.1", port)).is_ok()
+// The n8n recorded in AppConfig — a Workbench-managed version, or an existing
+// install the user opted into. Returns the program and the arguments that
+// precede an n8n subcommand (`start`, `export:workflow`, ...).
+pub(crate) fn detect_n8n_command() -> Result<(String, Vec<String>), String> {
+    recorded_n8n()
+        .map(|path| (path.to_string_lossy().to_string(), Vec::new()))
+        .ok_or_else(|| {
+            "n8n is not installed. Install it from the Workbench, or choose an existing installation.".to_string()
+        })
 }
 
-// Try to free a port. Windows uses netstat + taskkill; Unix uses lsof + kill.
-fn free_port(port: u16) -> Result<(), String> {
-    #[cfg(target_os = "windows")]
-    {
-        let cmd = format!("netstat -ano | findstr :{}", port);
-        let output = Command::new("cmd")
-            .args(&["/C", &cmd])
-            .output()
-            .map_err(|e| format!("Failed to run netstat: {}", e))?;
-
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        if stdout.trim().is_empty() {
-            return Ok(());
-        }
+/// 🚀 Launch n8n with OLLAMA_API_URL
+#[tauri::command]
+pub async fn launch_n8n_with_ollama(app: AppHandle) -> Result<(), String> {
+    let n8n_port = on_blocking_pool(&app, start_n8n).await?;
 
-        for line in stdout.lines() {
-            if let Some(pid_str) = line.split_whitespace().last() {
-                if let Ok(pid) = pid_str.parse::<u32>() {
-                    let _ = Command::new("taskkill")
-                        .args(&["/F", "/PID", &pid.to_string()])
-                        .output()
-                        .map_err(|e| format!("Failed taskkill: {}", e))?;
-                }
-            }
-        }
-        Ok(())
+    // 🌐 Open in main Tauri window
+    if let Some(main_window) = app.webview_windows().get("main") {
+        let url = format!("http://127.0.0.1:{}", n8n_port);
+        let _ = main_window.eval(&format!("window.location.href = '{}';", url));
     }
 
-    #[cfg(not(target_os = "windows"))]
-    {
-        let output = Command::new("sh")
-            .arg("-c")
-            .arg(format!("lsof -t -i :{}", port))
-            .output()
-            .map_err(|e| format!("Failed to run lsof: {}", e))?;
-
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        if stdout.trim().is_empty() {
-            return Ok(());
-        }
-
-        for pid_line in stdout.lines() {
-            if let Ok(pid) = pid_line.trim().parse::<i32>() {
-                let _ = Command::new("kill")
-                    .arg("-9")
-                    .arg(pid.to_string())
-                    .output()
-                    .map_err(|e| format!("Failed to kill PID {}: {}", pid, e))?;
-            }
-        }
-        Ok(())
-    }
+    Ok(())
 }
 
-// Try locating possible n8n executable paths or fallback to `npx n8n`
-fn detect_n8n_command() -> (String, Vec<String>) {
-    #[cfg(target_os = "windows")]
-    {
-        let default_path = r"C:\Users\Nilkhil\AppData\Roaming\npm\n8n.cmd";
-        if std::path::Path::new(default_path).exists() {
-            return (default_path.to_string(), vec!["start".to_string()]);
-        }
-    }
-
-    if which::which("n8n").is_ok() {
-        return ("n8n".to_string(), vec!["start".to_string()]);
-    }
+/// Run a blocking launch step off the async runtime.
+async fn on_blocking_pool(
+    app: &AppHandle,
+    step: fn(&AppHandle) -> Result<u16, String>,
+) -> Result<u16, String> {
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || step(&app))
+        .await
+        .map_err(|e| format!("❌ Failed to launch n8n: {}", e))?
+}
 
-    if which::which("npx").is_ok() {
-        return (
-            "npx".to_string(),
-            vec!["--yes".to_string(), "n8n".to_string(), "start".to_string()],
-        );
+/// Port of a healthy n8n: the configured one if it answers, otherwise
+/// whatever port a fresh start lands on.
+fn ensure_n8n(app: &AppHandle) -> Result<u16, String> {
+    let configured = AppConfig::load_or_default().n8n_port.unwrap_or(5678);
+    if readiness::probe(ServiceKind::N8n, configured).is_ok() {
+        return Ok(configured);
     }
-
-    ("npx".to_string(), vec!["n8n".to_string(), "start".to_string()])
+    start_n8n(app)
 }
 
-/// 🚀 Launch n8n with OLLAMA_API_URL
-#[tauri::command]
-pub fn launch_n8n_with_ollama(app: AppHandle) -> Result<(), String> {
+/// Start n8n under the supervisor and block until /healthz answers.
+/// Returns the port it is serving on.
+pub(crate) fn start_n8n(app: &AppHandle) -> Result<u16, String> {
     let component = "Agentic Platform (n8n)";
     app.emit("component-log", ComponentLog {
         component: component.into(),
         message: "🚀 Launching n8n with Ollama binding...".into(),
     }).ok();
 
-    let cfg = AppConfig::load();
-    let ollama_port = cfg.ollama_port.unwrap_or(11434);
-    let n8n_port = cfg.n8n_port.unwrap_or(5678);
+    let cfg = AppConfig::load_or_default();
+    let preferred_port = registry::release_for_launch(registry::N8N)?;
+
+    // 🔌 Reuse a running n8n, or move to a free port — never kill the holder
+    let n8n_port = match resolve_port(ServiceKind::N8n, preferred_port)? {
+        PortResolution::Free(port) => port,
+        PortResolution::Reuse { port, holder } => {
+            app.emit("component-log", ComponentLog {
+                component: component.into(),
+                message: format!(
+                    "✅ n8n already running on port {}{}.",
+                    port,
+                    holder.map(|h| format!(" as {}", h.describe())).unwrap_or_default()
+                ),
+            }).ok();
+            return Ok(port);
+        }
+        PortResolution::Relocated { from, to, holder } => {
+            let who = holder
+                .map(|h| h.describe())
+                .unwrap_or_else(|| "another process".into());
+            app.emit("component-log", ComponentLog {
+                component: component.into(),
+                message: format!("⚠ Port {} is held by {}; using port {} instead.", from, who, to),
+            }).ok();
+            to
+        }
+    };
 
-    let _ = free_port(n8n_port);
+    // 🗄 An unreachable PostgreSQL backend would only surface as a crash loop
+    if let N8nDatabase::Postgres(pg) = &cfg.n8n.database {
+        let check = n8n_database::check_postgres(pg)?;
+        app.emit("component-log", ComponentLog {
+            component: component.into(),
+            message: format!("✅ {}", check.summary()),
+        }).ok();
+    }
 
-    let (bin, mut base_args) = detect_n8n_command();
+    let (bin, mut base_args) = detect_n8n_command()?;
+    base_args.push("start".to_string());
     base_args.push("--port".to_string());
     base_args.push(n8n_port.to_string());
 
-    let mut cmd = Command::new(&bin);
-    for a in &base_args {
-        cmd.arg(a);
-    }
+    // Task-runner broker port comes from the registry too
+    let runner_port = registry::release_for_launch(registry::N8N_TASK_RUNNER).ok();
+
+    let env = n8n_process_env(&cfg, runner_port);
+
+    let pid = supervisor::start(
+        app,
+        ServiceSpec {
+            kind: ServiceKind::N8n,
+            program: bin,
+            args: base_args,
+            env,
+            port: n8n_port,
+        },
+    )
+    .map_err(|e| format!("❌ {}", e))?;
+
+    // ⏳ Wait for /healthz rather than a fixed delay
+    app.emit("component-log", ComponentLog {
+        component: component.into(),
+        message: format!("⏳ Waiting for n8n (PID {}) to become ready on port {}...", pid, n8n_port),
+    }).ok();
 
-    cmd.env("OLLAMA_API_URL", format!("http://127.0.0.1:{}", ollama_port));
-    cmd.env("DB_SQLITE_POOL_SIZE", "2");
-    cmd.env("N8N_RUNNERS_ENABLED", "true");
-    cmd.env("N8N_BLOCK_ENV_ACCESS_IN_NODE", "false");
-    cmd.env("N8N_GIT_NODE_DISABLE_BARE_REPOS", "true");
-    cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+    let waited = readiness::wait_until_ready(ServiceKind::N8n, n8n_port, N8N_READY_TIMEOUT)
+        .map_err(|e| e.to_string())?;
 
-    let mut child = cmd.spawn().map_err(|e| format!("❌ Failed to launch n8n: {}", e))?;
-    let stdout_opt = child.stdout.take();
-    let stderr_opt = child.stderr.take();
+    app.emit("component-log", ComponentLog {
+        component: component.into(),
+        message: format!("✅ n8n launched on port {} (ready in {:.1}s).", n8n_port, waited.as_secs_f32()),
+    }).ok();
+
+    Ok(n8n_port)
+}
 
-    {
-        let mut guard = N8N_PROCESS.lock().unwrap();
-        *guard = Some(child);
+/// Variables set on the n8n process on top of the inherited environment
+/// (PATH for the managed runtime is added at spawn time).
+fn n8n_environment(cfg: &AppConfig, runner_port: Option<u16>) -> Vec<EnvVar> {
+    let ollama_port = cfg.ollama_port.unwrap_or(11434);
+    let mut vars = vec![EnvVar::new(
+        "OLLAMA_API_URL",
+        format!("http://127.0.0.1:{}", ollama_port),
+    )];
+    vars.extend(cfg.n8n.env(version::launched_n8n(cfg).as_ref()));
+    if let Some(port) = runner_port {
+        vars.push(EnvVar::new("N8N_RUNNERS_BROKER_PORT", port.to_string()));
     }
+    vars
+}
+
+/// `n8n_environment` plus PATH for the managed runtime, ready to spawn with.
+pub(crate) fn n8n_process_env(cfg: &AppConfig, runner_port: Option<u16>) -> Vec<(String, OsString)> {
+    let mut env: Vec<(String, OsString)> = n8n_environment(cfg, runner_port)
+        .into_iter()
+        .map(|var| (var.name, var.value.into()))
+        .collect();
 
-    // 🔁 Stream logs
-    if let Some(stdout) = stdout_opt {
-        let app_clone = app.clone();
-        thread::spawn(move || {
-            for line in BufReader::new(stdout).lines().flatten() {
-                let _ = app_clone.emit("component-log", ComponentLog {
-                    component: "Agentic Platform (n8n)".into(),
-                    message: line,
-                });
-            }
-        });
+    // Run under the installer-owned Node.js when one is provisioned
+    if let Some(rt) = managed_runtime() {
+        env.push(("PATH".into(), rt.path_env()));
     }
+    env
+}
 
-    if let Some(stderr) = stderr_opt {
-        let app_clone = app.clone();
-        thread::spawn(move || {
-            for line in BufReader::new(stderr).lines().flatten() {
-                let _ = app_clone.emit("component-log", ComponentLog {
-                    component: "Agentic Platform (n8n)".into(),
-                    message: format!("⚠ {}", line),
-                });
-            }
-        });
+/// Run an n8n CLI command (`export:workflow`, `import:credentials`, ...) with
+/// the same environment the supervised n8n gets. Returns stdout.
+pub(crate) fn run_n8n_cli(cfg: &AppConfig, args: &[&str]) -> Result<String, String> {
+    let (bin, mut full_args) = detect_n8n_command()?;
+    full_args.extend(args.iter().map(|a| a.to_string()));
+
+    let output = Command::new(&bin)
+        .args(&full_args)
+        .envs(n8n_process_env(cfg, None))
+        .output()
+        .map_err(|e| format!("Failed to run n8n {}: {}", args.first().unwrap_or(&""), e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    if output.status.success() {
+        return Ok(stdout);
     }
 
-    // ⏳ Wait a few seconds before opening
-    thread::sleep(Duration::from_secs(3));
-    app.emit("component-log", ComponentLog {
-        component: component.into(),
-        message: format!("✅ n8n launched on port {}.", n8n_port),
-    }).ok();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let tail: Vec<&str> = stderr
+        .lines()
+        .chain(stdout.lines())
+        .filter(|l| !l.trim().is_empty())
+        .collect();
+    Err(format!(
+        "n8n {} failed: {}",
+        args.first().unwrap_or(&""),
+        tail[tail.len().saturating_sub(5)..].join(" | ")
+    ))
+}
 
-    // 🌐 Open in main Tauri window
-    if let Some(main_window) = app.webview_windows().get("main") {
-        let url = format!("http://127.0.0.1:{}", n8n_port);
-        let _ = main_window.eval(&format!("window.location.href = '{}';", url));
+#[derive(Serialize, Clone)]
+pub struct N8nEnvPreview {
+    /// Exactly what the child receives on top of the inherited environment;
+    /// secret values are redacted.
+    pub variables: Vec<EnvVar>,
+    /// Problems with the n8n settings. Rejected `extra_env` / `secrets`
+    /// entries and unsupported basic auth are left out of `variables`; other
+    /// typed settings are passed as given.
+    pub issues: Vec<ConfigIssue>,
+}
+
+/// 👁 Preview the environment n8n will be launched with (secrets redacted).
+/// Reserves no ports and changes nothing.
+#[tauri::command]
+pub fn preview_n8n_env() -> Result<N8nEnvPreview, String> {
+    let cfg = AppConfig::load()?;
+    let runner_port = registry::planned(&cfg, registry::N8N_TASK_RUNNER);
+
+    let mut variables: Vec<EnvVar> = n8n_environment(&cfg, runner_port)
+        .iter()
+        .map(EnvVar::redacted)
+        .collect();
+    if let Some(rt) = managed_runtime() {
+        variables.push(EnvVar::new("PATH", rt.path_env().to_string_lossy()));
     }
 
-    Ok(())
+    Ok(N8nEnvPreview {
+        variables,
+        issues: cfg.n8n.validate(version::launched_n8n(&cfg).as_ref()),
+    })
 }
 
 /// 🛑 Stop n8n process
 #[tauri::command]
 pub fn stop_n8n(app: AppHandle) -> Result<(), String> {
     let component = "Agentic Platform (n8n)";
-    let mut guard = N8N_PROCESS.lock().unwrap();
-
-    if let Some(child) = guard.as_mut() {
-        let _ = child.kill();
-        *guard = None;
-        app.emit("component-log", ComponentLog {
-            component: component.into(),
-            message: "🛑 n8n stopped.".into(),
-        }).ok();
-        return Ok(());
-    }
-
+    // Only report a stop if a live process was actually terminated
+    let message = if supervisor::stop(&app, ServiceKind::N8n) {
+        "🛑 n8n stopped."
+    } else {
+        "ℹ n8n was not running."
+    };
     app.emit("component-log", ComponentLog {
         component: component.into(),
-        message: "ℹ n8n was not running.".into(),
+        message: message.into(),
     }).ok();
     Ok(())
 }
@@ -210,11 +263,11 @@ pub fn stop_n8n(app: AppHandle) -> Result<(), String> {
 /// 🔍 Check n8n health
 #[tauri::command]
 pub fn check_n8n_health(app: AppHandle) -> Result<String, String> {
-    let cfg = AppConfig::load();
+    let cfg = AppConfig::load_or_default();
     let n8n_port = cfg.n8n_port.unwrap_or(5678);
     let addr = format!("127.0.0.1:{}", n8n_port);
 
-    if TcpStream::connect_timeout(&addr.parse().unwrap(), Duration::from_secs(2)).is_ok() {
+    if readiness::probe(ServiceKind::N8n, n8n_port).is_ok() {
         let msg = format!("✅ n8n is reachable at http://{}", addr);
         app.emit("component-log", ComponentLog {
             component: "Agentic Platform (n8n)".into(),
@@ -233,9 +286,9 @@ pub fn check_n8n_health(app: AppHandle) -> Result<String, String> {
 
 /// 🌐 Launch Agentic Platform UI in same window
 #[tauri::command]
-pub fn launch_agentic_platform(app: AppHandle) -> Result<(), String> {
-    let cfg = AppConfig::load();
-    let n8n_port = cfg.n8n_port.unwrap_or(5678);
+pub async fn launch_agentic_platform(app: AppHandle) -> Result<(), String> {
+    // Ensure n8n is running and healthy before pointing the webview at it
+    let n8n_port = on_blocking_pool(&app, ensure_n8n).await?;
     let n8n_url = format!("http://127.0.0.1:{}", n8n_port);
 
     app.emit("component-log", ComponentLog {
@@ -243,12 +296,6 @@ pub fn launch_agentic_platform(app: AppHandle) -> Result<(), String> {
         message: format!("🌐 Launching Agentic Platform at {}", n8n_url),
     }).ok();
 
-    // Ensure n8n is running
-    if !is_listening(n8n_port) {
-        let _ = launch_n8n_with_ollama(app.clone());
-        thread::sleep(Duration::from_secs(3));
-    }
-
     // Open inside main window
     if let Some(main_window) = app.webview_windows().get("main") {
         let _ = main_window.eval(&format!("window.location.href = '{}';", n8n_url));
@@ -0,0 +1,449 @@
+// 🧩 Gignaati Workbench — Ollama REST client
+//
+// Thin, typed wrapper over the Ollama HTTP API (/api/version, /api/tags,
+// /api/show, /api/ps, /api/pull, /api/delete). The base URL comes from the
+// configured `ollama_port`, or can be given explicitly (e.g. a mock server).
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fmt;
+use std::io::{BufRead, BufReader};
+use std::time::Duration;
+
+use crate::config::AppConfig;
+
+// === Errors ===
+
+#[derive(Debug, Clone)]
+pub enum OllamaError {
+    /// Nothing answered at the base URL.
+    Unreachable(String),
+    /// The server answered with a non-2xx status.
+    Http { status: u16, message: String },
+    /// The server reported an error inside a streamed response.
+    Remote(String),
+    /// The response body did not match the expected shape.
+    Decode(String),
+}
+
+impl fmt::Display for OllamaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OllamaError::Unreachable(e) => write!(f, "Ollama server unreachable: {}", e),
+            OllamaError::Http { status, message } => {
+                write!(f, "Ollama returned HTTP {}: {}", status, message)
+            }
+            OllamaError::Remote(e) => write!(f, "Ollama error: {}", e),
+            OllamaError::Decode(e) => write!(f, "Unexpected Ollama response: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for OllamaError {}
+
+impl From<ureq::Error> for OllamaError {
+    fn from(err: ureq::Error) -> Self {
+        match err {
+            ureq::Error::Status(status, response) => {
+                let body = response.into_string().unwrap_or_default();
+                let message = serde_json::from_str::<ErrorBody>(&body)
+                    .map(|b| b.error)
+                    .unwrap_or(body);
+                OllamaError::Http { status, message }
+            }
+            ureq::Error::Transport(t) => OllamaError::Unreachable(t.to_string()),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ErrorBody {
+    error: String,
+}
+
+// === Response types ===
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionResponse {
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelDetails {
+    #[serde(default)]
+    pub parent_model: String,
+    #[serde(default)]
+    pub format: String,
+    #[serde(default)]
+    pub family: String,
+    #[serde(default)]
+    pub families: Option<Vec<String>>,
+    #[serde(default)]
+    pub parameter_size: String,
+    #[serde(default)]
+    pub quantization_level: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagModel {
+    pub name: String,
+    #[serde(default)]
+    pub model: String,
+    #[serde(default)]
+    pub modified_at: String,
+    #[serde(default)]
+    pub size: u64,
+    #[serde(default)]
+    pub digest: String,
+    #[serde(default)]
+    pub details: ModelDetails,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagsResponse {
+    #[serde(default)]
+    pub models: Vec<TagModel>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShowResponse {
+    #[serde(default)]
+    pub modelfile: String,
+    #[serde(default)]
+    pub parameters: String,
+    #[serde(default)]
+    pub template: String,
+    #[serde(default)]
+    pub details: ModelDetails,
+    #[serde(default)]
+    pub model_info: serde_json::Map<String, serde_json::Value>,
+    #[serde(default)]
+    pub modified_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunningModel {
+    pub name: String,
+    #[serde(default)]
+    pub model: String,
+    #[serde(default)]
+    pub size: u64,
+    #[serde(default)]
+    pub digest: String,
+    #[serde(default)]
+    pub details: ModelDetails,
+    #[serde(default)]
+    pub expires_at: String,
+    #[serde(default)]
+    pub size_vram: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PsResponse {
+    #[serde(default)]
+    pub models: Vec<RunningModel>,
+}
+
+/// One line of the NDJSON stream returned by /api/pull.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PullProgress {
+    #[serde(default)]
+    pub status: String,
+    pub digest: Option<String>,
+    pub total: Option<u64>,
+    pub completed: Option<u64>,
+    pub error: Option<String>,
+}
+
+// === Client ===
+
+pub struct OllamaClient {
+    base_url: String,
+    agent: ureq::Agent,
+    stream_agent: ureq::Agent,
+}
+
+impl OllamaClient {
+    /// Client for an explicit base URL such as `http://127.0.0.1:11434`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self::with_timeouts(base_url, Duration::from_secs(30), Duration::from_secs(300))
+    }
+
+    /// `request` bounds a whole non-streaming call; `stream_gap` bounds the
+    /// silence between two chunks of a pull (pulls themselves can run long).
+    fn with_timeouts(base_url: impl Into<String>, request: Duration, stream_gap: Duration) -> Self {
+        OllamaClient {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            agent: ureq::AgentBuilder::new()
+                .timeout_connect(Duration::from_secs(3))
+                .timeout(request)
+                .build(),
+            stream_agent: ureq::AgentBuilder::new()
+                .timeout_connect(Duration::from_secs(3))
+                .timeout_read(stream_gap)
+                .build(),
+        }
+    }
+
+    /// Client for the Ollama port recorded in AppConfig.
+    pub fn from_config() -> Self {
+        let port = AppConfig::load_or_default().ollama_port.unwrap_or(11434);
+        Self::new(format!("http://127.0.0.1:{}", port))
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// GET /api/version
+    pub fn version(&self) -> Result<VersionResponse, OllamaError> {
+        let resp = self.agent.get(&self.url("/api/version")).call()?;
+        resp.into_json().map_err(|e| OllamaError::Decode(e.to_string()))
+    }
+
+    /// GET /api/tags — locally available models.
+    pub fn tags(&self) -> Result<TagsResponse, OllamaError> {
+        let resp = self.agent.get(&self.url("/api/tags")).call()?;
+        resp.into_json().map_err(|e| OllamaError::Decode(e.to_string()))
+    }
+
+    /// POST /api/show — model metadata.
+    pub fn show(&self, model: &str) -> Result<ShowResponse, OllamaError> {
+        let resp = self
+            .agent
+            .post(&self.url("/api/show"))
+            .send_json(json!({ "model": model }))?;
+        resp.into_json().map_err(|e| OllamaError::Decode(e.to_string()))
+    }
+
+    /// GET /api/ps — models currently loaded in memory.
+    #[allow(dead_code)] // part of the client surface; no command uses it yet
+    pub fn ps(&self) -> Result<PsResponse, OllamaError> {
+        let resp = self.agent.get(&self.url("/api/ps")).call()?;
+        resp.into_json().map_err(|e| OllamaError::Decode(e.to_string()))
+    }
+
+    /// DELETE /api/delete
+    pub fn delete(&self, model: &str) -> Result<(), OllamaError> {
+        self.agent
+            .request("DELETE", &self.url("/api/delete"))
+            .send_json(json!({ "model": model }))?;
+        Ok(())
+    }
+
+    /// POST /api/pull, decoding the NDJSON progress stream.
+    ///
+    /// `on_progress` is called for every line; returning `false` stops reading
+    /// and closes the connection.
+    pub fn pull<F>(&self, model: &str, mut on_progress: F) -> Result<PullOutcome, OllamaError>
+    where
+        F: FnMut(&PullProgress) -> bool,
+    {
+        let resp = self
+            .stream_agent
+            .post(&self.url("/api/pull"))
+            .send_json(json!({ "model": model, "stream": true }))?;
+
+        let reader = BufReader::new(resp.into_reader());
+        for line in reader.lines() {
+            let line = line.map_err(|e| OllamaError::Unreachable(e.to_string()))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let progress: PullProgress = serde_json::from_str(&line)
+                .map_err(|e| OllamaError::Decode(format!("{}: {}", e, line)))?;
+
+            if let Some(err) = &progress.error {
+                return Err(OllamaError::Remote(err.clone()));
+            }
+
+            let finished = progress.status == "success";
+            if !on_progress(&progress) {
+                return Ok(PullOutcome::Stopped);
+            }
+            if finished {
+                return Ok(PullOutcome::Completed);
+            }
+        }
+
+        Err(OllamaError::Decode(
+            "pull stream ended without a success status".into(),
+        ))
+    }
+}
+
+/// How a pull stream ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PullOutcome {
+    Completed,
+    /// The caller asked to stop (e.g. cancel).
+    Stopped,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{serve, Reply};
+
+    const PULL_STREAM: &str = concat!(
+        "{\"status\":\"pulling manifest\"}\n",
+        "{\"status\":\"downloading\",\"digest\":\"sha256:ab\",\"total\":100,\"completed\":40}\n",
+        "\n",
+        "{\"status\":\"downloading\",\"digest\":\"sha256:ab\",\"total\":100,\"completed\":100}\n",
+        "{\"status\":\"success\"}\n",
+    );
+
+    fn quick(base: String) -> OllamaClient {
+        OllamaClient::with_timeouts(base, Duration::from_millis(500), Duration::from_millis(300))
+    }
+
+    #[test]
+    fn pull_decodes_every_progress_line() {
+        let base = serve(vec![("/api/pull", Reply::ok(PULL_STREAM))]);
+        let mut seen = Vec::new();
+        let outcome = quick(base)
+            .pull("llama3.2:1b", |p| {
+                seen.push((p.status.clone(), p.completed));
+                true
+            })
+            .unwrap();
+
+        assert_eq!(outcome, PullOutcome::Completed);
+        assert_eq!(
+            seen,
+            vec![
+                ("pulling manifest".to_string(), None),
+                ("downloading".to_string(), Some(40)),
+                ("downloading".to_string(), Some(100)),
+                ("success".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn pull_stops_when_the_callback_says_so() {
+        let base = serve(vec![("/api/pull", Reply::ok(PULL_STREAM))]);
+        let mut calls = 0;
+        let outcome = quick(base)
+            .pull("llama3.2:1b", |_| {
+                calls += 1;
+                calls < 2
+            })
+            .unwrap();
+        assert_eq!(outcome, PullOutcome::Stopped);
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn pull_surfaces_errors_inside_the_stream() {
+        let body = "{\"status\":\"pulling manifest\"}\n{\"error\":\"pull model manifest: file does not exist\"}\n";
+        let base = serve(vec![("/api/pull", Reply::ok(body))]);
+        match quick(base).pull("nope", |_| true) {
+            Err(OllamaError::Remote(e)) => assert!(e.contains("file does not exist")),
+            other => panic!("expected a remote error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn pull_without_success_line_is_an_error() {
+        let base = serve(vec![("/api/pull", Reply::ok("{\"status\":\"pulling manifest\"}\n"))]);
+        assert!(matches!(quick(base).pull("m", |_| true), Err(OllamaError::Decode(_))));
+    }
+
+    #[test]
+    fn pull_times_out_when_the_stream_stalls() {
+        let base = serve(vec![(
+            "/api/pull",
+            Reply::Stall {
+                body: b"{\"status\":\"pulling manifest\"}\n".to_vec(),
+                pause: Duration::from_secs(3),
+            },
+        )]);
+        let mut seen = 0;
+        let result = quick(base).pull("m", |_| {
+            seen += 1;
+            true
+        });
+        assert_eq!(seen, 1);
+        assert!(matches!(result, Err(OllamaError::Unreachable(_))), "{:?}", result.err());
+    }
+
+    #[test]
+    fn http_error_bodies_are_unwrapped() {
+        let base = serve(vec![(
+            "/api/show",
+            Reply::Body(404, b"{\"error\":\"model 'x' not found\"}".to_vec()),
+        )]);
+        match quick(base).show("x") {
+            Err(OllamaError::Http { status, message }) => {
+                assert_eq!(status, 404);
+                assert_eq!(message, "model 'x' not found");
+            }
+            other => panic!("expected an HTTP error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn plain_text_error_bodies_are_kept() {
+        let base = serve(vec![("/api/delete", Reply::Body(500, b"boom".to_vec()))]);
+        match quick(base).delete("x") {
+            Err(OllamaError::Http { status: 500, message }) => assert_eq!(message, "boom"),
+            other => panic!("expected an HTTP error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn version_and_tags_decode() {
+        let base = serve(vec![
+            ("/api/version", Reply::ok("{\"version\":\"0.5.7\"}")),
+            (
+                "/api/tags",
+                Reply::ok("{\"models\":[{\"name\":\"qwen2.5:3b\",\"size\":1929912432,\"details\":{\"parameter_size\":\"3.1B\"}}]}"),
+            ),
+        ]);
+        let client = quick(base);
+        assert_eq!(client.version().unwrap().version, "0.5.7");
+        let tags = client.tags().unwrap();
+        assert_eq!(tags.models[0].name, "qwen2.5:3b");
+        assert_eq!(tags.models[0].details.parameter_size, "3.1B");
+    }
+
+    #[test]
+    fn ps_decodes_loaded_models() {
+        let base = serve(vec![(
+            "/api/ps",
+            Reply::ok(
+                "{\"models\":[{\"name\":\"llama3.2:1b\",\"model\":\"llama3.2:1b\",\"size\":1500000000,\"digest\":\"sha256:ab\",\"details\":{\"family\":\"llama\"},\"expires_at\":\"2026-10-17T12:00:00Z\",\"size_vram\":1200000000}]}",
+            ),
+        )]);
+        let ps = quick(base).ps().unwrap();
+        assert_eq!(ps.models.len(), 1);
+        let running = &ps.models[0];
+        assert_eq!(running.name, "llama3.2:1b");
+        assert_eq!(running.size_vram, 1_200_000_000);
+        assert_eq!(running.details.family, "llama");
+        assert_eq!(running.expires_at, "2026-10-17T12:00:00Z");
+    }
+
+    #[test]
+    fn ps_with_nothing_loaded_is_empty() {
+        let base = serve(vec![("/api/ps", Reply::ok("{}"))]);
+        assert!(quick(base).ps().unwrap().models.is_empty());
+    }
+
+    #[test]
+    fn unanswered_requests_time_out() {
+        let base = serve(vec![(
+            "/api/version",
+            Reply::Stall { body: Vec::new(), pause: Duration::from_secs(3) },
+        )]);
+        assert!(quick(base).version().is_err());
+    }
+
+    #[test]
+    fn nothing_listening_is_unreachable() {
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let client = quick(format!("http://127.0.0.1:{}/", port));
+        assert!(matches!(client.version(), Err(OllamaError::Unreachable(_))));
+    }
+}
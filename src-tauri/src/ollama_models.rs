@@ -0,0 +1,280 @@
+// 🧩 Gignaati Workbench — Local model inventory
+//
+// Turns /api/tags (plus /api/show where tags lacks details) into typed
+// `OllamaModel` records and applies the UI's sort / filter options.
+
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::path::PathBuf;
+
+use crate::ollama_client::{ModelDetails, OllamaClient, OllamaError, TagModel};
+
+#[derive(Serialize, Debug, Clone)]
+pub struct OllamaModel {
+    /// Model name without tag, e.g. `llama3`.
+    pub name: String,
+    /// Tag, e.g. `latest` or `8b-instruct-q4_0`.
+    pub tag: String,
+    pub size: u64,
+    pub digest: String,
+    pub modified_at: String,
+    pub parameter_size: Option<String>,
+    pub quantization_level: Option<String>,
+    pub family: Option<String>,
+}
+
+impl OllamaModel {
+    /// `name:tag`, the form accepted by pull / delete.
+    pub fn reference(&self) -> String {
+        format!("{}:{}", self.name, self.tag)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelSortKey {
+    #[default]
+    Name,
+    Size,
+    Modified,
+    Family,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct ModelSort {
+    #[serde(default)]
+    pub by: ModelSortKey,
+    #[serde(default)]
+    pub descending: bool,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct ModelFilter {
+    /// Case-insensitive family match (e.g. `llama`, `qwen2`).
+    pub family: Option<String>,
+    /// Only models at or below this size in bytes.
+    pub max_size_bytes: Option<u64>,
+    /// Case-insensitive substring of `name:tag`.
+    pub name_contains: Option<String>,
+}
+
+/// Fetch, enrich, filter and sort the locally installed models.
+pub fn fetch_models(
+    client: &OllamaClient,
+    sort: &ModelSort,
+    filter: &ModelFilter,
+) -> Result<Vec<OllamaModel>, OllamaError> {
+    let tags = client.tags()?;
+
+    let mut models: Vec<OllamaModel> = tags
+        .models
+        .into_iter()
+        .map(|tag| to_model(client, tag))
+        .filter(|m| matches_filter(m, filter))
+        .collect();
+
+    sort_models(&mut models, sort);
+    Ok(models)
+}
+
+fn to_model(client: &OllamaClient, tag: TagModel) -> OllamaModel {
+    let (name, tag_name) = split_reference(&tag.name);
+
+    // Older servers return empty details from /api/tags; fall back to /api/show
+    let details = if tag.details.family.is_empty() && tag.details.parameter_size.is_empty() {
+        client
+            .show(&tag.name)
+            .map(|show| show.details)
+            .unwrap_or_default()
+    } else {
+        tag.details
+    };
+
+    OllamaModel {
+        name,
+        tag: tag_name,
+        size: tag.size,
+        digest: tag.digest,
+        modified_at: tag.modified_at,
+        parameter_size: non_empty(&details.parameter_size),
+        quantization_level: non_empty(&details.quantization_level),
+        family: family_of(&details),
+    }
+}
+
+fn split_reference(reference: &str) -> (String, String) {
+    match reference.rsplit_once(':') {
+        Some((name, tag)) if !tag.contains('/') => (name.to_string(), tag.to_string()),
+        _ => (reference.to_string(), "latest".to_string()),
+    }
+}
+
+fn family_of(details: &ModelDetails) -> Option<String> {
+    non_empty(&details.family).or_else(|| {
+        details
+            .families
+            .as_ref()
+            .and_then(|f| f.first())
+            .and_then(|f| non_empty(f))
+    })
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    let v = value.trim();
+    (!v.is_empty()).then(|| v.to_string())
+}
+
+fn matches_filter(model: &OllamaModel, filter: &ModelFilter) -> bool {
+    if let Some(family) = filter.family.as_deref().filter(|f| !f.is_empty()) {
+        let matches = model
+            .family
+            .as_deref()
+            .is_some_and(|f| f.eq_ignore_ascii_case(family));
+        if !matches {
+            return false;
+        }
+    }
+    if let Some(max) = filter.max_size_bytes {
+        if model.size > max {
+            return false;
+        }
+    }
+    if let Some(needle) = filter.name_contains.as_deref().filter(|n| !n.is_empty()) {
+        if !model
+            .reference()
+            .to_lowercase()
+            .contains(&needle.to_lowercase())
+        {
+            return false;
+        }
+    }
+    true
+}
+
+fn sort_models(models: &mut [OllamaModel], sort: &ModelSort) {
+    models.sort_by(|a, b| {
+        let ord = match sort.by {
+            ModelSortKey::Name => a.reference().cmp(&b.reference()),
+            ModelSortKey::Size => a.size.cmp(&b.size),
+            // Ollama reports RFC 3339 timestamps in one offset, so they sort as strings
+            ModelSortKey::Modified => a.modified_at.cmp(&b.modified_at),
+            ModelSortKey::Family => a
+                .family
+                .cmp(&b.family)
+                .then_with(|| a.reference().cmp(&b.reference())),
+        };
+        if sort.descending {
+            ord.reverse()
+        } else {
+            ord
+        }
+    });
+}
+
+/// Where Ollama stores model blobs (`OLLAMA_MODELS`, else `~/.ollama/models`).
+pub(crate) fn ollama_models_dir() -> Option<PathBuf> {
+    if let Ok(custom) = env::var("OLLAMA_MODELS") {
+        return Some(PathBuf::from(custom));
+    }
+    dirs::home_dir().map(|h| h.join(".ollama").join("models"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{serve, Reply};
+
+    fn model(reference: &str, size: u64, modified_at: &str, family: Option<&str>) -> OllamaModel {
+        let (name, tag) = split_reference(reference);
+        OllamaModel {
+            name,
+            tag,
+            size,
+            digest: String::new(),
+            modified_at: modified_at.into(),
+            parameter_size: None,
+            quantization_level: None,
+            family: family.map(str::to_string),
+        }
+    }
+
+    fn references(models: &[OllamaModel]) -> Vec<String> {
+        models.iter().map(OllamaModel::reference).collect()
+    }
+
+    #[test]
+    fn split_reference_defaults_the_tag() {
+        assert_eq!(split_reference("llama3:8b"), ("llama3".into(), "8b".into()));
+        assert_eq!(split_reference("llama3"), ("llama3".into(), "latest".into()));
+        assert_eq!(
+            split_reference("registry.local:5000/team/model"),
+            ("registry.local:5000/team/model".into(), "latest".into())
+        );
+        assert_eq!(
+            split_reference("registry.local:5000/team/model:q4"),
+            ("registry.local:5000/team/model".into(), "q4".into())
+        );
+    }
+
+    #[test]
+    fn sort_models_by_each_key() {
+        let mut models = vec![
+            model("qwen2.5:3b", 2_000, "2025-02-01T00:00:00Z", Some("qwen2")),
+            model("llama3.2:1b", 1_000, "2025-03-01T00:00:00Z", Some("llama")),
+            model("phi3:mini", 3_000, "2025-01-01T00:00:00Z", None),
+        ];
+
+        sort_models(&mut models, &ModelSort::default());
+        assert_eq!(references(&models), ["llama3.2:1b", "phi3:mini", "qwen2.5:3b"]);
+
+        sort_models(&mut models, &ModelSort { by: ModelSortKey::Size, descending: true });
+        assert_eq!(references(&models), ["phi3:mini", "qwen2.5:3b", "llama3.2:1b"]);
+
+        sort_models(&mut models, &ModelSort { by: ModelSortKey::Modified, descending: false });
+        assert_eq!(references(&models), ["phi3:mini", "qwen2.5:3b", "llama3.2:1b"]);
+
+        // Models without a family sort first
+        sort_models(&mut models, &ModelSort { by: ModelSortKey::Family, descending: false });
+        assert_eq!(references(&models), ["phi3:mini", "llama3.2:1b", "qwen2.5:3b"]);
+    }
+
+    #[test]
+    fn filters_combine() {
+        let m = model("llama3.2:1b", 1_000, "", Some("llama"));
+        let filter = |family: Option<&str>, max: Option<u64>, name: Option<&str>| ModelFilter {
+            family: family.map(str::to_string),
+            max_size_bytes: max,
+            name_contains: name.map(str::to_string),
+        };
+        assert!(matches_filter(&m, &filter(Some("LLAMA"), Some(1_000), Some("3.2:1"))));
+        assert!(!matches_filter(&m, &filter(Some("qwen2"), None, None)));
+        assert!(!matches_filter(&m, &filter(None, Some(999), None)));
+        assert!(!matches_filter(&m, &filter(None, None, Some("mistral"))));
+        assert!(matches_filter(&m, &filter(Some(""), None, Some(""))));
+    }
+
+    #[test]
+    fn fetch_models_falls_back_to_show_for_details() {
+        let base = serve(vec![
+            (
+                "/api/tags",
+                Reply::ok(r#"{"models":[
+                    {"name":"old:latest","size":10,"details":{}},
+                    {"name":"new:7b","size":20,"details":{"family":"qwen2","parameter_size":"7.6B","quantization_level":"Q4_K_M"}}
+                ]}"#),
+            ),
+            (
+                "/api/show",
+                Reply::ok(r#"{"details":{"families":["llama"],"parameter_size":"3B"}}"#),
+            ),
+        ]);
+        let models = fetch_models(&OllamaClient::new(base), &ModelSort::default(), &ModelFilter::default()).unwrap();
+
+        assert_eq!(references(&models), ["new:7b", "old:latest"]);
+        assert_eq!(models[0].family.as_deref(), Some("qwen2"));
+        assert_eq!(models[0].quantization_level.as_deref(), Some("Q4_K_M"));
+        assert_eq!(models[1].family.as_deref(), Some("llama"));
+        assert_eq!(models[1].parameter_size.as_deref(), Some("3B"));
+        assert_eq!(models[1].quantization_level, None);
+    }
+}
@@ -1,16 +1,14 @@
 use tauri::{AppHandle, Emitter};
-use serde::{Deserialize, Serialize};
-use std::process::{Command, Stdio};
-use std::io::{BufRead, BufReader};
-use std::thread;
-use std::path::Path;
-use std::time::{Duration, Instant};
-use std::sync::{Arc, Mutex};
-use std::net::TcpStream;
-use std::env;
-use once_cell::sync::Lazy;
+use serde::Serialize;
 
-use crate::config::AppConfig;
+use crate::discovery::{self, Tool};
+use crate::model_downloads;
+use crate::ollama_client::OllamaClient;
+use crate::ollama_models::{fetch_models, ModelFilter, ModelSort, OllamaModel};
+use crate::ports::ownership::{resolve_port, PortResolution};
+use crate::ports::registry;
+use crate::readiness::{self, OLLAMA_READY_TIMEOUT};
+use crate::supervisor::{self, ServiceKind, ServiceSpec};
 
 #[derive(Serialize, Clone)]
 struct ComponentLog {
@@ -18,16 +16,18 @@ struct ComponentLog {
     message: String,
 }
 
-// === Global Handles ===
-static OLLAMA_PROCESS: Lazy<Arc<Mutex<Option<std::process::Child>>>> =
-    Lazy::new(|| Arc::new(Mutex::new(None)));
-
-static OLLAMA_DOWNLOAD: Lazy<Arc<Mutex<Option<std::process::Child>>>> =
-    Lazy::new(|| Arc::new(Mutex::new(None)));
-
 /// 🚀 Start Ollama server
 #[tauri::command]
-pub fn start_ollama_server(app: AppHandle) -> Result<(), String> {
+pub async fn start_ollama_server(app: AppHandle) -> Result<(), String> {
+    // Waiting for readiness blocks; keep it off the async runtime
+    tauri::async_runtime::spawn_blocking(move || start_server(&app))
+        .await
+        .map_err(|e| format!("❌ Failed to start Ollama: {}", e))?
+}
+
+/// Start Ollama under the supervisor (or reuse a running one) and block until
+/// it answers.
+pub(crate) fn start_server(app: &AppHandle) -> Result<(), String> {
     let component = "Ollama Server";
     app.emit(
         "component-log",
@@ -38,10 +38,9 @@ pub fn start_ollama_server(app: AppHandle) -> Result<(), String> {
     )
     .ok();
 
-    let config = AppConfig::load();
-    let ollama_port = config.ollama_port.unwrap_or(11434);
+    let preferred_port = registry::release_for_launch(registry::OLLAMA)?;
     let ollama_path =
-        detect_ollama_path().ok_or("❌ Ollama binary not found on this system.")?;
+        discovery::locate(Tool::Ollama).ok_or("❌ Ollama binary not found on this system.")?;
 
     app.emit(
         "component-log",
@@ -52,90 +51,72 @@ pub fn start_ollama_server(app: AppHandle) -> Result<(), String> {
     )
     .ok();
 
-    // 🧹 Free port if busy
-    let port = config.ollama_port.unwrap_or(11434);
-    if is_listening(port) {
-        app.emit(
-            "component-log",
-            ComponentLog {
-                component: component.into(),
-                message: format!("⚠ Port {} is already in use. Attempting to free it...", port),
-            },
-        )
-        .ok();
-        let _ = free_port(port);
-        thread::sleep(Duration::from_secs(2));
-    }
-
-    if is_listening(ollama_port) {
-        app.emit(
-            "component-log",
-            ComponentLog {
-                component: component.into(),
-                message: format!("✅ Ollama already running on port {}", ollama_port),
-            },
-        )
-        .ok();
-        return Ok(());
-    }
-
-    let mut cmd = Command::new(&ollama_path)
-        .arg("serve")
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| format!("❌ Failed to start Ollama server: {}", e))?;
-
-    let stdout = cmd.stdout.take().unwrap();
-    let stderr = cmd.stderr.take().unwrap();
-
-    let app_out = app.clone();
-    let component_out = component.to_string();
-    thread::spawn(move || {
-        let reader = BufReader::new(stdout);
-        for line in reader.lines().flatten() {
-            app_out
-                .emit(
-                    "component-log",
-                    ComponentLog {
-                        component: component_out.clone(),
-                        message: line,
-                    },
-                )
-                .ok();
+    // 🔌 Never kill whatever holds the port; reuse it if it is Ollama, else move
+    let ollama_port = match resolve_port(ServiceKind::Ollama, preferred_port)? {
+        PortResolution::Free(port) => port,
+        PortResolution::Reuse { port, holder } => {
+            app.emit(
+                "component-log",
+                ComponentLog {
+                    component: component.into(),
+                    message: format!(
+                        "✅ Ollama already running on port {}{}",
+                        port,
+                        holder.map(|h| format!(" as {}", h.describe())).unwrap_or_default()
+                    ),
+                },
+            )
+            .ok();
+            return Ok(());
         }
-    });
-
-    let app_err = app.clone();
-    let component_err = component.to_string();
-    thread::spawn(move || {
-        let reader = BufReader::new(stderr);
-        for line in reader.lines().flatten() {
-            app_err
-                .emit(
-                    "component-log",
-                    ComponentLog {
-                        component: component_err.clone(),
-                        message: format!("⚠ {}", line),
-                    },
-                )
-                .ok();
+        PortResolution::Relocated { from, to, holder } => {
+            let who = holder
+                .map(|h| h.describe())
+                .unwrap_or_else(|| "another process".into());
+            app.emit(
+                "component-log",
+                ComponentLog {
+                    component: component.into(),
+                    message: format!("⚠ Port {} is held by {}; using port {} instead.", from, who, to),
+                },
+            )
+            .ok();
+            to
         }
-    });
+    };
+
+    let pid = supervisor::start(
+        app,
+        ServiceSpec {
+            kind: ServiceKind::Ollama,
+            program: ollama_path,
+            args: vec!["serve".into()],
+            env: vec![("OLLAMA_HOST".into(), format!("127.0.0.1:{}", ollama_port).into())],
+            port: ollama_port,
+        },
+    )
+    .map_err(|e| format!("❌ {}", e))?;
 
-    {
-        let mut handle = OLLAMA_PROCESS.lock().unwrap();
-        *handle = Some(cmd);
-    }
+    app.emit(
+        "component-log",
+        ComponentLog {
+            component: component.into(),
+            message: format!("⏳ Waiting for Ollama (PID {}) to answer on port {}...", pid, ollama_port),
+        },
+    )
+    .ok();
+
+    let waited = readiness::wait_until_ready(ServiceKind::Ollama, ollama_port, OLLAMA_READY_TIMEOUT)
+        .map_err(|e| e.to_string())?;
 
-    thread::sleep(Duration::from_secs(3));
     app.emit(
         "component-log",
         ComponentLog {
             component: component.into(),
             message: format!(
-                "✅ Ollama server started successfully on port {}",
-                ollama_port
+                "✅ Ollama server started successfully on port {} (ready in {:.1}s)",
+                ollama_port,
+                waited.as_secs_f32()
             ),
         },
     )
@@ -148,226 +129,68 @@ pub fn start_ollama_server(app: AppHandle) -> Result<(), String> {
 #[tauri::command]
 pub fn stop_ollama_server(app: AppHandle) -> Result<(), String> {
     let component = "Ollama Server";
-    let mut handle = OLLAMA_PROCESS.lock().unwrap();
-
-    if let Some(child) = handle.as_mut() {
-        let _ = child.kill();
-        *handle = None;
-        app.emit(
-            "component-log",
-            ComponentLog {
-                component: component.into(),
-                message: "🛑 Ollama server stopped successfully.".into(),
-            },
-        )
-        .ok();
+    let message = if supervisor::stop(&app, ServiceKind::Ollama) {
+        "🛑 Ollama server stopped successfully."
     } else {
-        app.emit(
-            "component-log",
-            ComponentLog {
-                component: component.into(),
-                message: "ℹ Ollama server was not running.".into(),
-            },
-        )
-        .ok();
-    }
+        "ℹ Ollama server was not running."
+    };
+    app.emit(
+        "component-log",
+        ComponentLog {
+            component: component.into(),
+            message: message.into(),
+        },
+    )
+    .ok();
     Ok(())
 }
 
-/// 📦 List available models
+/// 📦 List available models with size, digest, family and quantization.
+/// `sort` and `filter` are optional; by default models are sorted by name.
 #[tauri::command]
-pub fn list_ollama_models() -> Result<Vec<String>, String> {
-    let ollama_path = detect_ollama_path().ok_or("❌ Ollama binary not found.")?;
-    let output = Command::new(&ollama_path)
-        .arg("list")
-        .output()
-        .map_err(|e| format!("Failed to list models: {}", e))?;
-
-    if !output.status.success() {
-        return Err(format!(
-            "❌ Failed to list models: {}",
-            String::from_utf8_lossy(&output.stderr)
-        ));
-    }
+pub fn list_ollama_models(
+    sort: Option<ModelSort>,
+    filter: Option<ModelFilter>,
+) -> Result<Vec<OllamaModel>, String> {
+    fetch_models(
+        &OllamaClient::from_config(),
+        &sort.unwrap_or_default(),
+        &filter.unwrap_or_default(),
+    )
+    .map_err(|e| format!("❌ Failed to list models: {}", e))
+}
 
-    let result = String::from_utf8_lossy(&output.stdout)
-        .lines()
-        .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty())
-        .collect::<Vec<_>>();
+/// ⬇ Queue a model pull; progress arrives as `model-pull-progress` events
+#[tauri::command]
+pub fn pull_ollama_model(app: AppHandle, model_name: String) -> Result<(), String> {
+    // The REST API needs a running server; fail early with a clear hint
+    OllamaClient::from_config().version().map_err(|e| {
+        format!("❌ {} — start the Ollama server before pulling models.", e)
+    })?;
 
-    Ok(result)
+    model_downloads::enqueue(&app, &model_name)
 }
 
-/// ⬇ Pull model from Ollama registry (real-time JSON progress)
+/// ⏹ Cancel one model download, or all of them when no model is given
 #[tauri::command]
-pub fn pull_ollama_model(app: AppHandle, model_name: String) -> Result<(), String> {
-    let component = "Ollama Model Pull";
-    let ollama_path = detect_ollama_path().ok_or("❌ Ollama binary not found.")?;
+pub fn cancel_ollama_download(app: AppHandle, model_name: Option<String>) -> Result<(), String> {
+    let component = "Ollama Cancel Download";
+    let cancelled = model_downloads::cancel(&app, model_name.as_deref());
 
+    let message = if cancelled.is_empty() {
+        "ℹ No active download to cancel.".to_string()
+    } else {
+        format!("⏹ Download cancelled by user: {}", cancelled.join(", "))
+    };
     app.emit(
         "component-log",
         ComponentLog {
             component: component.into(),
-            message: format!("⬇ Starting download for model '{}'...", model_name),
+            message,
         },
     )
     .ok();
 
-    thread::spawn(move || {
-        let cmd_result = if is_listening(11434) {
-            Command::new("curl")
-                .args([
-                    "-N",
-                    "-s",
-                    "-X",
-                    "POST",
-                    "http://localhost:11434/api/pull",
-                    "-H",
-                    "Content-Type: application/json",
-                    "-d",
-                    &format!("{{\"name\":\"{}\"}}", model_name),
-                ])
-                .stdout(Stdio::piped())
-                .stderr(Stdio::null())
-                .spawn()
-        } else {
-            Command::new(&ollama_path)
-                .args(["pull", &model_name])
-                .stdout(Stdio::piped())
-                .stderr(Stdio::null())
-                .spawn()
-        };
-
-        let mut cmd = match cmd_result {
-            Ok(c) => c,
-            Err(e) => {
-                let _ = app.emit(
-                    "component-log",
-                    ComponentLog {
-                        component: component.into(),
-                        message: format!("❌ Failed to start pull: {}", e),
-                    },
-                );
-                return;
-            }
-        };
-
-        let stdout = cmd.stdout.take();
-        {
-            let mut dl = OLLAMA_DOWNLOAD.lock().unwrap();
-            *dl = Some(cmd);
-        }
-
-        if let Some(stdout) = stdout {
-            let app_progress = app.clone();
-            let comp = component.to_string();
-            let model = model_name.clone();
-            thread::spawn(move || {
-                let reader = BufReader::new(stdout);
-                let mut last_emit = Instant::now();
-                let mut progress_seen = false;
-
-                for line in reader.lines().flatten() {
-                    if let Some(msg) = parse_ollama_json_line(&line) {
-                        progress_seen = true;
-                        if last_emit.elapsed() > Duration::from_secs(1) {
-                            let _ = app_progress.emit(
-                                "component-log",
-                                ComponentLog {
-                                    component: comp.clone(),
-                                    message: msg,
-                                },
-                            );
-                            last_emit = Instant::now();
-                        }
-                    }
-                }
-
-                if progress_seen {
-                    let _ = app_progress.emit(
-                        "component-log",
-                        ComponentLog {
-                            component: comp.clone(),
-                            message: format!("✅ Finished pulling '{}'", model),
-                        },
-                    );
-                }
-            });
-        }
-
-        let status = {
-            let mut handle = OLLAMA_DOWNLOAD.lock().unwrap();
-            if let Some(mut child) = handle.take() {
-                child.wait()
-            } else {
-                return;
-            }
-        };
-
-        match status {
-            Ok(s) if s.success() => {
-                let _ = app.emit(
-                    "component-log",
-                    ComponentLog {
-                        component: component.into(),
-                        message: format!("✅ Model '{}' pulled successfully.", model_name),
-                    },
-                );
-            }
-            Ok(_) => {
-                let _ = app.emit(
-                    "component-log",
-                    ComponentLog {
-                        component: component.into(),
-                        message:
-                            "❌ Model pull failed. 💡 Try the Repair Model Pull option.".into(),
-                    },
-                );
-            }
-            Err(e) => {
-                let _ = app.emit(
-                    "component-log",
-                    ComponentLog {
-                        component: component.into(),
-                        message: format!("❌ Pull interrupted: {}", e),
-                    },
-                );
-            }
-        }
-    });
-
-    Ok(())
-}
-
-/// ⏹ Cancel active model download
-#[tauri::command]
-pub fn cancel_ollama_download(app: AppHandle) -> Result<(), String> {
-    let component = "Ollama Cancel Download";
-    let mut handle = OLLAMA_DOWNLOAD.lock().unwrap();
-
-    if let Some(child) = handle.as_mut() {
-        let _ = child.kill();
-        *handle = None;
-        app.emit(
-            "component-log",
-            ComponentLog {
-                component: component.into(),
-                message: "⏹ Download cancelled by user.".into(),
-            },
-        )
-        .ok();
-    } else {
-        app.emit(
-            "component-log",
-            ComponentLog {
-                component: component.into(),
-                message: "ℹ No active download to cancel.".into(),
-            },
-        )
-        .ok();
-    }
-
     Ok(())
 }
 
@@ -375,34 +198,28 @@ pub fn cancel_ollama_download(app: AppHandle) -> Result<(), String> {
 #[tauri::command]
 pub fn remove_ollama_model(app: AppHandle, model_name: String) -> Result<(), String> {
     let component = "Ollama Remove Model";
-    let ollama_path = detect_ollama_path().ok_or("❌ Ollama binary not found.")?;
-
-    let output = Command::new(&ollama_path)
-        .args(["rm", &model_name])
-        .output()
-        .map_err(|e| format!("❌ Failed to remove model: {}", e))?;
 
-    if output.status.success() {
-        app.emit(
-            "component-log",
-            ComponentLog {
-                component: component.into(),
-                message: format!("✅ Model '{}' removed successfully.", model_name),
-            },
-        )
-        .ok();
-    } else {
-        app.emit(
-            "component-log",
-            ComponentLog {
-                component: component.into(),
-                message: format!(
-                    "❌ Removal failed: {}",
-                    String::from_utf8_lossy(&output.stderr)
-                ),
-            },
-        )
-        .ok();
+    match OllamaClient::from_config().delete(&model_name) {
+        Ok(()) => {
+            app.emit(
+                "component-log",
+                ComponentLog {
+                    component: component.into(),
+                    message: format!("✅ Model '{}' removed successfully.", model_name),
+                },
+            )
+            .ok();
+        }
+        Err(e) => {
+            app.emit(
+                "component-log",
+                ComponentLog {
+                    component: component.into(),
+                    message: format!("❌ Removal failed: {}", e),
+                },
+            )
+            .ok();
+        }
     }
     Ok(())
 }
@@ -421,104 +238,3 @@ pub fn repair_ollama_model(app: AppHandle, model_name: String) -> Result<(), Str
     .ok();
     pull_ollama_model(app, model_name)
 }
-
-// === Helpers ===
-
-#[derive(Deserialize)]
-struct OllamaProgress {
-    status: Option<String>,
-    completed: Option<u64>,
-    total: Option<u64>,
-}
-
-fn parse_ollama_json_line(line: &str) -> Option<String> {
-    if let Ok(json) = serde_json::from_str::<OllamaProgress>(line) {
-        if let (Some(c), Some(t)) = (json.completed, json.total) {
-            let pct = ((c as f64 / t as f64) * 100.0) as u8;
-            Some(format!("📦 Downloading model: {}% complete", pct))
-        } else if let Some(status) = json.status {
-            Some(format!("📦 {}", status))
-        } else {
-            None
-        }
-    } else if line.contains("pulling") {
-        Some(format!("📦 {}", line))
-    } else {
-        None
-    }
-}
-
-fn detect_ollama_path() -> Option<String> {
-    let username = env::var("USERNAME").unwrap_or_default();
-    let candidates = vec![
-        "ollama".to_string(),
-        r"C:\Program Files\Ollama\ollama.exe".to_string(),
-        format!(
-            r"C:\Users\{}\AppData\Local\Programs\Ollama\ollama.exe",
-            username
-        ),
-        "/usr/local/bin/ollama".to_string(),
-        "/usr/bin/ollama".to_string(),
-    ];
-    for c in candidates {
-        if Path::new(&c).exists() {
-            return Some(c);
-        }
-    }
-    None
-}
-
-/// ✅ Check if port is listening
-fn is_listening(port: u16) -> bool {
-    TcpStream::connect(("127.0.0.1", port)).is_ok()
-}
-
-/// 🧹 Free the port if in use (cross-platform)
-fn free_port(port: u16) -> Result<(), String> {
-    #[cfg(target_os = "windows")]
-    {
-        let cmd = format!("netstat -ano | findstr :{}", port);
-        let output = Command::new("cmd")
-            .args(&["/C", &cmd])
-            .output()
-            .map_err(|e| format!("Failed to run netstat: {}", e))?;
-
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        if stdout.trim().is_empty() {
-            return Ok(());
-        }
-
-        for line in stdout.lines() {
-            if let Some(pid) = line.split_whitespace().last() {
-                let _ = Command::new("taskkill")
-                    .args(&["/F", "/PID", pid])
-                    .output()
-                    .map_err(|e| format!("Failed to kill process on port {}: {}", port, e))?;
-            }
-        }
-        Ok(())
-    }
-
-    #[cfg(not(target_os = "windows"))]
-    {
-        let output = Command::new("sh")
-            .arg("-c")
-            .arg(format!("lsof -t -i :{}", port))
-            .output()
-            .map_err(|e| format!("Failed to run lsof: {}", e))?;
-
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        if stdout.trim().is_empty() {
-            return Ok(());
-        }
-
-        for pid in stdout.lines() {
-            let _ = Command::new("kill")
-                .arg("-9")
-                .arg(pid)
-                .output()
-                .map_err(|e| format!("Failed to kill PID {}: {}", pid, e))?;
-        }
-        Ok(())
-    }
-}
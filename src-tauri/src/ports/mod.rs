@@ -3,6 +3,8 @@
 
 #[allow(unused)]
 pub mod manager;
+pub mod ownership;   // ✅ Port holder identification and conflict resolution
+pub mod registry;    // ✅ Session-wide port reservations backed by AppConfig
 
 // ✅ Keep re-export for consistency, silence warning safely
 #[allow(unused)]
@@ -0,0 +1,259 @@
+// 🧩 Port ownership — who holds a port, and what to do about it
+//
+// Services never kill whatever is listening on their port. If the holder is
+// the service we would start anyway we reuse it; otherwise we move to the next
+// free port and persist that. Terminating a foreign process is a separate,
+// explicitly confirmed action (`release_port`).
+
+use serde::Serialize;
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, Signal, System};
+use tauri::{AppHandle, Emitter};
+
+use crate::ports::manager::{check_port_available, find_available_port};
+use crate::ports::registry;
+use crate::readiness;
+use crate::supervisor::{self, ServiceKind};
+
+/// How far past the preferred port we look for a free one.
+const RELOCATE_RANGE: u16 = 100;
+
+#[derive(Serialize, Clone)]
+struct ComponentLog {
+    component: String,
+    message: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct PortHolder {
+    pub port: u16,
+    pub pid: u32,
+    pub name: Option<String>,
+    pub exe: Option<String>,
+    pub cmdline: Option<String>,
+    /// Set when the holder is one of our supervised services.
+    pub managed: Option<ServiceKind>,
+}
+
+impl PortHolder {
+    pub fn describe(&self) -> String {
+        let name = self
+            .name
+            .as_deref()
+            .or(self.exe.as_deref())
+            .unwrap_or("unknown process");
+        format!("'{}' (PID {})", name, self.pid)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum PortResolution {
+    /// Nothing is listening; start the service here.
+    Free(u16),
+    /// The port already serves this service; don't start another copy.
+    Reuse { port: u16, holder: Option<PortHolder> },
+    /// Something else owns the preferred port; use `to` (already persisted).
+    Relocated { from: u16, to: u16, holder: Option<PortHolder> },
+}
+
+/// Decide which port `kind` should run on, starting from `preferred`.
+pub fn resolve_port(kind: ServiceKind, preferred: u16) -> Result<PortResolution, String> {
+    if check_port_available(preferred) {
+        return Ok(PortResolution::Free(preferred));
+    }
+
+    let holder = port_holder(preferred);
+    if is_same_service(kind, preferred, holder.as_ref()) {
+        return Ok(PortResolution::Reuse {
+            port: preferred,
+            holder,
+        });
+    }
+
+    let start = preferred.saturating_add(1);
+    let to = find_available_port(start, start.saturating_add(RELOCATE_RANGE)).ok_or_else(|| {
+        format!(
+            "Port {} is in use and no free port was found in {}-{}",
+            preferred,
+            start,
+            start.saturating_add(RELOCATE_RANGE)
+        )
+    })?;
+
+    registry::record(kind.id(), to);
+    Ok(PortResolution::Relocated {
+        from: preferred,
+        to,
+        holder,
+    })
+}
+
+/// The holder counts as "our" service if the supervisor owns that PID, or if
+/// it answers the service's health endpoint and looks like the same program
+/// (e.g. an Ollama started by the OS service manager).
+fn is_same_service(kind: ServiceKind, port: u16, holder: Option<&PortHolder>) -> bool {
+    if let Some(h) = holder {
+        if h.managed == Some(kind) {
+            return true;
+        }
+    }
+
+    if readiness::probe(kind, port).is_err() {
+        return false;
+    }
+
+    let marker = match kind {
+        ServiceKind::N8n => "n8n",
+        ServiceKind::Ollama => "ollama",
+    };
+    match holder {
+        Some(h) => [&h.name, &h.exe, &h.cmdline]
+            .into_iter()
+            .flatten()
+            .any(|s| s.to_lowercase().contains(marker)),
+        // Holder not identifiable (permissions); trust the health endpoint
+        None => true,
+    }
+}
+
+/// Identify the process listening on `port`, if the OS will tell us.
+pub fn port_holder(port: u16) -> Option<PortHolder> {
+    let pid = listening_pid(port)?;
+
+    let mut sys = System::new();
+    sys.refresh_process(Pid::from_u32(pid));
+    let process = sys.process(Pid::from_u32(pid));
+
+    Some(PortHolder {
+        port,
+        pid,
+        name: process.map(|p| p.name().to_string()),
+        exe: process
+            .and_then(|p| p.exe())
+            .map(|e| e.to_string_lossy().to_string()),
+        cmdline: process
+            .map(|p| p.cmd().join(" "))
+            .filter(|c| !c.is_empty()),
+        managed: managed_kind(pid),
+    })
+}
+
+fn managed_kind(pid: u32) -> Option<ServiceKind> {
+    [ServiceKind::N8n, ServiceKind::Ollama]
+        .into_iter()
+        .find(|&k| supervisor::status(k).and_then(|s| s.pid) == Some(pid))
+}
+
+#[cfg(target_os = "windows")]
+fn listening_pid(port: u16) -> Option<u32> {
+    let output = Command::new("netstat").args(["-ano", "-p", "TCP"]).output().ok()?;
+    let suffix = format!(":{}", port);
+
+    String::from_utf8_lossy(&output.stdout).lines().find_map(|line| {
+        let cols: Vec<&str> = line.split_whitespace().collect();
+        match cols.as_slice() {
+            [_, local, _, state, pid] if local.ends_with(&suffix) && *state == "LISTENING" => {
+                pid.parse().ok()
+            }
+            _ => None,
+        }
+    })
+}
+
+#[cfg(not(target_os = "windows"))]
+fn listening_pid(port: u16) -> Option<u32> {
+    let lsof = Command::new("lsof")
+        .args(["-nP", &format!("-iTCP:{}", port), "-sTCP:LISTEN", "-t"])
+        .output();
+    if let Ok(output) = lsof {
+        if let Some(pid) = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find_map(|l| l.trim().parse().ok())
+        {
+            return Some(pid);
+        }
+    }
+
+    // Minimal Linux installs often lack lsof; `ss -p` prints users:(("x",pid=123,fd=4))
+    let output = Command::new("ss")
+        .args(["-ltnpH", &format!("sport = :{}", port)])
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let start = stdout.find("pid=")? + 4;
+    let digits: String = stdout[start..].chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+/// 🔎 Report which process is listening on a port
+#[tauri::command]
+pub fn inspect_port(port: u16) -> Option<PortHolder> {
+    port_holder(port)
+}
+
+/// 🛑 Terminate the process holding a port.
+///
+/// Only runs when the user has confirmed it in the UI (`confirmed: true`).
+/// Our own services are stopped through the supervisor; anything else gets a
+/// polite terminate first and a forced kill only if the port stays busy.
+#[tauri::command]
+pub fn release_port(app: AppHandle, port: u16, confirmed: bool) -> Result<PortHolder, String> {
+    let holder = port_holder(port)
+        .ok_or_else(|| format!("No identifiable process is listening on port {}", port))?;
+
+    if !confirmed {
+        return Err(format!(
+            "Refusing to stop {} on port {} without user confirmation.",
+            holder.describe(),
+            port
+        ));
+    }
+
+    log(
+        &app,
+        &format!("🛑 Stopping {} on port {} at the user's request...", holder.describe(), port),
+    );
+
+    if let Some(kind) = holder.managed {
+        supervisor::stop(&app, kind);
+        return Ok(holder);
+    }
+
+    let mut sys = System::new();
+    let pid = Pid::from_u32(holder.pid);
+    sys.refresh_process(pid);
+    let process = sys
+        .process(pid)
+        .ok_or_else(|| format!("Process {} already exited", holder.pid))?;
+
+    if process.kill_with(Signal::Term) != Some(true) {
+        process.kill();
+    }
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !check_port_available(port) && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(200));
+    }
+    if !check_port_available(port) {
+        sys.refresh_process(pid);
+        if let Some(p) = sys.process(pid) {
+            p.kill();
+        }
+    }
+
+    log(&app, &format!("✅ Port {} released.", port));
+    Ok(holder)
+}
+
+fn log(app: &AppHandle, message: &str) {
+    app.emit(
+        "component-log",
+        ComponentLog {
+            component: "Ports".into(),
+            message: message.into(),
+        },
+    )
+    .ok();
+}
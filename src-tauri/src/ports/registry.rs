@@ -0,0 +1,190 @@
+// 🧩 Port registry — one source of truth for every service port
+//
+// Ports live in AppConfig (`n8n_port`, `ollama_port`, and `service_ports` for
+// extra named services). At startup each port is reserved for the session by
+// holding a listener on it, so nothing else grabs it before our service
+// starts; the listener is dropped right before the service binds.
+
+use once_cell::sync::Lazy;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::net::TcpListener;
+use std::path::Path;
+use std::sync::Mutex;
+
+use crate::config::AppConfig;
+use crate::ports::manager::{check_port_available, PortConfig};
+
+pub const N8N: &str = "n8n";
+pub const OLLAMA: &str = "ollama";
+/// n8n task-runner broker (`N8N_RUNNERS_BROKER_PORT`).
+pub const N8N_TASK_RUNNER: &str = "n8n_task_runner";
+
+/// How far past the preferred port we look when it is taken by another entry.
+const SEARCH_RANGE: u16 = 20;
+
+struct Reservation {
+    port: u16,
+    /// Held until the owning service is launched.
+    guard: Option<TcpListener>,
+}
+
+static RESERVED: Lazy<Mutex<HashMap<String, Reservation>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn default_port(name: &str) -> Option<u16> {
+    match name {
+        N8N => Some(5678),
+        OLLAMA => Some(11434),
+        N8N_TASK_RUNNER => Some(5679),
+        _ => None,
+    }
+}
+
+fn configured_port(cfg: &AppConfig, name: &str) -> Option<u16> {
+    match name {
+        N8N => cfg.n8n_port,
+        OLLAMA => cfg.ollama_port,
+        other => cfg.service_ports.get(other).copied(),
+    }
+}
+
+fn set_configured_port(cfg: &mut AppConfig, name: &str, port: u16) {
+    match name {
+        N8N => cfg.n8n_port = Some(port),
+        OLLAMA => cfg.ollama_port = Some(port),
+        other => {
+            cfg.service_ports.insert(other.to_string(), port);
+        }
+    }
+}
+
+/// Import ports from the legacy `<app_data_dir>/.gwconfig` into AppConfig,
+/// then rename the file so it is not imported twice.
+pub fn migrate_legacy(app_data_dir: &Path) {
+    let legacy = app_data_dir.join(".gwconfig");
+    let Ok(data) = fs::read_to_string(&legacy) else {
+        return;
+    };
+
+    if let Ok(old) = serde_json::from_str::<PortConfig>(&data) {
+        let saved = AppConfig::modify(|cfg| {
+            cfg.n8n_port = Some(old.n8n_port);
+            cfg.ollama_port = Some(old.ollama_port);
+            for (name, port) in old.extra {
+                cfg.service_ports.entry(name).or_insert(port);
+            }
+        });
+        if let Err(e) = saved {
+            // Leave .gwconfig in place so the import is retried next start
+            eprintln!("⚠ Failed to migrate legacy port config: {}", e);
+            return;
+        }
+    }
+    let _ = fs::rename(&legacy, app_data_dir.join(".gwconfig.migrated"));
+}
+
+/// Reserve a port for `name` for the rest of the session and persist it.
+///
+/// Starts from `preferred`, then the configured port, then the built-in
+/// default. A port already claimed by another registry entry is skipped; a
+/// port held by an outside process is kept as-is so the launch path can
+/// decide whether to reuse that process or relocate.
+pub fn reserve(name: &str, preferred: Option<u16>) -> Result<u16, String> {
+    let mut reserved = RESERVED.lock().unwrap();
+    if let Some(r) = reserved.get(name) {
+        if preferred.is_none() || preferred == Some(r.port) {
+            return Ok(r.port);
+        }
+    }
+
+    let mut cfg = AppConfig::load()?;
+    let start = preferred
+        .or_else(|| configured_port(&cfg, name))
+        .or_else(|| default_port(name))
+        .ok_or_else(|| format!("No port known for service '{}'; pass a preferred port", name))?;
+
+    let claimed = |port: u16| {
+        reserved
+            .iter()
+            .any(|(other, r)| other != name && r.port == port)
+    };
+
+    let port = if claimed(start) {
+        (start.saturating_add(1)..=start.saturating_add(SEARCH_RANGE))
+            .find(|&p| !claimed(p) && check_port_available(p))
+            .ok_or_else(|| format!("No free port near {} for '{}'", start, name))?
+    } else {
+        start
+    };
+
+    // Dropping an old reservation first lets us re-bind the same port
+    reserved.remove(name);
+    let guard = TcpListener::bind(("127.0.0.1", port)).ok();
+
+    if configured_port(&cfg, name) != Some(port) {
+        set_configured_port(&mut cfg, name, port);
+        cfg.save()?;
+    }
+
+    reserved.insert(name.to_string(), Reservation { port, guard });
+    Ok(port)
+}
+
+/// The port `name` is reserved on, else its configured or default port.
+/// Reserves and writes nothing, for previews.
+pub fn planned(cfg: &AppConfig, name: &str) -> Option<u16> {
+    if let Some(r) = RESERVED.lock().unwrap().get(name) {
+        return Some(r.port);
+    }
+    configured_port(cfg, name).or_else(|| default_port(name))
+}
+
+/// The port whose placeholder listener this process still holds for `name`.
+pub fn holding(name: &str) -> Option<u16> {
+    RESERVED
+        .lock()
+        .unwrap()
+        .get(name)
+        .filter(|r| r.guard.is_some())
+        .map(|r| r.port)
+}
+
+/// Free the placeholder listener so the service can bind; returns its port.
+pub fn release_for_launch(name: &str) -> Result<u16, String> {
+    let port = reserve(name, None)?;
+    if let Some(r) = RESERVED.lock().unwrap().get_mut(name) {
+        r.guard = None;
+    }
+    Ok(port)
+}
+
+/// Record that `name` ended up on a different port (e.g. after relocation).
+pub fn record(name: &str, port: u16) {
+    if let Err(e) = AppConfig::modify(|cfg| set_configured_port(cfg, name, port)) {
+        eprintln!("⚠ Failed to persist port {} for '{}': {}", port, name, e);
+    }
+
+    RESERVED
+        .lock()
+        .unwrap()
+        .insert(name.to_string(), Reservation { port, guard: None });
+}
+
+/// Reserve every known service port: n8n, Ollama and any configured extras.
+pub fn reserve_all() -> Result<PortConfig, String> {
+    let extras: Vec<String> = AppConfig::load()?.service_ports.into_keys().collect();
+
+    let n8n_port = reserve(N8N, None)?;
+    let ollama_port = reserve(OLLAMA, None)?;
+    let mut extra = BTreeMap::new();
+    for name in extras {
+        extra.insert(name.clone(), reserve(&name, None)?);
+    }
+
+    Ok(PortConfig {
+        n8n_port,
+        ollama_port,
+        extra,
+    })
+}
@@ -0,0 +1,693 @@
+// 🧩 Gignaati Workbench — Standard preflight checks
+//
+// One unit struct (or small enum) per check. Add a check by implementing
+// `PreflightCheck` and registering it in `standard()`.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+use sysinfo::System;
+use tauri::Manager;
+
+use super::{Outcome, Preflight, PreflightCheck, PreflightContext};
+use crate::config::{redact_url, redact_urls};
+use crate::installer::n8n_versions::registry_url;
+use crate::installer::node_runtime::node_mirror;
+use crate::installer::ollama_runtime::{ollama_mirror, OLLAMA_VERSION};
+use crate::ports::manager::check_port_available;
+use crate::ports::ownership::port_holder;
+use crate::ports::registry;
+use crate::supervisor::ServiceKind;
+use crate::system::detector::{check_volumes, disk_targets, VolumeCheck};
+use crate::version::{self, Verdict};
+
+const GIB: u64 = 1024 * 1024 * 1024;
+/// Node.js 18+ official Linux builds need glibc 2.28.
+const MIN_GLIBC: (u64, u64) = (2, 28);
+/// Node.js 18+ needs kernel 4.18 on Linux.
+const MIN_LINUX_KERNEL: (u64, u64) = (4, 18);
+/// Ollama's macOS app needs Big Sur.
+const MIN_MACOS: u64 = 11;
+/// Windows 10 1607 / Server 2016: oldest build Node.js supports.
+const MIN_WINDOWS_BUILD: u64 = 14393;
+/// Windows 10 22H2: oldest build Ollama supports.
+const RECOMMENDED_WINDOWS_BUILD: u64 = 19045;
+/// Antivirus scanners holding a fresh file longer than this slow installs noticeably.
+const SLOW_SCAN: Duration = Duration::from_secs(2);
+
+/// Every check the Workbench runs before installing.
+pub fn standard() -> Preflight {
+    Preflight::new()
+        .with(Ram)
+        .with(Disk)
+        .with(OsVersion)
+        .with(Architecture)
+        .with(WritablePaths)
+        .with(Mirror::Node)
+        .with(Mirror::Npm)
+        .with(Mirror::Ollama)
+        .with(Port(ServiceKind::N8n))
+        .with(Port(ServiceKind::Ollama))
+        .with(Antivirus)
+        .with(Glibc)
+        .with(Components)
+}
+
+// === Hardware ===
+
+pub struct Ram;
+
+impl PreflightCheck for Ram {
+    fn id(&self) -> &str {
+        "ram"
+    }
+
+    fn name(&self) -> &str {
+        "Memory"
+    }
+
+    fn run(&self, ctx: &PreflightContext) -> Outcome {
+        let mut sys = System::new();
+        sys.refresh_memory();
+        let total = sys.total_memory() as f64 / GIB as f64;
+        let available = sys.available_memory() as f64 / GIB as f64;
+        let measured = format!("{:.1} GB total, {:.1} GB available", total, available);
+        let required = format!("{} GB", ctx.min_ram_gb);
+
+        // Installed RAM is reported a little under its nominal size
+        if total + 0.5 < ctx.min_ram_gb as f64 {
+            return Outcome::error(format!("Only {:.1} GB of RAM installed.", total))
+                .measured(measured)
+                .required(required)
+                .remediation("Use a machine with more memory, or pick a smaller model from the recommendations.");
+        }
+        if available < 2.0 {
+            return Outcome::warning(format!("Only {:.1} GB of RAM is free right now.", available))
+                .measured(measured)
+                .required(required)
+                .remediation("Close memory-heavy applications before installing and loading models.");
+        }
+        Outcome::pass("Enough memory.").measured(measured).required(required)
+    }
+}
+
+pub struct Disk;
+
+impl PreflightCheck for Disk {
+    fn id(&self) -> &str {
+        "disk"
+    }
+
+    fn name(&self) -> &str {
+        "Disk space"
+    }
+
+    fn run(&self, ctx: &PreflightContext) -> Outcome {
+        let volumes = check_volumes(disk_targets(&ctx.app, ctx.min_disk_gb));
+        if volumes.is_empty() {
+            return Outcome::skipped("No install locations could be resolved.");
+        }
+
+        let label = |v: &VolumeCheck| {
+            v.mount_point.clone().unwrap_or_else(|| v.targets[0].path.clone())
+        };
+        let measured = volumes
+            .iter()
+            .map(|v| format!("{}: {} GB free", label(v), v.free_gb))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let required = volumes
+            .iter()
+            .map(|v| format!("{}: {} GB", label(v), v.required_gb))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        if let Some(short) = volumes.iter().find(|v| !v.passed) {
+            let names: Vec<&str> = short.targets.iter().map(|t| t.name.as_str()).collect();
+            return Outcome::error(format!(
+                "{} has {} GB free but {} need {} GB.",
+                label(short),
+                short.free_gb,
+                names.join(", "),
+                short.required_gb
+            ))
+            .measured(measured)
+            .required(required)
+            .remediation("Free up space on that volume or move the Ollama models directory (OLLAMA_MODELS) to a larger disk.");
+        }
+        if let Some(odd) = volumes.iter().find(|v| v.network || v.removable) {
+            let kind = if odd.network { "a network share" } else { "removable media" };
+            return Outcome::warning(format!("{} is on {}.", label(odd), kind))
+                .measured(measured)
+                .required(required)
+                .remediation("Install to a local fixed disk; network and removable volumes are slow and may disappear while services run.");
+        }
+        Outcome::pass("Enough free space on every install volume.")
+            .measured(measured)
+            .required(required)
+    }
+}
+
+// === Platform ===
+
+pub struct OsVersion;
+
+impl PreflightCheck for OsVersion {
+    fn id(&self) -> &str {
+        "os_version"
+    }
+
+    fn name(&self) -> &str {
+        "Operating system"
+    }
+
+    fn run(&self, _ctx: &PreflightContext) -> Outcome {
+        let long = System::long_os_version().unwrap_or_else(|| std::env::consts::OS.to_string());
+
+        if cfg!(target_os = "macos") {
+            let version = System::os_version().unwrap_or_default();
+            let measured = format!("{} ({})", long, version);
+            return match leading_numbers(&version).first() {
+                Some(&major) if major >= MIN_MACOS => Outcome::pass("Supported macOS version."),
+                Some(_) => Outcome::error(format!("macOS {} is too old.", version)).remediation(
+                    "Upgrade to macOS 11 Big Sur or later; Ollama does not run on older releases.",
+                ),
+                None => Outcome::warning("Could not determine the macOS version."),
+            }
+            .measured(measured)
+            .required(format!("macOS {}+", MIN_MACOS));
+        }
+
+        if cfg!(target_os = "windows") {
+            // sysinfo reports the build number as the kernel version
+            let build = System::kernel_version()
+                .and_then(|k| leading_numbers(&k).last().copied())
+                .unwrap_or(0);
+            let measured = format!("{} (build {})", long, build);
+            let required = format!("build {}+ (recommended {}+)", MIN_WINDOWS_BUILD, RECOMMENDED_WINDOWS_BUILD);
+            return if build == 0 {
+                Outcome::warning("Could not determine the Windows build.")
+            } else if build < MIN_WINDOWS_BUILD {
+                Outcome::error("This Windows build is too old for Node.js.")
+                    .remediation("Upgrade to Windows 10 22H2, Windows 11 or Windows Server 2016 or later.")
+            } else if build < RECOMMENDED_WINDOWS_BUILD {
+                Outcome::warning("Ollama officially supports Windows 10 22H2 and later.")
+                    .remediation("Install the latest Windows feature update.")
+            } else {
+                Outcome::pass("Supported Windows build.")
+            }
+            .measured(measured)
+            .required(required);
+        }
+
+        if cfg!(target_os = "linux") {
+            let kernel = System::kernel_version().unwrap_or_default();
+            let measured = format!("{} (kernel {})", long, kernel);
+            let required = format!("kernel {}.{}+", MIN_LINUX_KERNEL.0, MIN_LINUX_KERNEL.1);
+            return match leading_numbers(&kernel)[..] {
+                [major, minor, ..] if (major, minor) >= MIN_LINUX_KERNEL => {
+                    Outcome::pass("Supported Linux kernel.")
+                }
+                [_, _, ..] => Outcome::error(format!("Kernel {} is too old for Node.js.", kernel))
+                    .remediation("Upgrade to a distribution release with kernel 4.18 or later (e.g. RHEL 8, Ubuntu 20.04)."),
+                _ => Outcome::warning("Could not determine the kernel version."),
+            }
+            .measured(measured)
+            .required(required);
+        }
+
+        Outcome::error(format!("{} is not supported.", long))
+            .measured(long)
+            .required("Windows, macOS or Linux")
+    }
+}
+
+pub struct Architecture;
+
+impl PreflightCheck for Architecture {
+    fn id(&self) -> &str {
+        "architecture"
+    }
+
+    fn name(&self) -> &str {
+        "CPU architecture"
+    }
+
+    fn run(&self, _ctx: &PreflightContext) -> Outcome {
+        let arch = std::env::consts::ARCH;
+        let required = "x86_64 or aarch64";
+        match arch {
+            "x86_64" if !has_avx() => Outcome::warning("The CPU has no AVX support.")
+                .measured("x86_64 without AVX")
+                .required("x86_64 with AVX")
+                .remediation("Ollama falls back to a much slower code path; prefer small models or a newer machine."),
+            "x86_64" | "aarch64" => Outcome::pass("Supported architecture.")
+                .measured(arch)
+                .required(required),
+            _ => Outcome::error(format!("{} is not supported by Node.js and Ollama builds.", arch))
+                .measured(arch)
+                .required(required)
+                .remediation("Use a 64-bit x86 or ARM machine."),
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+fn has_avx() -> bool {
+    std::arch::is_x86_feature_detected!("avx")
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn has_avx() -> bool {
+    false
+}
+
+pub struct Glibc;
+
+impl PreflightCheck for Glibc {
+    fn id(&self) -> &str {
+        "glibc"
+    }
+
+    fn name(&self) -> &str {
+        "C library"
+    }
+
+    fn run(&self, _ctx: &PreflightContext) -> Outcome {
+        if !cfg!(target_os = "linux") {
+            return Outcome::skipped("Only relevant on Linux.");
+        }
+        let required = format!("glibc {}.{}+", MIN_GLIBC.0, MIN_GLIBC.1);
+
+        let Ok(output) = Command::new("ldd").arg("--version").output() else {
+            return Outcome::warning("Could not run `ldd --version`.")
+                .required(required)
+                .remediation("Install your distribution's libc-bin / glibc-common package.");
+        };
+        // glibc prints to stdout, musl to stderr
+        let text = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        if text.to_lowercase().contains("musl") {
+            return Outcome::error("This system uses musl libc.")
+                .measured("musl")
+                .required(required)
+                .remediation("Official Node.js and Ollama builds need glibc; use a glibc-based distribution or container.");
+        }
+
+        let first = text.lines().next().unwrap_or_default();
+        let version = first.split_whitespace().last().unwrap_or_default();
+        let outcome = match leading_numbers(version)[..] {
+            [major, minor, ..] if (major, minor) >= MIN_GLIBC => {
+                Outcome::pass("Supported glibc.").measured(format!("glibc {}", version))
+            }
+            [_, _, ..] => Outcome::error(format!("glibc {} is too old.", version))
+                .measured(format!("glibc {}", version))
+                .remediation("Upgrade to a distribution release with glibc 2.28 or later (e.g. Debian 10, Ubuntu 20.04, RHEL 8)."),
+            _ => Outcome::warning("Could not parse the glibc version.").measured(first.to_string()),
+        };
+        outcome.required(required)
+    }
+}
+
+// === Filesystem ===
+
+pub struct WritablePaths;
+
+impl PreflightCheck for WritablePaths {
+    fn id(&self) -> &str {
+        "writable_paths"
+    }
+
+    fn name(&self) -> &str {
+        "Install paths writable"
+    }
+
+    fn run(&self, ctx: &PreflightContext) -> Outcome {
+        let mut denied = Vec::new();
+        let mut checked = 0;
+        for (name, path, _) in disk_targets(&ctx.app, ctx.min_disk_gb) {
+            checked += 1;
+            if let Err(e) = probe_write(&path) {
+                denied.push(format!("{} ({}): {}", name, path.display(), e));
+            }
+        }
+
+        if denied.is_empty() {
+            return Outcome::pass(format!("All {} install locations are writable.", checked))
+                .measured(format!("{} of {} writable", checked, checked))
+                .required("all writable");
+        }
+        Outcome::error(format!("Cannot write to: {}", denied.join("; ")))
+            .measured(format!("{} of {} writable", checked - denied.len(), checked))
+            .required("all writable")
+            .remediation("Fix the directory permissions, or point the install at a user-writable location in the settings.")
+    }
+}
+
+/// Create and remove a probe file in `path` (or its nearest existing ancestor).
+fn probe_write(path: &Path) -> Result<(), String> {
+    let dir = path
+        .ancestors()
+        .find(|p| p.is_dir())
+        .ok_or("no existing parent directory")?;
+    let probe = dir.join(format!(".gignaati-preflight-{}", std::process::id()));
+    fs::write(&probe, b"probe").map_err(|e| e.to_string())?;
+    let _ = fs::remove_file(&probe);
+    Ok(())
+}
+
+pub struct Antivirus;
+
+impl PreflightCheck for Antivirus {
+    fn id(&self) -> &str {
+        "antivirus"
+    }
+
+    fn name(&self) -> &str {
+        "Antivirus interference"
+    }
+
+    fn run(&self, ctx: &PreflightContext) -> Outcome {
+        let Ok(dir) = ctx.app.path().app_data_dir() else {
+            return Outcome::skipped("App data directory is unavailable.");
+        };
+
+        if controlled_folder_access() {
+            return Outcome::warning("Windows Controlled Folder Access is enabled and may block installs.")
+                .measured("Controlled Folder Access on")
+                .required("Workbench allowed through Controlled Folder Access")
+                .remediation("Allow the Workbench, node.exe and ollama.exe under Windows Security › Ransomware protection.");
+        }
+
+        let started = Instant::now();
+        let result = probe_executable(&dir);
+        let took = started.elapsed();
+        let measured = format!("probe took {} ms", took.as_millis());
+
+        match result {
+            Err(e) => Outcome::warning(format!(
+                "A freshly written program file in {} was blocked: {}",
+                dir.display(),
+                e
+            ))
+            .measured(measured)
+            .required("files can be written, renamed and read back")
+            .remediation("Add the Workbench data directory to your antivirus exclusions."),
+            Ok(()) if took > SLOW_SCAN => Outcome::warning("New program files are scanned slowly.")
+                .measured(measured)
+                .required(format!("under {} ms", SLOW_SCAN.as_millis()))
+                .remediation("Add the Workbench data directory to your antivirus exclusions to speed up installs."),
+            Ok(()) => Outcome::pass("No interference with new program files.").measured(measured),
+        }
+    }
+}
+
+/// Write, rename, read back and delete an executable-looking file, the
+/// sequence installers perform and scanners tend to lock.
+fn probe_executable(dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let ext = if cfg!(target_os = "windows") { "exe" } else { "bin" };
+    let staged: PathBuf = dir.join(format!("preflight-{}.part", std::process::id()));
+    let final_path = staged.with_extension(ext);
+    let payload = b"gignaati preflight probe";
+
+    let result = (|| {
+        fs::write(&staged, payload).map_err(|e| e.to_string())?;
+        fs::rename(&staged, &final_path).map_err(|e| e.to_string())?;
+        match fs::read(&final_path) {
+            Ok(data) if data == payload => Ok(()),
+            Ok(_) => Err("contents were altered".to_string()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Err("file was removed".to_string()),
+            Err(e) => Err(e.to_string()),
+        }
+    })();
+
+    let _ = fs::remove_file(&staged);
+    if let Err(e) = fs::remove_file(&final_path) {
+        if result.is_ok() && e.kind() != ErrorKind::NotFound {
+            return Err(format!("could not delete probe: {}", e));
+        }
+    }
+    result
+}
+
+#[cfg(target_os = "windows")]
+fn controlled_folder_access() -> bool {
+    Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", "(Get-MpPreference).EnableControlledFolderAccess"])
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim() == "1")
+        .unwrap_or(false)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn controlled_folder_access() -> bool {
+    false
+}
+
+// === Network ===
+
+/// Download source for one component, reached through the same proxy
+/// settings the installer uses.
+pub enum Mirror {
+    Node,
+    Npm,
+    Ollama,
+}
+
+impl PreflightCheck for Mirror {
+    fn id(&self) -> &str {
+        match self {
+            Mirror::Node => "mirror:node",
+            Mirror::Npm => "mirror:npm",
+            Mirror::Ollama => "mirror:ollama",
+        }
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            Mirror::Node => "Node.js mirror",
+            Mirror::Npm => "npm registry",
+            Mirror::Ollama => "Ollama mirror",
+        }
+    }
+
+    fn run(&self, ctx: &PreflightContext) -> Outcome {
+        let cfg = &ctx.config;
+        let (url, setting, installed) = match self {
+            Mirror::Node => (
+                format!("{}/index.json", node_mirror().trim_end_matches('/')),
+                "node_mirror",
+                cfg.node_runtime_dir.is_some(),
+            ),
+            Mirror::Npm => (format!("{}/-/ping", registry_url(cfg)), "npm_registry", cfg.n8n_installed),
+            Mirror::Ollama => (
+                format!("{}/v{}/sha256sum.txt", ollama_mirror().trim_end_matches('/'), OLLAMA_VERSION),
+                "ollama_mirror",
+                cfg.ollama_installed,
+            ),
+        };
+        let via = match env_proxy() {
+            Some(proxy) => format!("via proxy {}", proxy),
+            None => "direct".to_string(),
+        };
+
+        let agent = ureq::AgentBuilder::new()
+            .try_proxy_from_env(true)
+            .timeout_connect(Duration::from_secs(5))
+            .timeout_read(Duration::from_secs(10))
+            .build();
+        let started = Instant::now();
+        let status = match agent.get(&url).call() {
+            Ok(response) => Ok(response.status()),
+            Err(ureq::Error::Status(code, _)) => Ok(code),
+            Err(e) => Err(redact_urls(&e.to_string())),
+        };
+        let url = redact_url(&url);
+        let measured = |what: String| format!("{} ({}, {} ms)", what, via, started.elapsed().as_millis());
+
+        match status {
+            Ok(code) if code < 400 => Outcome::pass(format!("{} is reachable.", url))
+                .measured(measured(format!("HTTP {}", code)))
+                .required("HTTP 2xx/3xx"),
+            Ok(code) => Outcome::warning(format!("{} answered HTTP {}.", url, code))
+                .measured(measured(format!("HTTP {}", code)))
+                .required("HTTP 2xx/3xx")
+                .remediation(format!("Check that `{}` points at a complete mirror.", setting)),
+            Err(e) => {
+                let outcome = if installed {
+                    Outcome::warning(format!("{} is unreachable; only needed for updates.", url))
+                } else {
+                    Outcome::error(format!("{} is unreachable.", url))
+                };
+                outcome
+                    .measured(measured(e))
+                    .required("reachable")
+                    .remediation(format!(
+                        "Check the network and the HTTPS_PROXY setting, or set `{}` to a reachable local mirror.",
+                        setting
+                    ))
+            }
+        }
+    }
+}
+
+/// Proxy the download agent will pick up, credentials removed.
+fn env_proxy() -> Option<String> {
+    // Same order ureq uses
+    let raw = ["ALL_PROXY", "all_proxy", "HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy"]
+        .iter()
+        .find_map(|key| std::env::var(key).ok().filter(|v| !v.trim().is_empty()))?;
+    Some(match raw.split_once('@') {
+        Some((userinfo, host)) => {
+            let scheme = userinfo.split_once("://").map(|(s, _)| format!("{}://", s));
+            format!("{}{}", scheme.unwrap_or_default(), host)
+        }
+        None => raw,
+    })
+}
+
+pub struct Port(pub ServiceKind);
+
+impl PreflightCheck for Port {
+    fn id(&self) -> &str {
+        match self.0 {
+            ServiceKind::N8n => "port:n8n",
+            ServiceKind::Ollama => "port:ollama",
+        }
+    }
+
+    fn name(&self) -> &str {
+        match self.0 {
+            ServiceKind::N8n => "n8n port",
+            ServiceKind::Ollama => "Ollama port",
+        }
+    }
+
+    fn run(&self, ctx: &PreflightContext) -> Outcome {
+        let (port, setting, marker, service) = match self.0 {
+            ServiceKind::N8n => (ctx.config.n8n_port.unwrap_or(5678), "n8n_port", "n8n", registry::N8N),
+            ServiceKind::Ollama => (ctx.config.ollama_port.unwrap_or(11434), "ollama_port", "ollama", registry::OLLAMA),
+        };
+        let required = format!("port {} free or held by {}", port, marker);
+
+        if check_port_available(port) {
+            return Outcome::pass(format!("Port {} is free.", port))
+                .measured("free")
+                .required(required);
+        }
+        // The registry's placeholder listener keeps the port until launch
+        let holder = port_holder(port);
+        if registry::holding(service) == Some(port)
+            || holder.as_ref().is_some_and(|h| h.pid == std::process::id())
+        {
+            return Outcome::pass(format!("Port {} is reserved for {} by the Workbench.", port, marker))
+                .measured("reserved")
+                .required(required);
+        }
+        let Some(holder) = holder else {
+            return Outcome::warning(format!("Port {} is in use by an unidentified process.", port))
+                .measured("in use")
+                .required(required)
+                .remediation(format!(
+                    "The Workbench will move to the next free port; set `{}` to choose one yourself.",
+                    setting
+                ));
+        };
+
+        let ours = holder.managed == Some(self.0)
+            || [&holder.name, &holder.exe, &holder.cmdline]
+                .into_iter()
+                .flatten()
+                .any(|s| s.to_lowercase().contains(marker));
+        if ours {
+            return Outcome::pass(format!("Port {} is already served by {}.", port, marker))
+                .measured(format!("held by {}", holder.describe()))
+                .required(required);
+        }
+        Outcome::warning(format!("Port {} is held by {}.", port, holder.describe()))
+            .measured(format!("held by {}", holder.describe()))
+            .required(required)
+            .remediation(format!(
+                "Stop {} or set `{}` to a free port; otherwise the Workbench relocates to the next free one.",
+                holder.describe(),
+                setting
+            ))
+    }
+}
+
+// === Components ===
+
+pub struct Components;
+
+impl PreflightCheck for Components {
+    fn id(&self) -> &str {
+        "components"
+    }
+
+    fn name(&self) -> &str {
+        "Installed component versions"
+    }
+
+    fn run(&self, _ctx: &PreflightContext) -> Outcome {
+        let findings = version::check_installed();
+        let Some(worst) = findings.iter().map(|f| f.verdict).max() else {
+            return Outcome::skipped("No components installed yet.");
+        };
+
+        let measured = findings
+            .iter()
+            .map(|f| format!("{} {}", f.component, f.found.as_deref().unwrap_or("?")))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let required = findings
+            .iter()
+            .filter_map(|f| f.required.as_ref().map(|r| format!("{} {}", f.component, r)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let problems: Vec<&str> = findings
+            .iter()
+            .filter(|f| f.verdict != Verdict::Ok)
+            .map(|f| f.message.as_str())
+            .collect();
+
+        let outcome = match worst {
+            Verdict::Ok => Outcome::pass("All installed components are compatible."),
+            Verdict::UpgradeRecommended => Outcome::warning(problems.join(" "))
+                .remediation("Upgrade the listed components from the Workbench when convenient."),
+            Verdict::Blocking => Outcome::error(problems.join(" "))
+                .remediation("Upgrade the listed components before starting the services."),
+        };
+        outcome.measured(measured).required(required)
+    }
+}
+
+// === Helpers ===
+
+/// Numeric components at the start of a version string: `6.1.0-18-amd64`
+/// → `[6, 1, 0, 18]`, `14.2.1` → `[14, 2, 1]`.
+fn leading_numbers(text: &str) -> Vec<u64> {
+    text.split(|c: char| !c.is_ascii_digit())
+        .take_while(|part| !part.is_empty())
+        .filter_map(|part| part.parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leading_numbers_stop_at_the_first_word() {
+        assert_eq!(leading_numbers("6.1.0-18-amd64"), [6, 1, 0, 18]);
+        assert_eq!(leading_numbers("14.2.1"), [14, 2, 1]);
+        assert_eq!(leading_numbers("5.15.153.1-microsoft-standard-WSL2"), [5, 15, 153, 1]);
+        assert!(leading_numbers("").is_empty());
+        assert!(leading_numbers("unknown").is_empty());
+    }
+}
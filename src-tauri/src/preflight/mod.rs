@@ -0,0 +1,401 @@
+// 🧩 Gignaati Workbench — Preflight checks
+//
+// Every probe the installer relies on (RAM, disk, OS, architecture, writable
+// directories, mirror reachability, ports, antivirus interference, glibc,
+// component versions) is a `PreflightCheck`. `Preflight` runs a set of them
+// in parallel under one deadline and folds the outcomes into a single
+// report; a check that does not finish in time is reported as timed out.
+
+pub mod checks;
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+use crate::config::AppConfig;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(20);
+const DEFAULT_MIN_RAM_GB: u64 = 8;
+const DEFAULT_MIN_DISK_GB: u64 = 20;
+
+static LAST_REPORT: Lazy<Mutex<Option<PreflightReport>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Pass,
+    /// Not applicable on this platform / configuration.
+    Skipped,
+    Warning,
+    Error,
+}
+
+/// What a check found.
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub severity: Severity,
+    pub message: String,
+    pub measured: Option<String>,
+    pub required: Option<String>,
+    pub remediation: Option<String>,
+}
+
+impl Outcome {
+    fn new(severity: Severity, message: impl Into<String>) -> Self {
+        Outcome {
+            severity,
+            message: message.into(),
+            measured: None,
+            required: None,
+            remediation: None,
+        }
+    }
+
+    pub fn pass(message: impl Into<String>) -> Self {
+        Self::new(Severity::Pass, message)
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, message)
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, message)
+    }
+
+    pub fn skipped(message: impl Into<String>) -> Self {
+        Self::new(Severity::Skipped, message)
+    }
+
+    pub fn measured(mut self, value: impl Into<String>) -> Self {
+        self.measured = Some(value.into());
+        self
+    }
+
+    pub fn required(mut self, value: impl Into<String>) -> Self {
+        self.required = Some(value.into());
+        self
+    }
+
+    pub fn remediation(mut self, hint: impl Into<String>) -> Self {
+        self.remediation = Some(hint.into());
+        self
+    }
+}
+
+/// Inputs shared by every check.
+pub struct PreflightContext {
+    pub app: AppHandle,
+    pub config: AppConfig,
+    pub min_ram_gb: u64,
+    /// Budget for Ollama models; install prefixes add their own needs.
+    pub min_disk_gb: u64,
+}
+
+/// One pluggable probe. Checks run on their own thread and must not touch
+/// the system beyond what they measure (probe files are cleaned up).
+pub trait PreflightCheck<C = PreflightContext>: Send + Sync {
+    /// Stable identifier, e.g. `ram` or `port:n8n`.
+    fn id(&self) -> &str;
+    /// Name shown in the UI.
+    fn name(&self) -> &str;
+    fn run(&self, ctx: &C) -> Outcome;
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct CheckResult {
+    pub id: String,
+    pub name: String,
+    pub severity: Severity,
+    pub message: String,
+    pub measured: Option<String>,
+    pub required: Option<String>,
+    pub remediation: Option<String>,
+    pub duration_ms: u64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct PreflightReport {
+    /// No check ended in `error`.
+    pub passed: bool,
+    pub errors: usize,
+    pub warnings: usize,
+    /// Unix seconds.
+    pub generated_at: u64,
+    pub os: String,
+    pub arch: String,
+    pub timeout_ms: u64,
+    pub duration_ms: u64,
+    pub checks: Vec<CheckResult>,
+}
+
+/// A set of checks. Generic over the context only so the runner can be
+/// exercised without an `AppHandle`.
+pub struct Preflight<C = PreflightContext> {
+    checks: Vec<Arc<dyn PreflightCheck<C>>>,
+}
+
+impl<C: Send + Sync + 'static> Preflight<C> {
+    pub fn new() -> Self {
+        Preflight { checks: Vec::new() }
+    }
+
+    pub fn with(mut self, check: impl PreflightCheck<C> + 'static) -> Self {
+        self.checks.push(Arc::new(check));
+        self
+    }
+
+    /// Run every check in parallel; whatever has not answered by `timeout`
+    /// is reported as timed out (its thread is left to finish on its own).
+    pub fn run(&self, ctx: C, timeout: Duration) -> PreflightReport {
+        let started = Instant::now();
+        let deadline = started + timeout;
+        let ctx = Arc::new(ctx);
+        let (tx, rx) = mpsc::channel();
+
+        for (index, check) in self.checks.iter().enumerate() {
+            let (check, ctx, tx) = (Arc::clone(check), Arc::clone(&ctx), tx.clone());
+            thread::spawn(move || {
+                let begun = Instant::now();
+                let outcome = panic::catch_unwind(AssertUnwindSafe(|| check.run(&ctx)))
+                    .unwrap_or_else(|_| Outcome::error("The check crashed."));
+                let _ = tx.send((index, outcome, begun.elapsed()));
+            });
+        }
+        drop(tx);
+
+        let mut finished: Vec<Option<(Outcome, Duration)>> = vec![None; self.checks.len()];
+        while finished.iter().any(Option::is_none) {
+            let left = deadline.saturating_duration_since(Instant::now());
+            match rx.recv_timeout(left) {
+                Ok((index, outcome, took)) => finished[index] = Some((outcome, took)),
+                Err(_) => break,
+            }
+        }
+
+        let checks: Vec<CheckResult> = self
+            .checks
+            .iter()
+            .zip(finished)
+            .map(|(check, done)| {
+                let (outcome, took) = done.unwrap_or_else(|| {
+                    let outcome = Outcome::warning(format!(
+                        "Did not finish within {} s.",
+                        timeout.as_secs()
+                    ))
+                    .remediation("Run the preflight again; if it keeps timing out, check network and disk responsiveness.");
+                    (outcome, timeout)
+                });
+                CheckResult {
+                    id: check.id().to_string(),
+                    name: check.name().to_string(),
+                    severity: outcome.severity,
+                    message: outcome.message,
+                    measured: outcome.measured,
+                    required: outcome.required,
+                    remediation: outcome.remediation,
+                    duration_ms: took.as_millis() as u64,
+                }
+            })
+            .collect();
+
+        let count = |severity| checks.iter().filter(|c| c.severity == severity).count();
+        let errors = count(Severity::Error);
+        PreflightReport {
+            passed: errors == 0,
+            errors,
+            warnings: count(Severity::Warning),
+            generated_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            timeout_ms: timeout.as_millis() as u64,
+            duration_ms: started.elapsed().as_millis() as u64,
+            checks,
+        }
+    }
+}
+
+/// Run `checks::standard()` and remember the report for export.
+fn run_standard(
+    app: AppHandle,
+    min_ram_gb: Option<u64>,
+    min_disk_gb: Option<u64>,
+    timeout_secs: Option<u64>,
+) -> Result<PreflightReport, String> {
+    let ctx = PreflightContext {
+        app,
+        config: AppConfig::load()?,
+        min_ram_gb: min_ram_gb.unwrap_or(DEFAULT_MIN_RAM_GB),
+        min_disk_gb: min_disk_gb.unwrap_or(DEFAULT_MIN_DISK_GB),
+    };
+    let timeout = timeout_secs.map(Duration::from_secs).unwrap_or(DEFAULT_TIMEOUT);
+    let report = checks::standard().run(ctx, timeout);
+    *LAST_REPORT.lock().unwrap() = Some(report.clone());
+    Ok(report)
+}
+
+/// The last report of this session, or a fresh run with default thresholds.
+pub(crate) fn latest_report(app: &AppHandle) -> Result<PreflightReport, String> {
+    if let Some(report) = LAST_REPORT.lock().unwrap().clone() {
+        return Ok(report);
+    }
+    run_standard(app.clone(), None, None, None)
+}
+
+// === Commands ===
+
+/// 🛫 Run the standard preflight checks in parallel
+#[tauri::command]
+pub async fn run_preflight(
+    app: AppHandle,
+    min_ram_gb: Option<u64>,
+    min_disk_gb: Option<u64>,
+    timeout_secs: Option<u64>,
+) -> Result<PreflightReport, String> {
+    tauri::async_runtime::spawn_blocking(move || run_standard(app, min_ram_gb, min_disk_gb, timeout_secs))
+        .await
+        .map_err(|e| format!("Preflight failed: {}", e))?
+}
+
+/// 💾 Write the last preflight report as JSON to `path` (default:
+/// `<app data>/reports/preflight-<timestamp>.json`); returns the file written.
+#[tauri::command]
+pub fn export_preflight_report(app: AppHandle, path: Option<String>) -> Result<String, String> {
+    let report = LAST_REPORT
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or("No preflight report yet; run the preflight first.")?;
+
+    let path = match path {
+        Some(p) => PathBuf::from(p),
+        None => app
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to get app data dir: {}", e))?
+            .join("reports")
+            .join(format!("preflight-{}.json", report.generated_at)),
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sleeps, then answers with `outcome` (or panics when there is none).
+    struct Stub {
+        id: &'static str,
+        delay: Duration,
+        outcome: Option<fn() -> Outcome>,
+    }
+
+    impl PreflightCheck<()> for Stub {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn name(&self) -> &str {
+            self.id
+        }
+
+        fn run(&self, _ctx: &()) -> Outcome {
+            thread::sleep(self.delay);
+            match self.outcome {
+                Some(outcome) => outcome(),
+                None => panic!("stub check exploded"),
+            }
+        }
+    }
+
+    fn stub(id: &'static str, delay_ms: u64, outcome: Option<fn() -> Outcome>) -> Stub {
+        Stub {
+            id,
+            delay: Duration::from_millis(delay_ms),
+            outcome,
+        }
+    }
+
+    fn ids(report: &PreflightReport) -> Vec<&str> {
+        report.checks.iter().map(|c| c.id.as_str()).collect()
+    }
+
+    #[test]
+    fn results_keep_declaration_order() {
+        let report = Preflight::new()
+            .with(stub("slow", 300, Some(|| Outcome::pass("slow"))))
+            .with(stub("medium", 150, Some(|| Outcome::pass("medium"))))
+            .with(stub("fast", 0, Some(|| Outcome::pass("fast"))))
+            .run((), Duration::from_secs(5));
+
+        assert_eq!(ids(&report), ["slow", "medium", "fast"]);
+        let messages: Vec<&str> = report.checks.iter().map(|c| c.message.as_str()).collect();
+        assert_eq!(messages, ["slow", "medium", "fast"]);
+        assert!(report.checks[0].duration_ms >= 300);
+    }
+
+    #[test]
+    fn checks_past_the_deadline_time_out_as_warnings() {
+        let started = Instant::now();
+        let report = Preflight::new()
+            .with(stub("quick", 0, Some(|| Outcome::pass("ok"))))
+            .with(stub("stuck", 5_000, Some(|| Outcome::pass("too late"))))
+            .run((), Duration::from_millis(200));
+
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert_eq!(ids(&report), ["quick", "stuck"]);
+        assert_eq!(report.checks[0].severity, Severity::Pass);
+        let stuck = &report.checks[1];
+        assert_eq!(stuck.severity, Severity::Warning);
+        assert!(stuck.message.starts_with("Did not finish within"), "{}", stuck.message);
+        assert!(stuck.remediation.is_some());
+        assert_eq!(stuck.duration_ms, 200);
+    }
+
+    #[test]
+    fn a_panicking_check_is_reported_as_crashed() {
+        let report = Preflight::new()
+            .with(stub("boom", 0, None))
+            .run((), Duration::from_secs(5));
+
+        assert_eq!(report.checks[0].severity, Severity::Error);
+        assert_eq!(report.checks[0].message, "The check crashed.");
+        assert!(!report.passed);
+    }
+
+    #[test]
+    fn errors_and_warnings_are_counted() {
+        let report = Preflight::new()
+            .with(stub("pass", 0, Some(|| Outcome::pass("ok"))))
+            .with(stub("skip", 0, Some(|| Outcome::skipped("n/a"))))
+            .with(stub("warn", 0, Some(|| Outcome::warning("hm"))))
+            .with(stub("err1", 0, Some(|| Outcome::error("no"))))
+            .with(stub("err2", 0, Some(|| Outcome::error("no"))))
+            .run((), Duration::from_secs(5));
+
+        assert_eq!(report.errors, 2);
+        assert_eq!(report.warnings, 1);
+        assert!(!report.passed);
+
+        let clean = Preflight::new()
+            .with(stub("pass", 0, Some(|| Outcome::pass("ok"))))
+            .with(stub("warn", 0, Some(|| Outcome::warning("hm"))))
+            .run((), Duration::from_secs(5));
+        assert_eq!(clean.errors, 0);
+        assert!(clean.passed);
+    }
+}
@@ -0,0 +1,145 @@
+// 🧩 Gignaati Workbench — Service readiness probes
+//
+// After a launch we poll the service's own health endpoint (Ollama's
+// /api/version, n8n's /healthz) until it answers 2xx or a deadline passes,
+// instead of sleeping a fixed number of seconds and hoping.
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::fmt;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::supervisor::{self, ServiceKind, ServiceState};
+
+pub const OLLAMA_READY_TIMEOUT: Duration = Duration::from_secs(30);
+/// First n8n start runs database migrations and can take a while.
+pub const N8N_READY_TIMEOUT: Duration = Duration::from_secs(120);
+const PROBE_INTERVAL: Duration = Duration::from_millis(500);
+
+static AGENT: Lazy<ureq::Agent> = Lazy::new(|| {
+    ureq::AgentBuilder::new()
+        .timeout_connect(Duration::from_secs(1))
+        .timeout(Duration::from_secs(2))
+        .build()
+});
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReadinessError {
+    /// The endpoint never answered successfully before the deadline.
+    TimedOut {
+        service: String,
+        url: String,
+        waited_secs: u64,
+        last_error: String,
+        recent_logs: Vec<String>,
+    },
+    /// The supervised process died while we were waiting.
+    Exited {
+        service: String,
+        exit_code: Option<i32>,
+        recent_logs: Vec<String>,
+    },
+}
+
+impl fmt::Display for ReadinessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let logs = match self {
+            ReadinessError::TimedOut {
+                service,
+                url,
+                waited_secs,
+                last_error,
+                recent_logs,
+            } => {
+                write!(
+                    f,
+                    "❌ {} did not become ready at {} within {}s (last error: {})",
+                    service, url, waited_secs, last_error
+                )?;
+                recent_logs
+            }
+            ReadinessError::Exited {
+                service,
+                exit_code,
+                recent_logs,
+            } => {
+                let code = exit_code
+                    .map(|c| c.to_string())
+                    .unwrap_or_else(|| "signal".into());
+                write!(f, "❌ {} exited (code {}) before becoming ready", service, code)?;
+                recent_logs
+            }
+        };
+        if !logs.is_empty() {
+            write!(f, "\nRecent output:")?;
+            for line in logs {
+                write!(f, "\n  {}", line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ReadinessError {}
+
+/// Health endpoint for a service on the given port.
+pub fn health_url(kind: ServiceKind, port: u16) -> String {
+    match kind {
+        ServiceKind::Ollama => format!("http://127.0.0.1:{}/api/version", port),
+        ServiceKind::N8n => format!("http://127.0.0.1:{}/healthz", port),
+    }
+}
+
+/// A single probe: `Ok` when the health endpoint answers 2xx.
+pub fn probe(kind: ServiceKind, port: u16) -> Result<(), String> {
+    AGENT
+        .get(&health_url(kind, port))
+        .call()
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Poll until the service is ready. Returns how long it took.
+///
+/// Fails early if the supervised process exits for good while we wait; a
+/// crash that is about to be restarted keeps the wait going.
+pub fn wait_until_ready(
+    kind: ServiceKind,
+    port: u16,
+    timeout: Duration,
+) -> Result<Duration, ReadinessError> {
+    let started = Instant::now();
+
+    loop {
+        let last_error = match probe(kind, port) {
+            Ok(()) => return Ok(started.elapsed()),
+            Err(e) => e,
+        };
+
+        if let Some(status) = supervisor::status(kind) {
+            let dead = status.pid.is_none()
+                && matches!(status.state, ServiceState::Crashed | ServiceState::Stopped);
+            if dead {
+                return Err(ReadinessError::Exited {
+                    service: kind.label().into(),
+                    exit_code: status.exit_code,
+                    recent_logs: supervisor::recent_logs(kind),
+                });
+            }
+        }
+
+        if started.elapsed() >= timeout {
+            return Err(ReadinessError::TimedOut {
+                service: kind.label().into(),
+                url: health_url(kind, port),
+                waited_secs: timeout.as_secs(),
+                last_error,
+                recent_logs: supervisor::recent_logs(kind),
+            });
+        }
+
+        thread::sleep(PROBE_INTERVAL);
+    }
+}
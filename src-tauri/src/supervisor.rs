@@ -0,0 +1,530 @@
+// 🧩 Gignaati Workbench — Service supervisor
+//
+// Owns the long-running n8n and Ollama child processes. Each service moves
+// through Starting → Running ⇄ Unhealthy (judged by the readiness probe). An
+// unexpected exit moves it to Restarting while it waits out an exponential
+// backoff, and to Crashed once the restart budget is spent. Stopping asks the
+// process to exit (SIGTERM, or CTRL_BREAK on Windows) before killing it.
+// Every transition is emitted as `service-state`.
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::ffi::OsString;
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+use crate::config::AppConfig;
+use crate::logging::LogLevel;
+use crate::ports::registry;
+use crate::readiness;
+
+/// Lines of output kept per service (and attached to crash reports).
+const LOG_TAIL_LINES: usize = 50;
+const DEFAULT_MAX_RESTARTS: u8 = 5;
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const BACKOFF_CAP_SECS: u64 = 60;
+/// A service that stays up this long gets its restart budget back.
+const STABLE_AFTER: Duration = Duration::from_secs(60);
+/// How long a stopping service may take to shut down before it is killed.
+const STOP_GRACE: Duration = Duration::from_secs(10);
+
+#[derive(Serialize, Clone)]
+struct ComponentLog {
+    component: String,
+    message: String,
+    /// Service output has no emoji prefix to classify it by.
+    level: LogLevel,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceKind {
+    N8n,
+    Ollama,
+}
+
+impl ServiceKind {
+    /// Registry / config key for this service.
+    pub fn id(self) -> &'static str {
+        match self {
+            ServiceKind::N8n => registry::N8N,
+            ServiceKind::Ollama => registry::OLLAMA,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ServiceKind::N8n => "Agentic Platform (n8n)",
+            ServiceKind::Ollama => "Ollama Server",
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceState {
+    Starting,
+    Running,
+    /// Process alive but its port stopped answering.
+    Unhealthy,
+    /// Exited without being asked to; waiting out the backoff before a respawn.
+    Restarting,
+    /// Exited without being asked to and will not be restarted.
+    Crashed,
+    Stopped,
+}
+
+/// How to (re)spawn a service.
+#[derive(Debug, Clone)]
+pub struct ServiceSpec {
+    pub kind: ServiceKind,
+    pub program: String,
+    pub args: Vec<String>,
+    pub env: Vec<(String, OsString)>,
+    /// Port the service should answer on once it is up.
+    pub port: u16,
+}
+
+impl ServiceSpec {
+    fn command(&self) -> Command {
+        let mut cmd = Command::new(&self.program);
+        cmd.args(&self.args)
+            .envs(self.env.iter().map(|(k, v)| (k, v)))
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        // Own process group, so CTRL_BREAK reaches only this service
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+            cmd.creation_flags(CREATE_NEW_PROCESS_GROUP);
+        }
+        cmd
+    }
+}
+
+/// Snapshot of one service; also the `service-state` event payload.
+#[derive(Serialize, Debug, Clone)]
+pub struct ServiceStatus {
+    pub service: ServiceKind,
+    pub label: String,
+    pub state: ServiceState,
+    pub pid: Option<u32>,
+    pub port: Option<u16>,
+    pub restarts: u32,
+    pub exit_code: Option<i32>,
+    /// Output captured around the last crash; empty otherwise.
+    pub crash_log: Vec<String>,
+    pub message: Option<String>,
+}
+
+struct Service {
+    spec: ServiceSpec,
+    child: Option<Child>,
+    state: ServiceState,
+    /// Consecutive restarts since the service was last stable.
+    attempts: u32,
+    restarts: u32,
+    exit_code: Option<i32>,
+    crash_log: Vec<String>,
+    logs: Arc<Mutex<VecDeque<String>>>,
+    running_since: Option<Instant>,
+    /// Bumped on every start/stop so stale monitor threads exit.
+    generation: u64,
+}
+
+impl Service {
+    fn status(&self, message: Option<String>) -> ServiceStatus {
+        ServiceStatus {
+            service: self.spec.kind,
+            label: self.spec.kind.label().into(),
+            state: self.state,
+            pid: self.child.as_ref().map(|c| c.id()),
+            port: Some(self.spec.port),
+            restarts: self.restarts,
+            exit_code: self.exit_code,
+            crash_log: self.crash_log.clone(),
+            message,
+        }
+    }
+
+    fn set_state(&mut self, app: &AppHandle, state: ServiceState, message: Option<String>) {
+        if self.state == state && message.is_none() {
+            return;
+        }
+        self.state = state;
+        app.emit("service-state", self.status(message)).ok();
+    }
+
+    fn spawn(&mut self, app: &AppHandle) -> Result<u32, String> {
+        let mut child = self
+            .spec
+            .command()
+            .spawn()
+            .map_err(|e| format!("Failed to start {}: {}", self.spec.kind.label(), e))?;
+
+        if let Some(stdout) = child.stdout.take() {
+            pipe_output(app, self.spec.kind, stdout, self.logs.clone());
+        }
+        if let Some(stderr) = child.stderr.take() {
+            pipe_output(app, self.spec.kind, stderr, self.logs.clone());
+        }
+
+        let pid = child.id();
+        self.child = Some(child);
+        self.running_since = None;
+        Ok(pid)
+    }
+}
+
+static SERVICES: Lazy<Mutex<HashMap<ServiceKind, Service>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Spawn a service under supervision. If it is already alive, returns its PID.
+pub fn start(app: &AppHandle, spec: ServiceSpec) -> Result<u32, String> {
+    let kind = spec.kind;
+    let mut services = SERVICES.lock().unwrap();
+
+    if let Some(svc) = services.get_mut(&kind) {
+        if let Some(child) = svc.child.as_mut() {
+            if matches!(child.try_wait(), Ok(None)) {
+                return Ok(child.id());
+            }
+        }
+    }
+
+    let generation = services.get(&kind).map(|s| s.generation + 1).unwrap_or(0);
+    let mut svc = Service {
+        spec,
+        child: None,
+        state: ServiceState::Stopped,
+        attempts: 0,
+        restarts: 0,
+        exit_code: None,
+        crash_log: Vec::new(),
+        logs: Arc::new(Mutex::new(VecDeque::with_capacity(LOG_TAIL_LINES))),
+        running_since: None,
+        generation,
+    };
+    let pid = svc.spawn(app)?;
+    svc.set_state(app, ServiceState::Starting, None);
+    services.insert(kind, svc);
+    drop(services);
+
+    let app = app.clone();
+    thread::spawn(move || monitor(app, kind, generation));
+    Ok(pid)
+}
+
+/// Stop a supervised service: ask it to exit, wait up to `STOP_GRACE`, then
+/// kill it. Returns `true` if a live process was stopped, `false` if there was
+/// nothing running (never started, or already exited).
+pub fn stop(app: &AppHandle, kind: ServiceKind) -> bool {
+    // Detach the child first so the status stays readable while it shuts down
+    let (child, generation) = {
+        let mut services = SERVICES.lock().unwrap();
+        let Some(svc) = services.get_mut(&kind) else {
+            return false;
+        };
+        svc.generation += 1;
+        svc.running_since = None;
+        (svc.child.take(), svc.generation)
+    };
+
+    let was_running = match child {
+        Some(mut child) => {
+            let alive = matches!(child.try_wait(), Ok(None));
+            if alive {
+                terminate(&mut child);
+            }
+            let _ = child.wait();
+            alive
+        }
+        None => false,
+    };
+
+    let mut services = SERVICES.lock().unwrap();
+    if let Some(svc) = services.get_mut(&kind).filter(|s| s.generation == generation) {
+        svc.set_state(app, ServiceState::Stopped, None);
+    }
+    was_running
+}
+
+/// Ask `child` to exit and give it `STOP_GRACE` to do so; kill it otherwise.
+fn terminate(child: &mut Child) {
+    if request_exit(child) {
+        let deadline = Instant::now() + STOP_GRACE;
+        while Instant::now() < deadline {
+            if !matches!(child.try_wait(), Ok(None)) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+    }
+    let _ = child.kill();
+}
+
+/// Send SIGTERM. Returns whether the request was delivered.
+#[cfg(unix)]
+fn request_exit(child: &Child) -> bool {
+    Command::new("kill")
+        .args(["-TERM", &child.id().to_string()])
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+/// Send CTRL_BREAK to the child's process group. Returns whether the request
+/// was delivered (it is not when we have no console to share).
+#[cfg(windows)]
+fn request_exit(child: &Child) -> bool {
+    use windows_sys::Win32::System::Console::{GenerateConsoleCtrlEvent, CTRL_BREAK_EVENT};
+    // SAFETY: plain Win32 call; the group id is the child's PID (see `ServiceSpec::command`)
+    unsafe { GenerateConsoleCtrlEvent(CTRL_BREAK_EVENT, child.id()) != 0 }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn request_exit(_child: &Child) -> bool {
+    false
+}
+
+pub fn status(kind: ServiceKind) -> Option<ServiceStatus> {
+    SERVICES.lock().unwrap().get(&kind).map(|s| s.status(None))
+}
+
+/// Last captured output lines of a service, oldest first.
+pub fn recent_logs(kind: ServiceKind) -> Vec<String> {
+    SERVICES
+        .lock()
+        .unwrap()
+        .get(&kind)
+        .map(|s| s.logs.lock().unwrap().iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// 📋 State of every supervised service
+#[tauri::command]
+pub fn get_service_status() -> Vec<ServiceStatus> {
+    let services = SERVICES.lock().unwrap();
+    [ServiceKind::Ollama, ServiceKind::N8n]
+        .into_iter()
+        .filter_map(|k| services.get(&k).map(|s| s.status(None)))
+        .collect()
+}
+
+// === Monitor ===
+
+fn monitor(app: AppHandle, kind: ServiceKind, generation: u64) {
+    let max_restarts = AppConfig::load_or_default()
+        .service_max_restarts
+        .unwrap_or(DEFAULT_MAX_RESTARTS) as u32;
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let mut services = SERVICES.lock().unwrap();
+        let Some(svc) = services.get_mut(&kind) else {
+            return;
+        };
+        if svc.generation != generation {
+            return;
+        }
+        let Some(child) = svc.child.as_mut() else {
+            return;
+        };
+
+        let exit = match child.try_wait() {
+            Ok(Some(status)) => status,
+            Ok(None) => {
+                // Probe over HTTP without holding the lock
+                let port = svc.spec.port;
+                drop(services);
+                let healthy = readiness::probe(kind, port).is_ok();
+                let mut services = SERVICES.lock().unwrap();
+                match services.get_mut(&kind) {
+                    Some(svc) if svc.generation == generation => {
+                        update_health(&app, svc, healthy)
+                    }
+                    _ => return,
+                }
+                continue;
+            }
+            Err(_) => continue,
+        };
+
+        // --- Unexpected exit ---
+        svc.child = None;
+        svc.running_since = None;
+        svc.exit_code = exit.code();
+        svc.crash_log = svc.logs.lock().unwrap().iter().cloned().collect();
+        let code = exit
+            .code()
+            .map(|c| c.to_string())
+            .unwrap_or_else(|| "signal".into());
+
+        if svc.attempts >= max_restarts {
+            let message = format!(
+                "❌ {} crashed (exit {}) and was restarted {} times; giving up.",
+                kind.label(),
+                code,
+                svc.attempts
+            );
+            log(&app, kind, LogLevel::Error, &message);
+            svc.set_state(&app, ServiceState::Crashed, Some(message));
+            return;
+        }
+
+        let delay = backoff(svc.attempts);
+        let message = format!(
+            "💥 {} exited unexpectedly (exit {}). Restarting in {}s...",
+            kind.label(),
+            code,
+            delay.as_secs()
+        );
+        log(&app, kind, LogLevel::Warn, &message);
+        svc.set_state(&app, ServiceState::Restarting, Some(message));
+        drop(services);
+
+        thread::sleep(delay);
+
+        let mut services = SERVICES.lock().unwrap();
+        let Some(svc) = services.get_mut(&kind) else {
+            return;
+        };
+        if svc.generation != generation {
+            return;
+        }
+        svc.attempts += 1;
+        svc.restarts += 1;
+        match svc.spawn(&app) {
+            Ok(_) => svc.set_state(&app, ServiceState::Starting, None),
+            Err(e) => {
+                let message = format!("❌ Restart failed: {}", e);
+                log(&app, kind, LogLevel::Error, &message);
+                svc.set_state(&app, ServiceState::Crashed, Some(message));
+                return;
+            }
+        }
+    }
+}
+
+fn update_health(app: &AppHandle, svc: &mut Service, healthy: bool) {
+    match (svc.state, healthy) {
+        (ServiceState::Starting, true) | (ServiceState::Unhealthy, true) => {
+            svc.running_since = Some(Instant::now());
+            svc.set_state(app, ServiceState::Running, None);
+        }
+        (ServiceState::Running, false) => {
+            svc.running_since = None;
+            svc.set_state(
+                app,
+                ServiceState::Unhealthy,
+                Some(format!("⚠ Health check on port {} stopped responding.", svc.spec.port)),
+            );
+        }
+        (ServiceState::Running, true)
+            if svc.running_since.is_some_and(|t| t.elapsed() >= STABLE_AFTER) =>
+        {
+            svc.attempts = 0;
+        }
+        _ => {}
+    }
+}
+
+/// 1s, 2s, 4s, … capped at a minute.
+fn backoff(attempt: u32) -> Duration {
+    Duration::from_secs((1u64 << attempt.min(6)).min(BACKOFF_CAP_SECS))
+}
+
+// === Output capture ===
+
+fn pipe_output<R: Read + Send + 'static>(
+    app: &AppHandle,
+    kind: ServiceKind,
+    stream: R,
+    logs: Arc<Mutex<VecDeque<String>>>,
+) {
+    let app = app.clone();
+    thread::spawn(move || {
+        for line in BufReader::new(stream).lines().map_while(Result::ok) {
+            {
+                let mut tail = logs.lock().unwrap();
+                if tail.len() == LOG_TAIL_LINES {
+                    tail.pop_front();
+                }
+                tail.push_back(line.clone());
+            }
+            log(&app, kind, line_level(&line), &line);
+        }
+    });
+}
+
+/// Level of one line of service output. Ollama logs everything to stderr, so
+/// the stream says nothing; only an explicit `level=WARN` / `level=ERROR`
+/// token raises a line above Info.
+fn line_level(line: &str) -> LogLevel {
+    let token = line
+        .split_whitespace()
+        .find_map(|field| field.strip_prefix("level="))
+        .unwrap_or("")
+        .trim_matches('"');
+    match token.to_ascii_uppercase().as_str() {
+        "ERROR" | "FATAL" => LogLevel::Error,
+        "WARN" | "WARNING" => LogLevel::Warn,
+        _ => LogLevel::Info,
+    }
+}
+
+fn log(app: &AppHandle, kind: ServiceKind, level: LogLevel, message: &str) {
+    app.emit(
+        "component-log",
+        ComponentLog {
+            component: kind.label().into(),
+            message: message.into(),
+            level,
+        },
+    )
+    .ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_a_minute() {
+        let secs: Vec<u64> = (0..8).map(|n| backoff(n).as_secs()).collect();
+        assert_eq!(secs, [1, 2, 4, 8, 16, 32, 60, 60]);
+        assert_eq!(backoff(u32::MAX).as_secs(), BACKOFF_CAP_SECS);
+    }
+
+    #[test]
+    fn output_lines_take_the_services_own_level() {
+        assert_eq!(
+            line_level("time=2026-10-17T10:00:00Z level=INFO source=routes.go:1187 msg=\"Listening on 127.0.0.1:11434\""),
+            LogLevel::Info
+        );
+        assert_eq!(line_level("time=2026-10-17T10:00:00Z level=WARN source=gpu.go:62 msg=\"no GPU\""), LogLevel::Warn);
+        assert_eq!(line_level("time=2026-10-17T10:00:00Z level=ERROR msg=\"load failed\""), LogLevel::Error);
+        assert_eq!(line_level("[GIN] 2026/10/17 - 10:00:01 | 200 | GET \"/api/tags\""), LogLevel::Info);
+        assert_eq!(line_level("Editor is now accessible via: http://localhost:5678"), LogLevel::Info);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn terminate_lets_the_process_exit_on_sigterm() {
+        let mut child = Command::new("sleep").arg("30").spawn().unwrap();
+        let started = Instant::now();
+        terminate(&mut child);
+        let status = child.wait().unwrap();
+
+        assert!(started.elapsed() < STOP_GRACE);
+        // Ended by SIGTERM (15), not by the SIGKILL fallback
+        use std::os::unix::process::ExitStatusExt;
+        assert_eq!(status.signal(), Some(15));
+    }
+}
@@ -1,7 +1,15 @@
 use serde::Serialize;
-use std::path::Path;
-use fs2::available_space;
+use std::path::PathBuf;
 use sysinfo::{System};
+use tauri::{AppHandle, Manager};
+
+use crate::config::AppConfig;
+use crate::installer::n8n_versions::versions_dir;
+use crate::installer::ollama_runtime::ollama_dir;
+use crate::ollama_models::ollama_models_dir;
+use crate::system::volumes::volume_for;
+
+const GIB: u64 = 1024 * 1024 * 1024;
 
 // ✅ Struct to hold validation data
 #[derive(Serialize, Debug)]
@@ -11,7 +19,31 @@ pub struct ValidationResult {
     pub warnings: Vec<String>,
     pub os: String,
     pub ram_gb: u64,
+    /// Free space on the volume holding the Ollama models.
     pub disk_gb: u64,
+    pub volumes: Vec<VolumeCheck>,
+}
+
+// ✅ Something the Workbench writes to, and the space it needs
+#[derive(Serialize, Debug, Clone)]
+pub struct DiskTarget {
+    pub name: String,
+    pub path: String,
+    pub required_gb: u64,
+}
+
+// ✅ One volume with every target on it, checked against their combined needs
+#[derive(Serialize, Debug, Clone)]
+pub struct VolumeCheck {
+    pub mount_point: Option<String>,
+    pub file_system: Option<String>,
+    pub removable: bool,
+    pub network: bool,
+    pub total_gb: u64,
+    pub free_gb: u64,
+    pub required_gb: u64,
+    pub passed: bool,
+    pub targets: Vec<DiskTarget>,
 }
 
 // ✅ Detect the current OS
@@ -21,23 +53,103 @@ fn detect_os() -> String {
 
 // ✅ RAM check (returns GB)
 fn check_ram() -> u64 {
-    let sys = System::new_all();
-    let total = sys.total_memory();
-    // sysinfo gives memory in KiB → convert to GB
-    total / 1024 / 1024
+    let mut sys = System::new();
+    sys.refresh_memory();
+    // sysinfo reports bytes
+    sys.total_memory() / GIB
+}
+
+// ✅ Where the Workbench will write, with what each location needs.
+// `models_gb` is the caller's budget for Ollama models.
+pub(crate) fn disk_targets(app: &AppHandle, models_gb: u64) -> Vec<(String, PathBuf, u64)> {
+    let cfg = AppConfig::load_or_default();
+    let mut targets = Vec::new();
+
+    if let Ok(dir) = app.path().app_data_dir() {
+        // Config, n8n database, backups
+        targets.push(("App data".to_string(), dir.clone(), 2));
+        let runtime = cfg
+            .node_runtime_dir
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| dir.join("runtime"));
+        targets.push(("Node.js runtime".to_string(), runtime, 1));
+    }
+    if !cfg.n8n_use_existing {
+        if let Ok(dir) = versions_dir(app) {
+            // Active and previous version side by side
+            targets.push(("n8n versions".to_string(), dir, 3));
+        }
+    }
+    if let Ok(dir) = ollama_dir(app) {
+        let external = cfg.ollama_path.as_ref().is_some_and(|p| !PathBuf::from(p).starts_with(&dir));
+        if !external {
+            targets.push(("Ollama".to_string(), dir, 3));
+        }
+    }
+    if let Some(dir) = ollama_models_dir() {
+        targets.push(("Ollama models".to_string(), dir, models_gb));
+    }
+    targets
 }
 
-// ✅ Disk space check (returns GB)
-fn check_disk_space<P: AsRef<Path>>(path: P) -> u64 {
-    available_space(path).unwrap_or(0) / 1024 / 1024 / 1024
+// ✅ Group targets by volume and check each volume against their sum
+pub(crate) fn check_volumes(targets: Vec<(String, PathBuf, u64)>) -> Vec<VolumeCheck> {
+    let mut volumes: Vec<VolumeCheck> = Vec::new();
+    for (name, path, required_gb) in targets {
+        let info = volume_for(&path);
+        let target = DiskTarget {
+            name,
+            path: path.to_string_lossy().to_string(),
+            required_gb,
+        };
+        let same = |v: &&mut VolumeCheck| v.mount_point.is_some() && v.mount_point == info.mount_point;
+        match volumes.iter_mut().find(same) {
+            Some(volume) => {
+                volume.required_gb += required_gb;
+                volume.targets.push(target);
+            }
+            None => volumes.push(VolumeCheck {
+                mount_point: info.mount_point,
+                file_system: info.file_system,
+                removable: info.removable,
+                network: info.network,
+                total_gb: info.total_bytes.unwrap_or(0) / GIB,
+                free_gb: info.free_bytes.unwrap_or(0) / GIB,
+                required_gb,
+                passed: false,
+                targets: vec![target],
+            }),
+        }
+    }
+    for volume in &mut volumes {
+        volume.passed = volume.free_gb >= volume.required_gb;
+    }
+    volumes
+}
+
+fn describe(volume: &VolumeCheck) -> String {
+    let mount = volume
+        .mount_point
+        .clone()
+        .unwrap_or_else(|| volume.targets[0].path.clone());
+    match &volume.file_system {
+        Some(fs) => format!("{} ({})", mount, fs),
+        None => mount,
+    }
 }
 
 // ✅ Main command exposed to frontend
 #[tauri::command]
-pub fn validate_requirements(min_ram: u64, min_disk: u64) -> ValidationResult {
+pub fn validate_requirements(app: AppHandle, min_ram: u64, min_disk: u64) -> ValidationResult {
     let os = detect_os();
     let ram_gb = check_ram();
-    let disk_gb = check_disk_space("C:\\");
+    let volumes = check_volumes(disk_targets(&app, min_disk));
+    let disk_gb = volumes
+        .iter()
+        .find(|v| v.targets.iter().any(|t| t.name == "Ollama models"))
+        .map(|v| v.free_gb)
+        .unwrap_or(0);
 
     let mut issues = vec![];
     let mut warnings = vec![];
@@ -49,11 +161,31 @@ pub fn validate_requirements(min_ram: u64, min_disk: u64) -> ValidationResult {
         ));
     }
 
-    if disk_gb < min_disk {
-        issues.push(format!(
-            "Low disk space: {} GB (found) < {} GB (required)",
-            disk_gb, min_disk
-        ));
+    for volume in &volumes {
+        let names: Vec<&str> = volume.targets.iter().map(|t| t.name.as_str()).collect();
+        if !volume.passed {
+            issues.push(format!(
+                "Low disk space on {}: {} GB (found) < {} GB (required for {})",
+                describe(volume),
+                volume.free_gb,
+                volume.required_gb,
+                names.join(", ")
+            ));
+        }
+        if volume.network {
+            warnings.push(format!(
+                "{} is on a network volume ({}); databases and model loading may be slow or unreliable",
+                names.join(", "),
+                describe(volume)
+            ));
+        }
+        if volume.removable {
+            warnings.push(format!(
+                "{} is on removable media ({}); the Workbench stops working when it is unplugged",
+                names.join(", "),
+                describe(volume)
+            ));
+        }
     }
 
     if os != "windows" && os != "macos" && os != "linux" {
@@ -67,5 +199,41 @@ pub fn validate_requirements(min_ram: u64, min_disk: u64) -> ValidationResult {
         os,
         ram_gb,
         disk_gb,
+        volumes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn targets_on_one_volume_share_a_single_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let targets = vec![
+            ("App data".to_string(), dir.path().join("data"), 2),
+            ("Ollama models".to_string(), dir.path().join("models").join("blobs"), 20),
+        ];
+
+        let volumes = check_volumes(targets);
+        assert_eq!(volumes.len(), 1);
+        let volume = &volumes[0];
+        assert!(volume.mount_point.is_some());
+        assert_eq!(volume.required_gb, 22);
+        let names: Vec<&str> = volume.targets.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["App data", "Ollama models"]);
+        assert_eq!(volume.passed, volume.free_gb >= 22);
+    }
+
+    #[test]
+    fn a_volume_fails_when_the_combined_need_exceeds_free_space() {
+        let dir = tempfile::tempdir().unwrap();
+        let volumes = check_volumes(vec![
+            ("n8n versions".to_string(), dir.path().join("a"), 3),
+            ("Ollama models".to_string(), dir.path().join("b"), u64::MAX / 2),
+        ]);
+        assert_eq!(volumes.len(), 1);
+        assert_eq!(volumes[0].required_gb, u64::MAX / 2 + 3);
+        assert!(!volumes[0].passed);
     }
 }
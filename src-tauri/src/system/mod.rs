@@ -1 +1,2 @@
-pub mod detector;
\ No newline at end of file
+pub mod detector;
+pub mod volumes;
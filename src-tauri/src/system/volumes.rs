@@ -0,0 +1,247 @@
+// 🧩 Gignaati Workbench — Volume inspection
+//
+// Resolves the volume a path lives on: mount point, filesystem type, free
+// and total space, and whether it is removable or network-backed. Linux
+// reads /proc/self/mounts (sysinfo hides NFS / CIFS mounts), macOS parses
+// `mount`, Windows uses sysinfo's drive list.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use sysinfo::Disks;
+
+/// Filesystems served over the network.
+const NETWORK_FS: &[&str] = &[
+    "nfs", "nfs4", "cifs", "smb", "smbfs", "smb3", "afpfs", "webdav", "davfs", "fuse.sshfs", "sshfs",
+    "9p", "ceph", "glusterfs", "fuse.glusterfs", "lustre",
+];
+
+#[derive(Serialize, Debug, Clone)]
+pub struct VolumeInfo {
+    pub mount_point: Option<String>,
+    pub file_system: Option<String>,
+    pub removable: bool,
+    pub network: bool,
+    pub total_bytes: Option<u64>,
+    pub free_bytes: Option<u64>,
+}
+
+struct Mount {
+    mount_point: PathBuf,
+    file_system: String,
+    /// Set when the mount table says so (macOS `local` flag).
+    network: Option<bool>,
+}
+
+/// Volume holding `path`. The path does not have to exist yet; its nearest
+/// existing ancestor is used.
+pub fn volume_for(path: &Path) -> VolumeInfo {
+    let existing = path
+        .ancestors()
+        .find(|p| p.exists())
+        .map(|p| strip_verbatim(p.canonicalize().unwrap_or_else(|_| p.to_path_buf())));
+    let Some(existing) = existing else {
+        return VolumeInfo {
+            mount_point: None,
+            file_system: None,
+            removable: false,
+            network: is_unc(path),
+            total_bytes: None,
+            free_bytes: None,
+        };
+    };
+
+    let disks = Disks::new_with_refreshed_list();
+    let disk = disks
+        .list()
+        .iter()
+        .filter(|d| existing.starts_with(d.mount_point()))
+        .max_by_key(|d| d.mount_point().as_os_str().len());
+
+    let mount = mount_for(&existing).or_else(|| {
+        disk.map(|d| Mount {
+            mount_point: d.mount_point().to_path_buf(),
+            file_system: d.file_system().to_string_lossy().to_string(),
+            network: None,
+        })
+    });
+    // Only trust sysinfo's removable flag for the same mount
+    let removable = match (&mount, disk) {
+        (Some(m), Some(d)) => m.mount_point == d.mount_point() && d.is_removable(),
+        (None, Some(d)) => d.is_removable(),
+        _ => false,
+    };
+    let network = is_unc(&existing)
+        || mount.as_ref().is_some_and(|m| {
+            m.network
+                .unwrap_or_else(|| NETWORK_FS.contains(&m.file_system.to_lowercase().as_str()))
+        });
+
+    VolumeInfo {
+        mount_point: mount.as_ref().map(|m| m.mount_point.to_string_lossy().to_string()),
+        file_system: mount.map(|m| m.file_system),
+        removable,
+        network,
+        total_bytes: fs2::total_space(&existing).ok(),
+        free_bytes: fs2::available_space(&existing).ok(),
+    }
+}
+
+fn is_unc(path: &Path) -> bool {
+    if !cfg!(target_os = "windows") {
+        return false;
+    }
+    let text = path.to_string_lossy();
+    text.starts_with(r"\\?\UNC\") || (text.starts_with(r"\\") && !text.starts_with(r"\\?\"))
+}
+
+/// Windows `canonicalize` returns `\\?\C:\...`; drive letters are what the
+/// drive list reports.
+fn strip_verbatim(path: PathBuf) -> PathBuf {
+    let simplified = path
+        .to_str()
+        .and_then(|text| text.strip_prefix(r"\\?\"))
+        .filter(|rest| cfg!(target_os = "windows") && !rest.starts_with("UNC\\"))
+        .map(PathBuf::from);
+    simplified.unwrap_or(path)
+}
+
+/// Longest mount point containing `path` from the OS mount table.
+#[cfg(target_os = "linux")]
+fn mount_for(path: &Path) -> Option<Mount> {
+    let table = std::fs::read_to_string("/proc/self/mounts").ok()?;
+    longest_mount(parse_proc_mounts(&table), path)
+}
+
+/// `device mount_point fs_type options ...` lines of /proc/self/mounts.
+#[cfg(target_os = "linux")]
+fn parse_proc_mounts(table: &str) -> Vec<Mount> {
+    table
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let _device = fields.next()?;
+            let mount_point = PathBuf::from(unescape_mount(fields.next()?));
+            let file_system = fields.next()?.to_string();
+            Some(Mount {
+                mount_point,
+                file_system,
+                network: None,
+            })
+        })
+        .collect()
+}
+
+/// `/proc/mounts` escapes spaces and tabs as octal (`\040`).
+#[cfg(target_os = "linux")]
+fn unescape_mount(field: &str) -> String {
+    field
+        .replace("\\040", " ")
+        .replace("\\011", "\t")
+        .replace("\\012", "\n")
+        .replace("\\134", "\\")
+}
+
+#[cfg(target_os = "macos")]
+fn mount_for(path: &Path) -> Option<Mount> {
+    let output = std::process::Command::new("mount").output().ok()?;
+    longest_mount(parse_mount_output(&String::from_utf8_lossy(&output.stdout)), path)
+}
+
+/// `/dev/disk3s1 on / (apfs, local, journaled)` lines from `mount`.
+#[cfg(target_os = "macos")]
+fn parse_mount_output(table: &str) -> Vec<Mount> {
+    table
+        .lines()
+        .filter_map(|line| {
+            let (_, rest) = line.split_once(" on ")?;
+            let (mount_point, options) = rest.rsplit_once(" (")?;
+            let options: Vec<&str> = options.trim_end_matches(')').split(", ").collect();
+            Some(Mount {
+                mount_point: PathBuf::from(mount_point),
+                file_system: options.first()?.to_string(),
+                network: Some(!options.contains(&"local")),
+            })
+        })
+        .collect()
+}
+
+/// The most specific mount containing `path` (`/home` wins over `/`).
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn longest_mount(mounts: Vec<Mount>, path: &Path) -> Option<Mount> {
+    mounts
+        .into_iter()
+        .filter(|m| path.starts_with(&m.mount_point))
+        .max_by_key(|m| m.mount_point.as_os_str().len())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn mount_for(_path: &Path) -> Option<Mount> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    const PROC_MOUNTS: &str = "\
+sysfs /sys sysfs rw,nosuid,nodev,noexec,relatime 0 0
+/dev/nvme0n1p2 / ext4 rw,relatime 0 0
+/dev/nvme0n1p3 /home ext4 rw,relatime 0 0
+/dev/sdb1 /media/bob/My\\040Disk vfat rw,nosuid,nodev 0 0
+//nas/share /home/bob/nas cifs rw,relatime 0 0
+";
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn unescape_mount_decodes_octal_escapes() {
+        assert_eq!(unescape_mount(r"/media/bob/My\040Disk"), "/media/bob/My Disk");
+        assert_eq!(unescape_mount(r"/mnt/tab\011and\134slash"), "/mnt/tab\tand\\slash");
+        assert_eq!(unescape_mount("/plain"), "/plain");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn the_longest_containing_mount_wins() {
+        let pick = |path: &str| {
+            longest_mount(parse_proc_mounts(PROC_MOUNTS), Path::new(path))
+                .map(|m| (m.mount_point.to_string_lossy().to_string(), m.file_system))
+        };
+        assert_eq!(pick("/home/bob/.ollama"), Some(("/home".into(), "ext4".into())));
+        assert_eq!(pick("/home/bob/nas/models"), Some(("/home/bob/nas".into(), "cifs".into())));
+        assert_eq!(pick("/media/bob/My Disk/n8n"), Some(("/media/bob/My Disk".into(), "vfat".into())));
+        assert_eq!(pick("/opt/app"), Some(("/".into(), "ext4".into())));
+        // Whole path components only: /homework is not under /home
+        assert_eq!(pick("/homework"), Some(("/".into(), "ext4".into())));
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn mount_output_marks_non_local_volumes_as_network() {
+        let table = "/dev/disk3s1s1 on / (apfs, sealed, local, read-only, journaled)\n\
+                     //bob@nas/share on /Volumes/share (smbfs, nodev, nosuid, mounted by bob)\n";
+        let share = longest_mount(parse_mount_output(table), Path::new("/Volumes/share/models")).unwrap();
+        assert_eq!(share.file_system, "smbfs");
+        assert_eq!(share.network, Some(true));
+        let root = longest_mount(parse_mount_output(table), Path::new("/Users/bob")).unwrap();
+        assert_eq!(root.network, Some(false));
+    }
+
+    #[test]
+    fn verbatim_and_unc_prefixes_are_windows_only() {
+        let verbatim = PathBuf::from(r"\\?\C:\Users\bob");
+        let unc = PathBuf::from(r"\\?\UNC\nas\share");
+        if cfg!(target_os = "windows") {
+            assert_eq!(strip_verbatim(verbatim), PathBuf::from(r"C:\Users\bob"));
+            assert_eq!(strip_verbatim(unc.clone()), unc);
+            assert!(is_unc(&unc));
+            assert!(is_unc(Path::new(r"\\nas\share\models")));
+            assert!(!is_unc(Path::new(r"\\?\C:\Users")));
+            assert!(!is_unc(Path::new(r"C:\Users")));
+        } else {
+            assert_eq!(strip_verbatim(verbatim.clone()), verbatim);
+            assert!(!is_unc(&unc));
+            assert!(!is_unc(Path::new(r"\\nas\share")));
+        }
+    }
+}
@@ -0,0 +1,102 @@
+// 🧪 Test helpers — a throwaway HTTP mirror on 127.0.0.1
+//
+// Serves canned replies per path so download, provisioning and Ollama client
+// code can be exercised offline. Each `serve` call gets its own port; the
+// server thread lives until the test process exits.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+#[derive(Clone)]
+pub enum Reply {
+    /// Full response with the given status and body.
+    Body(u16, Vec<u8>),
+    /// Announces `declared` bytes but sends only `body`, then hangs up.
+    Truncated { body: Vec<u8>, declared: usize },
+    /// Sends `body` with no declared length, then goes silent for `pause`.
+    Stall { body: Vec<u8>, pause: Duration },
+}
+
+impl Reply {
+    pub fn ok(body: impl Into<Vec<u8>>) -> Self {
+        Reply::Body(200, body.into())
+    }
+}
+
+/// Start a server answering `routes` (path → reply; anything else is 404).
+/// Returns the base URL, e.g. `http://127.0.0.1:40123`.
+pub fn serve(routes: Vec<(&str, Reply)>) -> String {
+    let routes: HashMap<String, Reply> = routes
+        .into_iter()
+        .map(|(path, reply)| (path.to_string(), reply))
+        .collect();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+
+    thread::spawn(move || {
+        for stream in listener.incoming().map_while(Result::ok) {
+            let routes = routes.clone();
+            thread::spawn(move || handle(stream, &routes));
+        }
+    });
+    base
+}
+
+fn handle(mut stream: TcpStream, routes: &HashMap<String, Reply>) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    let path = request_line.split_whitespace().nth(1).unwrap_or("/").to_string();
+
+    // Drain headers and any body so the client is not reset mid-write
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    let _ = reader.read_exact(&mut body);
+
+    let reply = routes
+        .get(&path)
+        .cloned()
+        .unwrap_or_else(|| Reply::Body(404, b"not found".to_vec()));
+    match reply {
+        Reply::Body(status, body) => {
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                status,
+                body.len()
+            );
+            let _ = stream.write_all(&body);
+        }
+        Reply::Truncated { body, declared } => {
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                declared
+            );
+            let _ = stream.write_all(&body);
+        }
+        Reply::Stall { body, pause } => {
+            let _ = write!(stream, "HTTP/1.1 200 OK\r\nConnection: close\r\n\r\n");
+            let _ = stream.write_all(&body);
+            let _ = stream.flush();
+            thread::sleep(pause);
+        }
+    }
+    let _ = stream.flush();
+}
@@ -0,0 +1,379 @@
+// 🧩 Gignaati Workbench — Versions & compatibility matrix
+//
+// Turns each tool's `--version` output into a semver version and checks the
+// installed set against the matrix below: which Node.js an n8n release runs
+// on, the oldest n8n / Ollama the Workbench supports, and the Ollama release
+// that introduced a model family. Every check yields a verdict the UI and
+// the installer can act on: ok, upgrade recommended, or blocking.
+
+use semver::{Prerelease, Version, VersionReq};
+use serde::Serialize;
+
+use crate::config::AppConfig;
+use crate::discovery::{self, Tool};
+use crate::installer::install_n8n_real::installed_n8n;
+use crate::installer::node_runtime::managed_runtime;
+
+/// Node.js per n8n release line: (n8n range, supported Node, recommended Node).
+const N8N_NODE: &[(&str, &str, &str)] = &[
+    (">=1.0.0", ">=18.17.0, <23.0.0", ">=20.0.0"),
+    ("<1.0.0", ">=16.9.0, <19.0.0", ">=18.0.0"),
+];
+
+/// Node.js when no n8n is installed yet (blocking, recommended).
+const NODE: (&str, &str) = (">=18.0.0", ">=20.0.0");
+/// n8n releases the Workbench manages (blocking, recommended).
+const N8N: (&str, &str) = (">=0.200.0", ">=1.0.0");
+/// Ollama releases the Workbench talks to (blocking, recommended).
+const OLLAMA: (&str, &str) = (">=0.1.33", ">=0.5.0");
+
+/// Oldest Ollama that can run a model family.
+const MODEL_OLLAMA: &[(&str, &str)] = &[
+    ("gemma2", "0.1.47"),
+    ("gemma3", "0.6.0"),
+    ("phi3", "0.1.39"),
+    ("phi4", "0.5.5"),
+    ("mistral-nemo", "0.2.8"),
+    ("llama3.1", "0.3.0"),
+    ("llama3.2", "0.3.12"),
+    ("llama3.2-vision", "0.4.0"),
+    ("llama3.3", "0.5.0"),
+    ("qwen2.5", "0.3.10"),
+    ("qwen2.5-coder", "0.3.10"),
+    ("qwen3", "0.6.6"),
+    ("deepseek-r1", "0.5.7"),
+];
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Ok,
+    UpgradeRecommended,
+    Blocking,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Finding {
+    /// `node`, `n8n`, `ollama` or `model:<name>`.
+    pub component: String,
+    pub found: Option<String>,
+    /// Range the verdict was checked against.
+    pub required: Option<String>,
+    pub verdict: Verdict,
+    pub message: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct CompatibilityReport {
+    /// Worst verdict across `findings`.
+    pub verdict: Verdict,
+    pub findings: Vec<Finding>,
+}
+
+// === Parsing ===
+
+/// First version-looking token in `raw`: `v20.18.0`, `10.8.2`,
+/// `ollama version is 0.3.12`, `1.64.3`. Missing minor / patch count as 0.
+pub fn parse(raw: &str) -> Option<Version> {
+    raw.split(|c: char| c.is_whitespace() || c == ',' || c == '(' || c == ')')
+        .find_map(parse_token)
+}
+
+/// Version from a tool's `--version` output.
+pub fn parse_output(tool: Tool, raw: &str) -> Option<Version> {
+    match tool {
+        // With no server running Ollama prints "Warning: client version is X";
+        // with a different server running, "ollama version is <server>" comes
+        // first. The client line is the binary's own version.
+        Tool::Ollama => raw
+            .lines()
+            .find(|line| line.contains("client version"))
+            .and_then(parse)
+            .or_else(|| parse(raw)),
+        Tool::Node | Tool::Npm | Tool::N8n => parse(raw),
+    }
+}
+
+fn parse_token(token: &str) -> Option<Version> {
+    let token = token
+        .trim_start_matches(['v', 'V'])
+        .trim_end_matches(['.', ':', ';']);
+    if !token.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let (core, rest) = match token.find(['-', '+']) {
+        Some(i) => token.split_at(i),
+        None => (token, ""),
+    };
+    let mut parts: Vec<&str> = core.split('.').collect();
+    if parts.len() > 3 || parts.iter().any(|p| p.is_empty() || !p.bytes().all(|b| b.is_ascii_digit())) {
+        return None;
+    }
+    parts.resize(3, "0");
+    Version::parse(&format!("{}{}", parts.join("."), rest)).ok()
+}
+
+/// `range` from the matrix matches `version`. Prereleases are checked as
+/// their release (semver ranges would otherwise never match `1.65.0-rc.1`).
+fn matches(range: &str, version: &Version) -> bool {
+    let req = VersionReq::parse(range).expect("compatibility matrix ranges are valid");
+    let mut release = version.clone();
+    release.pre = Prerelease::EMPTY;
+    req.matches(&release)
+}
+
+// === Checks ===
+
+fn finding(
+    component: &str,
+    found: &Version,
+    required: &str,
+    verdict: Verdict,
+    message: String,
+) -> Finding {
+    Finding {
+        component: component.to_string(),
+        found: Some(found.to_string()),
+        required: Some(required.to_string()),
+        verdict,
+        message,
+    }
+}
+
+/// Node.js against the n8n it has to run (or the general minimum).
+pub fn check_node(node: &Version, n8n: Option<&Version>) -> Finding {
+    let (supported, recommended) = match n8n {
+        Some(n8n) => N8N_NODE
+            .iter()
+            .find(|(range, _, _)| matches(range, n8n))
+            .map(|(_, supported, recommended)| (*supported, *recommended))
+            .unwrap_or(NODE),
+        None => NODE,
+    };
+    let target = n8n.map(|v| format!("n8n {}", v)).unwrap_or_else(|| "n8n".into());
+
+    if !matches(supported, node) {
+        finding(
+            "node",
+            node,
+            supported,
+            Verdict::Blocking,
+            format!("Node.js {} is not supported by {}; it needs {}.", node, target, supported),
+        )
+    } else if !matches(recommended, node) {
+        finding(
+            "node",
+            node,
+            recommended,
+            Verdict::UpgradeRecommended,
+            format!("Node.js {} works, but {} is recommended for {}.", node, recommended, target),
+        )
+    } else {
+        finding("node", node, supported, Verdict::Ok, format!("Node.js {} is compatible.", node))
+    }
+}
+
+/// n8n against the releases the Workbench supports.
+pub fn check_n8n(n8n: &Version) -> Finding {
+    check_minimum("n8n", "n8n", n8n, N8N)
+}
+
+/// Ollama against the releases the Workbench supports.
+pub fn check_ollama(ollama: &Version) -> Finding {
+    check_minimum("ollama", "Ollama", ollama, OLLAMA)
+}
+
+fn check_minimum(component: &str, name: &str, found: &Version, (minimum, recommended): (&str, &str)) -> Finding {
+    if !matches(minimum, found) {
+        finding(
+            component,
+            found,
+            minimum,
+            Verdict::Blocking,
+            format!("{} {} is no longer supported; upgrade to {}.", name, found, recommended),
+        )
+    } else if !matches(recommended, found) {
+        finding(
+            component,
+            found,
+            recommended,
+            Verdict::UpgradeRecommended,
+            format!("{} {} works, but {} is recommended.", name, found, recommended),
+        )
+    } else {
+        finding(component, found, minimum, Verdict::Ok, format!("{} {} is compatible.", name, found))
+    }
+}
+
+/// Minimum Ollama for a model's family (`llama3.2:3b` → `llama3.2`);
+/// `None` for families the matrix does not list.
+pub fn model_minimum(model: &str) -> Option<Version> {
+    let name = model.split(':').next().unwrap_or(model);
+    let family = name.rsplit('/').next().unwrap_or(name);
+    MODEL_OLLAMA
+        .iter()
+        .find(|(f, _)| f.eq_ignore_ascii_case(family))
+        .and_then(|(_, minimum)| Version::parse(minimum).ok())
+}
+
+/// Can the installed Ollama run `model`?
+pub fn check_model(model: &str, ollama: &Version) -> Option<Finding> {
+    let minimum = model_minimum(model)?;
+    let required = format!(">={}", minimum);
+    let component = format!("model:{}", model);
+    Some(if !matches(&required, ollama) {
+        finding(
+            &component,
+            ollama,
+            &required,
+            Verdict::Blocking,
+            format!("'{}' needs Ollama {} or newer; {} is installed.", model, minimum, ollama),
+        )
+    } else {
+        finding(
+            &component,
+            ollama,
+            &required,
+            Verdict::Ok,
+            format!("Ollama {} can run '{}'.", ollama, model),
+        )
+    })
+}
+
+// === Installed components ===
+
+/// Version of the preferred discovered binary for `tool`.
+pub fn located(tool: Tool) -> Option<Version> {
+    discovery::locate_candidate(tool)
+        .and_then(|c| c.version)
+        .and_then(|raw| parse_output(tool, &raw))
+}
+
+/// Version of the n8n the Workbench launches: the managed version recorded in
+/// `cfg`, else whatever the opted-in existing install reports.
+pub fn launched_n8n(cfg: &AppConfig) -> Option<Version> {
+    cfg.n8n_version.as_deref().and_then(parse).or_else(|| {
+        installed_n8n(managed_runtime().as_ref()).and_then(|(_, raw)| parse_output(Tool::N8n, &raw))
+    })
+}
+
+/// Parsed versions of what is installed; `None` when missing or unparsable.
+#[derive(Debug, Clone, Default)]
+pub struct Installed {
+    pub node: Option<Version>,
+    pub n8n: Option<Version>,
+    pub ollama: Option<Version>,
+}
+
+impl Installed {
+    pub fn detect() -> Self {
+        Installed {
+            node: located(Tool::Node),
+            n8n: installed_n8n(managed_runtime().as_ref()).and_then(|(_, raw)| parse_output(Tool::N8n, &raw)),
+            ollama: located(Tool::Ollama),
+        }
+    }
+
+    /// Findings for every installed component and for `models`. Missing
+    /// components are not findings; they are reported as not installed.
+    pub fn check(&self, models: &[String]) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        if let Some(node) = &self.node {
+            findings.push(check_node(node, self.n8n.as_ref()));
+        }
+        if let Some(n8n) = &self.n8n {
+            findings.push(check_n8n(n8n));
+        }
+        if let Some(ollama) = &self.ollama {
+            findings.push(check_ollama(ollama));
+            findings.extend(models.iter().filter_map(|m| check_model(m, ollama)));
+        }
+        findings
+    }
+}
+
+pub fn report(findings: Vec<Finding>) -> CompatibilityReport {
+    CompatibilityReport {
+        verdict: findings.iter().map(|f| f.verdict).max().unwrap_or(Verdict::Ok),
+        findings,
+    }
+}
+
+/// Findings for what is installed, including the default model.
+pub fn check_installed() -> Vec<Finding> {
+    let models: Vec<String> = AppConfig::load_or_default()
+        .ollama_default_model
+        .into_iter()
+        .collect();
+    Installed::detect().check(&models)
+}
+
+// === Commands ===
+
+/// 🧮 Check installed Node.js, n8n, Ollama (and `models`, default: the
+/// configured default model) against the compatibility matrix
+#[tauri::command]
+pub fn check_compatibility(models: Option<Vec<String>>) -> CompatibilityReport {
+    let findings = match models {
+        Some(models) => Installed::detect().check(&models),
+        None => check_installed(),
+    };
+    report(findings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(raw: &str) -> Version {
+        Version::parse(raw).unwrap()
+    }
+
+    #[test]
+    fn parses_tool_output() {
+        assert_eq!(parse("v20.18.0"), Some(v("20.18.0")));
+        assert_eq!(parse("10.8.2\n"), Some(v("10.8.2")));
+        assert_eq!(parse("ollama version is 0.3.12"), Some(v("0.3.12")));
+        assert_eq!(parse("1.64"), Some(v("1.64.0")));
+        assert_eq!(parse("n8n 1.65.0-rc.1"), Some(v("1.65.0-rc.1")));
+        assert_eq!(parse("version: unknown"), None);
+        assert_eq!(parse_token("1.2.3.4"), None);
+    }
+
+    #[test]
+    fn ollama_reports_the_client_version() {
+        let raw = "ollama version is 0.5.7\nWarning: client version is 0.6.2";
+        assert_eq!(parse_output(Tool::Ollama, raw), Some(v("0.6.2")));
+        assert_eq!(
+            parse_output(Tool::Ollama, "Warning: could not connect to a running Ollama instance\nWarning: client version is 0.5.4"),
+            Some(v("0.5.4"))
+        );
+        assert_eq!(parse_output(Tool::Ollama, "ollama version is 0.3.12"), Some(v("0.3.12")));
+    }
+
+    #[test]
+    fn node_is_checked_against_the_n8n_release_line() {
+        let n8n_1 = v("1.64.3");
+        let n8n_0 = v("0.236.0");
+        assert_eq!(check_node(&v("20.18.0"), Some(&n8n_1)).verdict, Verdict::Ok);
+        assert_eq!(check_node(&v("18.19.0"), Some(&n8n_1)).verdict, Verdict::UpgradeRecommended);
+        assert_eq!(check_node(&v("23.1.0"), Some(&n8n_1)).verdict, Verdict::Blocking);
+        assert_eq!(check_node(&v("16.20.0"), Some(&n8n_0)).verdict, Verdict::UpgradeRecommended);
+        assert_eq!(check_node(&v("20.18.0"), Some(&n8n_0)).verdict, Verdict::Blocking);
+        // Prereleases use their release's row
+        assert_eq!(check_node(&v("20.18.0"), Some(&v("1.0.0-rc.1"))).verdict, Verdict::Ok);
+        assert_eq!(check_node(&v("16.20.0"), None).verdict, Verdict::Blocking);
+    }
+
+    #[test]
+    fn model_families_need_their_minimum_ollama() {
+        assert_eq!(model_minimum("library/deepseek-r1:7b"), Some(v("0.5.7")));
+        assert_eq!(model_minimum("tinyllama"), None);
+
+        let verdict = |model: &str, ollama: &str| check_model(model, &v(ollama)).map(|f| f.verdict);
+        assert_eq!(verdict("deepseek-r1:7b", "0.5.7"), Some(Verdict::Ok));
+        assert_eq!(verdict("deepseek-r1:7b", "0.5.7-rc0"), Some(Verdict::Ok));
+        assert_eq!(verdict("deepseek-r1:7b", "0.5.6"), Some(Verdict::Blocking));
+        assert_eq!(verdict("tinyllama", "0.1.0"), None);
+    }
+}
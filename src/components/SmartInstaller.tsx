@@ -13,6 +13,35 @@ type ComponentProgress = {
 type ComponentLog = {
   component: string;
   message: string;
+  level?: "info" | "warn" | "error";
+};
+
+type OllamaModel = {
+  name: string;
+  tag: string;
+  size: number;
+  digest: string;
+  modified_at: string;
+  parameter_size: string | null;
+  quantization_level: string | null;
+  family: string | null;
+};
+
+type ModelPullProgress = {
+  model: string;
+  state: "queued" | "downloading" | "retrying" | "paused" | "completed" | "failed" | "cancelled";
+  status: string;
+  digest: string | null;
+  completed: number;
+  total: number;
+  speed_bytes_per_sec: number;
+  eta_seconds: number | null;
+};
+
+type CleanupReport = {
+  dry_run: boolean;
+  items: { target: string; component: string; path: string | null; bytes: number }[];
+  total_bytes: number;
 };
 
 export default function SmartInstaller() {
@@ -64,14 +93,6 @@ export default function SmartInstaller() {
 
       // Ollama progress tracking
       if (component.includes("Ollama Model Pull")) {
-        const match = message.match(/(\d+)%/);
-        if (match) {
-          const pct = Number(match[1]);
-          setModelProgress((prev) => (prev !== null && pct < prev ? prev : pct));
-          setModelStatus(`📦 Downloading: ${pct}%`);
-          setDownloadActive(true);
-        }
-
         if (message.includes("✅ Finished pulling")) {
           setModelProgress(100);
           setModelStatus("✅ Model pulled successfully!");
@@ -118,6 +139,18 @@ export default function SmartInstaller() {
       }
     });
 
+    // Structured per-model pull progress
+    const unPull = listen<ModelPullProgress>("model-pull-progress", (e: Event<ModelPullProgress>) => {
+      const p = e.payload;
+      if (p.state !== "downloading" || !p.total) return;
+      const pct = Math.floor((p.completed / p.total) * 100);
+      const speed = p.speed_bytes_per_sec ? ` @ ${(p.speed_bytes_per_sec / 1024 / 1024).toFixed(1)} MB/s` : "";
+      const eta = p.eta_seconds != null ? `, ~${p.eta_seconds}s left` : "";
+      setModelProgress(pct);
+      setModelStatus(`📦 ${p.model}: ${pct}%${speed}${eta}`);
+      setDownloadActive(true);
+    });
+
     const unC = listen<ComponentLog>("smart-complete", (e: Event<ComponentLog>) => {
       setLogs((prev) => [...prev, `[${e.payload.component}] ${e.payload.message}`]);
       setRunning(false);
@@ -129,6 +162,7 @@ export default function SmartInstaller() {
       unP.then((u) => u());
       unL.then((u) => u());
       unC.then((u) => u());
+      unPull.then((u) => u());
       unNode.then((u) => u());
     };
   }, [selectedModel]);
@@ -160,7 +194,15 @@ export default function SmartInstaller() {
     setLogs(["🧹 Starting cleanup..."]);
     setRunning(true);
     try {
-      await invoke("cleanup_installation");
+      // Preview first: nothing is removed until the user confirms the plan
+      const plan: CleanupReport = await invoke("cleanup_installation", { options: { dry_run: true } });
+      const lines = plan.items.map((i) => `• ${i.component}${i.path ? ` — ${i.path}` : ""}`);
+      const freed = (plan.total_bytes / 1024 / 1024).toFixed(1);
+      if (!window.confirm(`The following will be removed (~${freed} MB):\n\n${lines.join("\n")}\n\nContinue?`)) {
+        setLogs((l) => [...l, "ℹ Cleanup cancelled."]);
+        return;
+      }
+      await invoke("cleanup_installation", { options: { dry_run: false } });
     } catch (err) {
       setLogs((l) => [...l, `Error during cleanup: ${String(err)}`]);
     } finally {
@@ -213,9 +255,10 @@ export default function SmartInstaller() {
 
   const fetchModels = async () => {
     try {
-      const models: string[] = await invoke("list_ollama_models");
-      setAvailableModels(models);
-      setLogs((prev) => [...prev, `[Ollama] 📦 Available models:\n${models.join(", ")}`]);
+      const models: OllamaModel[] = await invoke("list_ollama_models");
+      const names = models.map((m) => `${m.name}:${m.tag}`);
+      setAvailableModels(names);
+      setLogs((prev) => [...prev, `[Ollama] 📦 Available models:\n${names.join(", ")}`]);
     } catch (err) {
       setLogs((prev) => [...prev, `[Ollama] ❌ Failed to list models: ${String(err)}`]);
     }
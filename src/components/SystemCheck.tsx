@@ -11,11 +11,25 @@ interface ValidationResult {
   os: string;
   ram_gb: number;
   disk_gb: number;
+  volumes: VolumeCheck[];
+}
+
+interface VolumeCheck {
+  mount_point: string | null;
+  file_system: string | null;
+  removable: boolean;
+  network: boolean;
+  total_gb: number;
+  free_gb: number;
+  required_gb: number;
+  passed: boolean;
+  targets: { name: string; path: string; required_gb: number }[];
 }
 
 interface PortConfig {
   n8n_port: number;
   ollama_port: number;
+  extra: Record<string, number>;
 }
 
 export default function SystemCheck() {
@@ -26,7 +40,7 @@ export default function SystemCheck() {
 
   // ✅ Automatically allocate ports when component mounts
   useEffect(() => {
-    invoke<PortConfig>("allocate_ports")
+    invoke<PortConfig>("allocate_ports_command")
       .then((result) => {
         console.log("Port Allocation Result:", result);
         setPorts(result);
@@ -136,9 +150,14 @@ export default function SystemCheck() {
           <Text>
             <strong>RAM:</strong> {status.ram_gb} GB
           </Text>
-          <Text>
-            <strong>Disk:</strong> {status.disk_gb} GB
-          </Text>
+          {status.volumes.map((volume, idx) => (
+            <Text key={idx} variant={volume.passed ? "body" : "error"}>
+              <strong>Disk {volume.mount_point ?? volume.targets[0]?.path}</strong>
+              {volume.file_system && ` (${volume.file_system})`}: {volume.free_gb} GB free,{" "}
+              {volume.required_gb} GB needed for{" "}
+              {volume.targets.map((t) => t.name).join(", ")}
+            </Text>
+          ))}
 
           {status.issues.length > 0 && (
             <div style={{ marginTop: "var(--space-4)" }}>